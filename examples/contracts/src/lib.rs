@@ -53,7 +53,7 @@ async fn deploy_contract() {
 
     // Here is an instance of your contract which you can use to make calls to
     // your functions
-    let contract_instance = MyContract::new(contract_id.to_string(), wallet);
+    let contract_instance = MyContract::new(contract_id, wallet);
 
     let result = contract_instance
         .initialize_counter(42) // Build the ABI call
@@ -138,7 +138,7 @@ async fn deploy_with_multiple_wallets() {
         .unwrap();
 
     println!("Contract deployed @ {:x}", contract_id_1);
-    let contract_instance_1 = MyContract::new(contract_id_1.to_string(), wallets[0].clone());
+    let contract_instance_1 = MyContract::new(contract_id_1, wallets[0].clone());
 
     let result = contract_instance_1
         .initialize_counter(42) // Build the ABI call
@@ -158,7 +158,7 @@ async fn deploy_with_multiple_wallets() {
         .unwrap();
 
     println!("Contract deployed @ {:x}", contract_id_2);
-    let contract_instance_2 = MyContract::new(contract_id_2.to_string(), wallets[1].clone());
+    let contract_instance_2 = MyContract::new(contract_id_2, wallets[1].clone());
 
     let result = contract_instance_2
         .initialize_counter(42) // Build the ABI call
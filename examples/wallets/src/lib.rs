@@ -58,6 +58,37 @@ async fn create_wallet_from_mnemonic() {
 }
 // ANCHOR_END: create_wallet_from_mnemonic
 
+#[tokio::test]
+// ANCHOR: manage_accounts_of_hd_wallet
+async fn manage_accounts_of_hd_wallet() {
+    use fuels::prelude::*;
+    use fuels::signers::hd_wallet::HdWallet;
+
+    let phrase = "oblige salon price punch saddle immune slogan rare snap desert retire surprise";
+
+    // Use the test helper to setup a test provider.
+    let (provider, _address) = setup_test_provider(vec![], Config::local_node()).await;
+
+    // The seed is derived once, with an optional BIP-39 passphrase.
+    let hd_wallet = HdWallet::new_from_mnemonic_phrase(phrase, None, Some(provider)).unwrap();
+
+    // Account `i` uses the default derivation path, ending in `/i`.
+    let wallet = hd_wallet.account(0).unwrap();
+    let expected_address = "f18b6446deb8135544ba60333e5b7522685cd2cf64aa4e4c75df725149850b65";
+    assert_eq!(wallet.address().to_string(), expected_address);
+
+    // Find the accounts used in a transaction, stopping after 20 unused accounts in a row.
+    let used_wallets = hd_wallet.discover_accounts(20).await.unwrap();
+    assert!(used_wallets.is_empty());
+
+    // Store the encrypted phrase; the passphrase is given again when loading it.
+    let dir = std::env::temp_dir();
+    let uuid = hd_wallet.encrypt(&dir, "my_master_password").unwrap();
+    let _recovered =
+        HdWallet::load_keystore(dir.join(uuid), "my_master_password", None, None).unwrap();
+}
+// ANCHOR_END: manage_accounts_of_hd_wallet
+
 #[tokio::test]
 // ANCHOR: create_and_restore_json_wallet
 async fn create_and_restore_json_wallet() {
@@ -128,3 +159,207 @@ async fn wallet_transfer() -> Result<(), Box<dyn std::error::Error>> {
     assert_eq!(wallet_2_final_coins.len(), 2);
     Ok(())
 }
+
+#[tokio::test]
+// ANCHOR: multi_signature_transfer
+async fn multi_signature_transfer() -> Result<(), Box<dyn std::error::Error>> {
+    use fuels::prelude::*;
+    use fuels::signers::partially_signed::PartiallySignedTransaction;
+    use fuels::tx::Output;
+
+    let wallets = launch_provider_and_get_wallets(WalletsConfig {
+        num_wallets: 3,
+        coins_per_wallet: 1,
+        coin_amount: 10,
+    })
+    .await;
+    let (alice, bob, carol) = (&wallets[0], &wallets[1], &wallets[2]);
+    let provider = alice.get_provider()?;
+
+    // Alice's coins are signed by witness 0, Bob's by witness 1.
+    let asset_id = Default::default();
+    let mut inputs = alice.get_asset_inputs_for_amount(asset_id, 10, 0).await?;
+    inputs.extend(bob.get_asset_inputs_for_amount(asset_id, 10, 1).await?);
+    let outputs = vec![Output::coin(carol.address(), 20, asset_id)];
+    let tx = provider.build_transfer_tx(&inputs, &outputs, TxParameters::default());
+
+    // Both sign, in any order, then the transaction can be submitted.
+    let mut pst = PartiallySignedTransaction::new(tx)?;
+    assert_eq!(pst.missing_signers(), vec![alice.address(), bob.address()]);
+    pst.sign(bob).await?;
+    pst.sign(alice).await?;
+    assert!(pst.is_complete());
+    pst.submit(provider).await?;
+
+    assert_eq!(carol.get_asset_balance(&asset_id).await?, 30);
+    Ok(())
+}
+// ANCHOR_END: multi_signature_transfer
+
+#[tokio::test]
+// ANCHOR: transfer_signed_offline
+async fn transfer_signed_offline() -> Result<(), Box<dyn std::error::Error>> {
+    use fuels::prelude::*;
+    use fuels::signers::partially_signed::PartiallySignedTransaction;
+
+    // The cold wallet has no provider: its key never touches a networked host.
+    let cold_wallet = LocalWallet::new_random(None);
+    let recipient = LocalWallet::new_random(None);
+
+    let coins = setup_single_asset_coins(cold_wallet.address(), BASE_ASSET_ID, 1, 10);
+    let (provider, _) = setup_test_provider(coins, Config::local_node()).await;
+
+    // On the networked host: fetch the coins to spend, knowing only the address.
+    let inputs = provider
+        .get_asset_inputs_for_amount(&cold_wallet.address(), BASE_ASSET_ID, 4, 0)
+        .await?;
+
+    // Build the transaction, without a provider, and export it.
+    let tx = cold_wallet.build_transfer(
+        &recipient.address(),
+        4,
+        BASE_ASSET_ID,
+        inputs,
+        TxParameters::default(),
+    );
+    let unsigned = serde_json::to_string(&PartiallySignedTransaction::new(tx)?)?;
+
+    // On the air-gapped machine: sign it.
+    let mut pst: PartiallySignedTransaction = serde_json::from_str(&unsigned)?;
+    pst.sign(&cold_wallet).await?;
+    let signed = serde_json::to_string(&pst)?;
+
+    // Back on the networked host: broadcast it.
+    let pst: PartiallySignedTransaction = serde_json::from_str(&signed)?;
+    provider.send_transaction(&pst.into_transaction()?).await?;
+
+    let balance = provider
+        .get_asset_balance(&recipient.address(), BASE_ASSET_ID)
+        .await?;
+    assert_eq!(balance, 4);
+    Ok(())
+}
+// ANCHOR_END: transfer_signed_offline
+
+#[tokio::test]
+// ANCHOR: coin_selection
+async fn coin_selection() -> Result<(), Box<dyn std::error::Error>> {
+    use fuels::prelude::*;
+    use fuels::signers::coin_selection::{BranchAndBound, SmallestFirst};
+
+    let mut wallet = LocalWallet::new_random(None);
+    let recipient = LocalWallet::new_random(None);
+
+    let mut coins = setup_single_asset_coins(wallet.address(), BASE_ASSET_ID, 1, 100);
+    coins.extend(setup_single_asset_coins(
+        wallet.address(),
+        BASE_ASSET_ID,
+        4,
+        5,
+    ));
+    let (provider, _) = setup_test_provider(coins, Config::local_node()).await;
+    wallet.set_provider(provider);
+
+    // Every transaction of the wallet spends its smallest coins first.
+    wallet.set_coin_selector(SmallestFirst);
+    wallet
+        .transfer(
+            &recipient.address(),
+            8,
+            BASE_ASSET_ID,
+            TxParameters::default(),
+        )
+        .await?;
+
+    // A single transaction looks for coins adding up exactly to the amount.
+    wallet
+        .clone()
+        .with_coin_selector(BranchAndBound::default())
+        .transfer(
+            &recipient.address(),
+            10,
+            BASE_ASSET_ID,
+            TxParameters::default(),
+        )
+        .await?;
+
+    let balance = wallet
+        .get_provider()?
+        .get_asset_balance(&recipient.address(), BASE_ASSET_ID)
+        .await?;
+    assert_eq!(balance, 18);
+    Ok(())
+}
+// ANCHOR_END: coin_selection
+
+#[tokio::test]
+// ANCHOR: consolidate_and_split_coins
+async fn consolidate_and_split_coins() -> Result<(), Box<dyn std::error::Error>> {
+    use fuels::prelude::*;
+
+    let mut wallet = LocalWallet::new_random(None);
+    let coins = setup_single_asset_coins(wallet.address(), BASE_ASSET_ID, 50, 10);
+    let (provider, _) = setup_test_provider(coins, Config::local_node()).await;
+    wallet.set_provider(provider);
+
+    // Merge the 50 coins into one, spending at most 20 coins per transaction.
+    let sent = wallet
+        .consolidate_coins(BASE_ASSET_ID, 20, TxParameters::default())
+        .await?;
+    let fees: u64 = sent.iter().map(|tx| tx.fee).sum();
+    assert_eq!(fees, 0);
+
+    // Prepare 8 coins of 50, one per transaction to send in parallel.
+    wallet
+        .split_coins(BASE_ASSET_ID, 8, 50, TxParameters::default())
+        .await?;
+    Ok(())
+}
+// ANCHOR_END: consolidate_and_split_coins
+
+#[tokio::test]
+// ANCHOR: transfer_many
+async fn transfer_many() -> Result<(), Box<dyn std::error::Error>> {
+    use fuels::prelude::*;
+
+    let mut wallet = LocalWallet::new_random(None);
+    let coins = setup_single_asset_coins(wallet.address(), BASE_ASSET_ID, 1, 1_000);
+    let (provider, _) = setup_test_provider(coins, Config::local_node()).await;
+    wallet.set_provider(provider);
+
+    let payouts: Vec<(Address, u64, AssetId)> = (0..10)
+        .map(|_| (LocalWallet::new_random(None).address(), 10, BASE_ASSET_ID))
+        .collect();
+
+    // A single transaction pays the 10 recipients.
+    let sent = wallet
+        .transfer_many(&payouts, TxParameters::default())
+        .await?;
+    assert_eq!(sent.len(), 1);
+    Ok(())
+}
+// ANCHOR_END: transfer_many
+
+#[tokio::test]
+// ANCHOR: sign_typed_data
+async fn sign_typed_data() -> Result<(), Box<dyn std::error::Error>> {
+    use fuels::prelude::*;
+    use fuels::signers::typed_data::{Domain, TypedData};
+
+    let wallet = LocalWallet::new_random(None);
+
+    // The order is only valid for this order book contract, on chain 0.
+    let order_book = ContractId::from([1u8; 32]);
+    let domain = Domain::new(0, order_book);
+
+    // Any `Tokenizable` type is a message, e.g. a struct generated by `abigen!`.
+    let order = (BASE_ASSET_ID, 100u64, 42u64);
+    let typed_data = TypedData::new(domain, order)?;
+    let signature = typed_data.sign(&wallet).await?;
+
+    // The receiver of the order checks who signed it.
+    assert_eq!(typed_data.recover(&signature)?, wallet.address());
+    typed_data.verify(&signature, &wallet.address())?;
+    Ok(())
+}
+// ANCHOR_END: sign_typed_data
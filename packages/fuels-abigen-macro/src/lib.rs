@@ -1,10 +1,15 @@
 use fuels_core::code_gen::abigen::Abigen;
+use fuels_core::code_gen::{BuildProfile, ForcProject, TypeAttributes};
 use proc_macro::TokenStream;
 use proc_macro2::Span;
+use quote::ToTokens;
 
+use std::collections::HashMap;
 use std::ops::Deref;
+use std::path::PathBuf;
 use syn::parse::{Parse, ParseStream, Result as ParseResult};
-use syn::{parse_macro_input, Ident, LitStr, Token};
+use syn::punctuated::Punctuated;
+use syn::{parenthesized, parse_macro_input, Attribute, Ident, LitBool, LitStr, Path, Token};
 
 /// Abigen proc macro definition and helper functions/types.
 
@@ -12,18 +17,61 @@ use syn::{parse_macro_input, Ident, LitStr, Token};
 pub fn abigen(input: TokenStream) -> TokenStream {
     let args = parse_macro_input!(input as Spanned<ContractArgs>);
 
-    let c = Abigen::new(&args.name, &args.abi).unwrap();
-
-    c.expand().unwrap().into()
+    new_abigen(&args)
+        .and_then(|c| expand(&args, c))
+        .unwrap_or_else(|e| e.to_compile_error().into())
 }
 
 #[proc_macro]
 pub fn wasm_abigen(input: TokenStream) -> TokenStream {
     let args = parse_macro_input!(input as Spanned<ContractArgs>);
 
-    let c = Abigen::new(&args.name, &args.abi).unwrap().no_std();
+    new_abigen(&args)
+        .and_then(|c| expand(&args, c.no_std()))
+        .unwrap_or_else(|e| e.to_compile_error().into())
+}
+
+// Every error is reported as a compile error pointing at the ABI argument of
+// the macro invocation.
+fn new_abigen(args: &Spanned<ContractArgs>) -> syn::Result<Abigen> {
+    match &args.source {
+        ContractSource::Abi(abi) => {
+            Abigen::new(&args.name, abi).map_err(|e| syn::Error::new(args.span(), e))
+        }
+        ContractSource::ForcProject { path, profile } => {
+            // Relative paths are resolved from the crate invoking the macro,
+            // so that they don't depend on where `cargo` is run from.
+            let mut project_dir = PathBuf::from(path);
+            if project_dir.is_relative() {
+                if let Ok(manifest_dir) = std::env::var("CARGO_MANIFEST_DIR") {
+                    project_dir = PathBuf::from(manifest_dir).join(project_dir);
+                }
+            }
+
+            ForcProject::load(&project_dir, *profile)
+                .and_then(|project| Abigen::from_forc_project(&args.name, project))
+                .map_err(|e| syn::Error::new(args.span(), e))
+        }
+    }
+}
+
+fn expand(args: &Spanned<ContractArgs>, mut abigen: Abigen) -> syn::Result<TokenStream> {
+    if args.mock {
+        abigen = abigen.with_mock();
+    }
+    if !args.type_attributes.is_empty() {
+        abigen = abigen.with_type_attributes(args.type_attributes.clone());
+    }
+    if !args.renames.is_empty() {
+        abigen = abigen
+            .with_renames(args.renames.clone())
+            .map_err(|e| syn::Error::new(args.span(), e))?;
+    }
 
-    c.expand().unwrap().into()
+    abigen
+        .expand()
+        .map(Into::into)
+        .map_err(|e| syn::Error::new(args.span(), e))
 }
 
 /// Trait that abstracts functionality for inner data that can be parsed and
@@ -51,7 +99,6 @@ struct Spanned<T>(Span, T);
 
 impl<T> Spanned<T> {
     /// Retrieves the captured `Span` information for the parsed data.
-    #[allow(dead_code)]
     pub fn span(&self) -> Span {
         self.0
     }
@@ -72,10 +119,45 @@ impl<T> Deref for Spanned<T> {
 }
 
 /// Contract procedural macro arguments.
-#[cfg_attr(test, derive(Debug, Eq, PartialEq))]
+#[cfg_attr(test, derive(Debug))]
 pub(crate) struct ContractArgs {
     name: String,
-    abi: String,
+    source: ContractSource,
+    /// Whether to generate `<name>Mock`, set with `mock = true`.
+    mock: bool,
+    /// The names to give ABI identifiers in Rust, set with
+    /// `rename("<abi name>" = "<rust name>", ...)`.
+    renames: HashMap<String, String>,
+    /// The extra derives and attributes of the custom types, set with
+    /// `derives(<path>, ...)` and `attributes(#[...] ...)`.
+    type_attributes: TypeAttributes,
+}
+
+/// A `"<abi name>" = "<rust name>"` pair of `rename(...)`.
+struct Rename {
+    abi_name: LitStr,
+    rust_name: LitStr,
+}
+
+impl Parse for Rename {
+    fn parse(input: ParseStream) -> ParseResult<Self> {
+        let abi_name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let rust_name = input.parse()?;
+        Ok(Rename {
+            abi_name,
+            rust_name,
+        })
+    }
+}
+
+/// Where the contract's ABI comes from: either `"<abi>"`, a JSON ABI or the
+/// path to one, or `project = "<path>"`, the directory of a forc project,
+/// optionally followed by `profile = "debug" | "release"`.
+#[cfg_attr(test, derive(Debug))]
+pub(crate) enum ContractSource {
+    Abi(String),
+    ForcProject { path: String, profile: BuildProfile },
 }
 
 impl ParseInner for ContractArgs {
@@ -86,14 +168,99 @@ impl ParseInner for ContractArgs {
         // skip the comma
         input.parse::<Token![,]>()?;
 
-        let (span, abi) = {
+        let (span, mut source) = if input.peek(LitStr) {
             let literal = input.parse::<LitStr>()?;
-            (literal.span(), literal.value())
+            (literal.span(), ContractSource::Abi(literal.value()))
+        } else {
+            let path = parse_named_arg::<LitStr>(input, "project")?;
+            (
+                path.span(),
+                ContractSource::ForcProject {
+                    path: path.value(),
+                    profile: BuildProfile::Debug,
+                },
+            )
         };
+
+        // read the optional `<name> = <value>` arguments
+        let mut mock = false;
+        let mut renames = HashMap::new();
+        let mut type_attributes = TypeAttributes::new();
+        while input.peek(Token![,]) && input.peek2(Ident) {
+            input.parse::<Token![,]>()?;
+            let key = input.fork().parse::<Ident>()?;
+
+            match (key.to_string().as_str(), &mut source) {
+                ("profile", ContractSource::ForcProject { profile, .. }) => {
+                    let profile_arg = parse_named_arg::<LitStr>(input, "profile")?;
+                    *profile = profile_arg
+                        .value()
+                        .parse()
+                        .map_err(|e| syn::Error::new(profile_arg.span(), e))?;
+                }
+                ("mock", _) => mock = parse_named_arg::<LitBool>(input, "mock")?.value,
+                ("rename", _) => {
+                    input.parse::<Ident>()?;
+                    let content;
+                    parenthesized!(content in input);
+                    for rename in Punctuated::<Rename, Token![,]>::parse_terminated(&content)? {
+                        renames.insert(rename.abi_name.value(), rename.rust_name.value());
+                    }
+                }
+                ("derives", _) => {
+                    input.parse::<Ident>()?;
+                    let content;
+                    parenthesized!(content in input);
+                    for derive in Punctuated::<Path, Token![,]>::parse_terminated(&content)? {
+                        type_attributes
+                            .add_derive(&derive.to_token_stream().to_string())
+                            .map_err(|e| syn::Error::new_spanned(&derive, e))?;
+                    }
+                }
+                ("attributes", _) => {
+                    input.parse::<Ident>()?;
+                    let content;
+                    parenthesized!(content in input);
+                    for attribute in content.call(Attribute::parse_outer)? {
+                        type_attributes
+                            .add_attribute(&attribute.to_token_stream().to_string())
+                            .map_err(|e| syn::Error::new_spanned(&attribute, e))?;
+                    }
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("unexpected argument `{}`", key),
+                    ))
+                }
+            }
+        }
         if !input.is_empty() {
             input.parse::<Token![,]>()?;
         }
 
-        Ok((span, ContractArgs { name, abi }))
+        Ok((
+            span,
+            ContractArgs {
+                name,
+                source,
+                mock,
+                renames,
+                type_attributes,
+            },
+        ))
+    }
+}
+
+/// Parses a `<name> = <value>` argument.
+fn parse_named_arg<T: Parse>(input: ParseStream, name: &str) -> ParseResult<T> {
+    let key = input.parse::<Ident>()?;
+    if key != name {
+        return Err(syn::Error::new(
+            key.span(),
+            format!("expected `{}`, found `{}`", name, key),
+        ));
     }
+    input.parse::<Token![=]>()?;
+    input.parse::<T>()
 }
@@ -8,7 +8,7 @@ use fuels::prelude::{
 use fuels_abigen_macro::abigen;
 use fuels_core::tx::Address;
 use fuels_core::Parameterize;
-use fuels_core::{constants::BASE_ASSET_ID, Token};
+use fuels_core::{constants::BASE_ASSET_ID, Bits256, Identity, SizedAsciiString, Token};
 use sha2::{Digest, Sha256};
 use std::str::FromStr;
 /// Note: all the tests and examples below require pre-compiled Sway projects.
@@ -24,9 +24,9 @@ fn init_tracing() {
     let _ = tracing_subscriber::fmt::try_init();
 }
 
-fn null_contract_id() -> String {
+fn null_contract_id() -> ContractId {
     // a null contract address ~[0u8;32]
-    String::from("0000000000000000000000000000000000000000000000000000000000000000")
+    ContractId::zeroed()
 }
 
 #[tokio::test]
@@ -54,6 +54,60 @@ async fn compile_bindings_from_contract_file() {
     assert_eq!("000000009593586c000000000000002a", encoded);
 }
 
+#[tokio::test]
+async fn mock_contract_calls() {
+    abigen!(
+        MockedContract,
+        r#"
+        [
+            {
+                "type": "function",
+                "inputs": [
+                    {
+                        "name": "only_argument",
+                        "type": "u32"
+                    }
+                ],
+                "name": "takes_ints_returns_bool",
+                "outputs": [
+                    {
+                        "name": "",
+                        "type": "bool"
+                    }
+                ]
+            }
+        ]
+        "#,
+        mock = true
+    );
+
+    async fn is_valid(contract: &impl MockedContractMethods, value: u32) -> Result<bool, Error> {
+        contract.takes_ints_returns_bool(value).await
+    }
+
+    let mock = MockedContractMock::new();
+    mock.expect_takes_ints_returns_bool(Ok(true))
+        .expect_takes_ints_returns_bool(Err(Error::ContractCallError(
+            "Revert(42)".to_string(),
+            vec![],
+        )));
+
+    assert!(is_valid(&mock, 42).await.unwrap());
+    assert!(matches!(
+        is_valid(&mock, 43).await,
+        Err(Error::ContractCallError(reason, _)) if reason == "Revert(42)"
+    ));
+    // No more results were programmed
+    assert!(is_valid(&mock, 44).await.is_err());
+
+    assert_eq!(
+        mock.calls(),
+        [42, 43, 44]
+            .map(|only_argument| MockedContractCall::TakesIntsReturnsBool { only_argument })
+            .to_vec()
+    );
+}
+
 #[tokio::test]
 async fn compile_bindings_from_inline_contract() {
     // Generates the bindings from the an ABI definition inline.
@@ -97,6 +151,156 @@ async fn compile_bindings_from_inline_contract() {
     assert_eq!("000000009593586c000000000000002a", encoded);
 }
 
+#[test]
+fn generated_types_derive_serde() {
+    abigen!(
+        SerdeContract,
+        r#"
+        [
+            {
+                "type": "function",
+                "inputs": [
+                    {
+                        "name": "order",
+                        "type": "struct Order",
+                        "components": [
+                            {
+                                "name": "owner",
+                                "type": "b256"
+                            },
+                            {
+                                "name": "label",
+                                "type": "str[5]"
+                            },
+                            {
+                                "name": "recipient",
+                                "type": "enum Identity",
+                                "components": [
+                                    {
+                                        "name": "Address",
+                                        "type": "struct Address",
+                                        "components": [
+                                            {
+                                                "name": "value",
+                                                "type": "b256"
+                                            }
+                                        ]
+                                    },
+                                    {
+                                        "name": "ContractId",
+                                        "type": "struct ContractId",
+                                        "components": [
+                                            {
+                                                "name": "value",
+                                                "type": "b256"
+                                            }
+                                        ]
+                                    }
+                                ]
+                            },
+                            {
+                                "name": "item",
+                                "type": "struct Item",
+                                "components": [
+                                    {
+                                        "name": "id",
+                                        "type": "u64"
+                                    },
+                                    {
+                                        "name": "in_stock",
+                                        "type": "bool"
+                                    }
+                                ]
+                            },
+                            {
+                                "name": "status",
+                                "type": "enum State",
+                                "components": [
+                                    {
+                                        "name": "Pending",
+                                        "type": "()"
+                                    },
+                                    {
+                                        "name": "Shipped",
+                                        "type": "str[3]"
+                                    }
+                                ]
+                            }
+                        ]
+                    }
+                ],
+                "name": "place_order",
+                "outputs": []
+            }
+        ]
+        "#,
+        derives(serde::Serialize, serde::Deserialize)
+    );
+
+    let order = Order {
+        owner: Bits256([1; 32]),
+        label: SizedAsciiString::new("fuels".to_string()).unwrap(),
+        recipient: Identity::ContractId(ContractId::new([2; 32])),
+        item: Item {
+            id: 42,
+            in_stock: true,
+        },
+        status: State::Shipped(SizedAsciiString::new("abc".to_string()).unwrap()),
+    };
+
+    // `b256`s and the ids of `Identity`s are `0x`-prefixed hex strings and `str[N]`s plain
+    // strings, as in `DecodedValue`.
+    let json = serde_json::to_string(&order).unwrap();
+    assert_eq!(
+        json,
+        format!(
+            r#"{{"owner":"0x{}","label":"fuels","recipient":{{"ContractId":"0x{}"}},"item":{{"id":42,"in_stock":true}},"status":{{"Shipped":"abc"}}}}"#,
+            "01".repeat(32),
+            "02".repeat(32)
+        )
+    );
+    assert_eq!(serde_json::from_str::<Order>(&json).unwrap(), order);
+
+    // The lengths of the strings are checked when deserializing too.
+    let too_long = json.replace("fuels", "fuels-rs");
+    assert!(serde_json::from_str::<Order>(&too_long).is_err());
+}
+
+#[test]
+fn decode_call_data_of_any_signer() {
+    use fuels::signers::remote_signer::RemoteSigner;
+
+    abigen!(
+        DecodedContract,
+        r#"
+        [
+            {
+                "type": "function",
+                "inputs": [{ "name": "amount", "type": "u64" }],
+                "name": "increment",
+                "outputs": []
+            }
+        ]
+        "#,
+    );
+
+    let call_data = [
+        DecodedContractFunction::Increment.selector().to_vec(),
+        vec![0, 0, 0, 0, 0, 0, 0, 42],
+    ]
+    .concat();
+
+    let expected = DecodedContractCall::Increment { amount: 42 };
+    assert_eq!(
+        <DecodedContract>::decode_call_data(&call_data).unwrap(),
+        expected
+    );
+    assert_eq!(
+        DecodedContract::<RemoteSigner>::decode_call_data(&call_data).unwrap(),
+        expected
+    );
+}
+
 #[tokio::test]
 async fn compile_bindings_array_input() {
     // Generates the bindings from the an ABI definition inline.
@@ -127,7 +331,7 @@ async fn compile_bindings_array_input() {
     // `SimpleContract` is the name of the contract
     let contract_instance = SimpleContract::new(null_contract_id(), wallet);
 
-    let input: Vec<u16> = vec![1, 2, 3, 4];
+    let input: [u16; 3] = [1, 2, 3];
     let call_handler = contract_instance.takes_array(input);
 
     let encoded = format!(
@@ -137,7 +341,7 @@ async fn compile_bindings_array_input() {
     );
 
     assert_eq!(
-        "000000005898d3a40000000000000001000000000000000200000000000000030000000000000004",
+        "000000005898d3a4000000000000000100000000000000020000000000000003",
         encoded
     );
 }
@@ -172,7 +376,7 @@ async fn compile_bindings_bool_array_input() {
     // `SimpleContract` is the name of the contract
     let contract_instance = SimpleContract::new(null_contract_id(), wallet);
 
-    let input: Vec<bool> = vec![true, false, true];
+    let input: [bool; 3] = [true, false, true];
     let call_handler = contract_instance.takes_array(input);
 
     let encoded = format!(
@@ -258,7 +462,8 @@ async fn compile_bindings_string_input() {
     // `SimpleContract` is the name of the contract
     let contract_instance = SimpleContract::new(null_contract_id(), wallet);
 
-    let call_handler = contract_instance.takes_string("This is a full sentence".into());
+    let call_handler =
+        contract_instance.takes_string("This is a full sentence".try_into().unwrap());
 
     let encoded = format!(
         "{}{}",
@@ -307,7 +512,7 @@ async fn compile_bindings_b256_input() {
 
     let arg = hasher.finalize();
 
-    let call_handler = contract_instance.takes_b256(arg.into());
+    let call_handler = contract_instance.takes_b256(Bits256(arg.into()));
 
     let encoded = format!(
         "{}{}",
@@ -356,8 +561,8 @@ async fn compile_bindings_struct_input() {
     // Because of the abigen! macro, `MyStruct` is now in scope
     // and can be used!
     let input = MyStruct {
-        foo: vec![10, 2],
-        bar: "fuel".to_string(),
+        foo: [10, 2],
+        bar: "fuel".try_into().unwrap(),
     };
 
     let wallet = launch_provider_and_get_single_wallet().await;
@@ -532,7 +737,7 @@ async fn create_struct_from_decoded_tokens() {
 
     // Create the struct using the decoded tokens.
     // `struct_from_tokens` is of type `MyStruct`.
-    let struct_from_tokens = MyStruct::new_from_tokens(&[foo, bar]);
+    let struct_from_tokens = MyStruct::new_from_tokens(&[foo, bar]).unwrap();
 
     assert_eq!(10, struct_from_tokens.foo);
     assert!(struct_from_tokens.bar);
@@ -594,7 +799,7 @@ async fn create_nested_struct_from_decoded_tokens() {
 
     // Creating just the InnerStruct is possible
     let a = Token::Bool(true);
-    let inner_struct_from_tokens = InnerStruct::new_from_tokens(&[a.clone()]);
+    let inner_struct_from_tokens = InnerStruct::new_from_tokens(&[a.clone()]).unwrap();
     assert!(inner_struct_from_tokens.a);
 
     // Creating the whole nested struct `MyNestedStruct`
@@ -603,7 +808,7 @@ async fn create_nested_struct_from_decoded_tokens() {
     // `a` is the token for the field `a` in `InnerStruct`
     let x = Token::U16(10);
 
-    let nested_struct_from_tokens = MyNestedStruct::new_from_tokens(&[x, a]);
+    let nested_struct_from_tokens = MyNestedStruct::new_from_tokens(&[x, a]).unwrap();
 
     assert_eq!(10, nested_struct_from_tokens.x);
     assert!(nested_struct_from_tokens.y.a);
@@ -624,27 +829,56 @@ async fn create_nested_struct_from_decoded_tokens() {
     assert_eq!("0000000088bf8a1b000000000000000a0000000000000001", encoded);
 }
 
+#[tokio::test]
+async fn failed_calls_release_their_coins() {
+    abigen!(
+        SimpleContract,
+        r#"
+        [
+            {
+                "type": "function",
+                "inputs": [],
+                "name": "deposit",
+                "outputs": []
+            }
+        ]
+        "#,
+    );
+
+    let wallet = launch_provider_and_get_single_wallet().await;
+    let provider = wallet.get_provider().unwrap().clone();
+    let contract_instance = SimpleContract::new(null_contract_id(), wallet.clone());
+
+    // The wallet only has coins of the base asset, so the call can't pay the
+    // forwarded asset once the base coins are selected.
+    let call_params = CallParameters::new(Some(1), Some(AssetId::new([1u8; 32])));
+    let result = contract_instance
+        .deposit()
+        .call_params(call_params)
+        .call()
+        .await;
+
+    assert!(result.is_err());
+    assert!(provider.reserved_coins(&wallet.address()).await.is_empty());
+}
+
 #[tokio::test]
 async fn type_safe_output_values() {
     // Generates the bindings from the an ABI definition inline.
     // The generated bindings can be accessed through `SimpleContract`.
     abigen!(
         MyContract,
-        "packages/fuels-abigen-macro/tests/test_projects/contract_output_test/out/debug/contract_output_test-abi.json"
+        project = "tests/test_projects/contract_output_test"
     );
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
-    let contract_id = Contract::deploy(
-        "tests/test_projects/contract_output_test/out/debug/contract_output_test.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let contract_id = Contract::deploy(MyContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
     println!("Contract deployed @ {:x}", contract_id);
 
-    let contract_instance = MyContract::new(contract_id.to_string(), wallet);
+    let contract_instance = MyContract::new(contract_id, wallet);
 
     // `response`'s type matches the return type of `is_event()`
     let response = contract_instance.is_even(10).call().await.unwrap();
@@ -652,7 +886,7 @@ async fn type_safe_output_values() {
 
     // `response`'s type matches the return type of `return_my_string()`
     let response = contract_instance
-        .return_my_string("fuel".to_string())
+        .return_my_string("fuel".try_into().unwrap())
         .call()
         .await
         .unwrap();
@@ -677,21 +911,17 @@ async fn call_with_structs() {
     // The generated bindings can be accessed through `MyContract`.
     abigen!(
         MyContract,
-        "packages/fuels-abigen-macro/tests/test_projects/complex_types_contract/out/debug/contract_test-abi.json"
+        project = "tests/test_projects/complex_types_contract"
     );
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
-    let contract_id = Contract::deploy(
-        "tests/test_projects/complex_types_contract/out/debug/contract_test.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let contract_id = Contract::deploy(MyContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
     println!("Contract deployed @ {:x}", contract_id);
 
-    let contract_instance = MyContract::new(contract_id.to_string(), wallet);
+    let contract_instance = MyContract::new(contract_id, wallet);
     let counter_config = CounterConfig {
         dummy: true,
         initial_value: 42,
@@ -720,21 +950,17 @@ async fn call_with_empty_return() {
     // The generated bindings can be accessed through `MyContract`.
     abigen!(
         MyContract,
-        "packages/fuels-abigen-macro/tests/test_projects/call_empty_return/out/debug/contract_test-abi.json"
+        project = "tests/test_projects/call_empty_return"
     );
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
-    let contract_id = Contract::deploy(
-        "tests/test_projects/call_empty_return/out/debug/contract_test.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let contract_id = Contract::deploy(MyContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
     println!("Contract deployed @ {:x}", contract_id);
 
-    let contract_instance = MyContract::new(contract_id.to_string(), wallet);
+    let contract_instance = MyContract::new(contract_id, wallet);
 
     let _result = contract_instance
         .store_value(42) // Build the ABI call
@@ -745,23 +971,16 @@ async fn call_with_empty_return() {
 
 #[tokio::test]
 async fn abigen_different_structs_same_arg_name() {
-    abigen!(
-        MyContract,
-        "packages/fuels-abigen-macro/tests/test_projects/two_structs/out/debug/two_structs-abi.json",
-    );
+    abigen!(MyContract, project = "tests/test_projects/two_structs");
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
-    let contract_id = Contract::deploy(
-        "tests/test_projects/two_structs/out/debug/two_structs.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let contract_id = Contract::deploy(MyContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
     println!("Contract deployed @ {:x}", contract_id);
 
-    let contract_instance = MyContract::new(contract_id.to_string(), wallet);
+    let contract_instance = MyContract::new(contract_id, wallet);
 
     let param_one = StructOne { foo: 42 };
     let param_two = StructTwo { bar: 42 };
@@ -783,15 +1002,19 @@ async fn abigen_different_structs_same_arg_name() {
 async fn test_reverting_transaction() {
     abigen!(
         RevertingContract,
-        "packages/fuels-abigen-macro/tests/test_projects/revert_transaction_error/out/debug/capture_revert_transaction_error-abi.json"
+        project = "tests/test_projects/revert_transaction_error"
     );
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
-    let contract_id = Contract::deploy("tests/test_projects/revert_transaction_error/out/debug/capture_revert_transaction_error.bin", &wallet, TxParameters::default())
-        .await
-        .unwrap();
-    let contract_instance = RevertingContract::new(contract_id.to_string(), wallet);
+    let contract_id = Contract::deploy(
+        RevertingContract::BIN_PATH,
+        &wallet,
+        TxParameters::default(),
+    )
+    .await
+    .unwrap();
+    let contract_instance = RevertingContract::new(contract_id, wallet);
     println!("Contract deployed @ {:x}", contract_id);
     let result = contract_instance.make_transaction_fail(0).call().await;
     assert!(matches!(result, Err(Error::ContractCallError(..))));
@@ -801,20 +1024,16 @@ async fn test_reverting_transaction() {
 async fn multiple_read_calls() {
     abigen!(
         MyContract,
-        "packages/fuels-abigen-macro/tests/test_projects/multiple_read_calls/out/debug/demo-abi.json"
+        project = "tests/test_projects/multiple_read_calls"
     );
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
-    let contract_id = Contract::deploy(
-        "tests/test_projects/multiple_read_calls/out/debug/demo.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let contract_id = Contract::deploy(MyContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
     println!("Contract deployed @ {:x}", contract_id);
-    let contract_instance = MyContract::new(contract_id.to_string(), wallet);
+    let contract_instance = MyContract::new(contract_id, wallet);
 
     contract_instance.store(42).call().await.unwrap();
 
@@ -834,23 +1053,16 @@ async fn multiple_read_calls() {
 async fn test_methods_typeless_argument() {
     // Generates the bindings from the an ABI definition inline.
     // The generated bindings can be accessed through `MyContract`.
-    abigen!(
-        MyContract,
-        "packages/fuels-abigen-macro/tests/test_projects/empty_arguments/out/debug/method_four_arguments-abi.json"
-    );
+    abigen!(MyContract, project = "tests/test_projects/empty_arguments");
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
-    let contract_id = Contract::deploy(
-        "tests/test_projects/empty_arguments/out/debug/method_four_arguments.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let contract_id = Contract::deploy(MyContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
     println!("Contract deployed @ {:x}", contract_id);
 
-    let contract_instance = MyContract::new(contract_id.to_string(), wallet);
+    let contract_instance = MyContract::new(contract_id, wallet);
 
     let result = contract_instance
         .method_with_empty_argument()
@@ -864,21 +1076,17 @@ async fn test_methods_typeless_argument() {
 async fn test_large_return_data() {
     abigen!(
         MyContract,
-        "packages/fuels-abigen-macro/tests/test_projects/large_return_data/out/debug/contract_test-abi.json"
+        project = "tests/test_projects/large_return_data"
     );
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
-    let contract_id = Contract::deploy(
-        "tests/test_projects/large_return_data/out/debug/contract_test.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let contract_id = Contract::deploy(MyContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
     println!("Contract deployed @ {:x}", contract_id);
 
-    let contract_instance = MyContract::new(contract_id.to_string(), wallet);
+    let contract_instance = MyContract::new(contract_id, wallet);
 
     let res = contract_instance.get_id().call().await.unwrap();
 
@@ -922,10 +1130,7 @@ async fn test_large_return_data() {
 
 #[tokio::test]
 async fn test_provider_launch_and_connect() {
-    abigen!(
-        MyContract,
-        "packages/fuels-abigen-macro/tests/test_projects/contract_test/out/debug/contract_test-abi.json"
-    );
+    abigen!(MyContract, project = "tests/test_projects/contract_test");
 
     let mut wallet = LocalWallet::new_random(None);
 
@@ -940,16 +1145,12 @@ async fn test_provider_launch_and_connect() {
 
     wallet.set_provider(connected_provider);
 
-    let contract_id = Contract::deploy(
-        "tests/test_projects/contract_test/out/debug/contract_test.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let contract_id = Contract::deploy(MyContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
     println!("Contract deployed @ {:x}", contract_id);
 
-    let contract_instance_connected = MyContract::new(contract_id.to_string(), wallet.clone());
+    let contract_instance_connected = MyContract::new(contract_id, wallet.clone());
 
     let result = contract_instance_connected
         .initialize_counter(42) // Build the ABI call
@@ -959,7 +1160,7 @@ async fn test_provider_launch_and_connect() {
     assert_eq!(42, result.value);
 
     wallet.set_provider(launched_provider);
-    let contract_instance_launched = MyContract::new(contract_id.to_string(), wallet);
+    let contract_instance_launched = contract_instance_connected.with_wallet(wallet);
 
     let result = contract_instance_launched
         .increment_counter(10)
@@ -972,49 +1173,38 @@ async fn test_provider_launch_and_connect() {
 #[tokio::test]
 async fn test_contract_calling_contract() {
     // Tests a contract call that calls another contract (FooCaller calls FooContract underneath)
-    abigen!(
-        FooContract,
-        "packages/fuels-abigen-macro/tests/test_projects/foo_contract/out/debug/foo_contract-abi.json"
-    );
+    abigen!(FooContract, project = "tests/test_projects/foo_contract");
 
     abigen!(
         FooCaller,
-        "packages/fuels-abigen-macro/tests/test_projects/foo_caller_contract/out/debug/foo_caller_contract-abi.json"
+        project = "tests/test_projects/foo_caller_contract"
     );
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
     // Load and deploy the first compiled contract
-    let foo_contract_id = Contract::deploy(
-        "tests/test_projects/foo_contract/out/debug/foo_contract.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let foo_contract_id = Contract::deploy(FooContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
     println!("Foo contract deployed @ {:x}", foo_contract_id);
 
-    let foo_contract_instance = FooContract::new(foo_contract_id.to_string(), wallet.clone());
+    let foo_contract_instance = FooContract::new(foo_contract_id, wallet.clone());
 
     // Call the contract directly; it just flips the bool value that's passed.
     let res = foo_contract_instance.foo(true).call().await.unwrap();
     assert!(!res.value);
 
     // Load and deploy the second compiled contract
-    let foo_caller_contract_id = Contract::deploy(
-        "tests/test_projects/foo_caller_contract/out/debug/foo_caller_contract.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let foo_caller_contract_id =
+        Contract::deploy(FooCaller::BIN_PATH, &wallet, TxParameters::default())
+            .await
+            .unwrap();
     println!(
         "Foo caller contract deployed @ {:x}",
         foo_caller_contract_id
     );
 
-    let foo_caller_contract_instance =
-        FooCaller::new(foo_caller_contract_id.to_string(), wallet.clone());
+    let foo_caller_contract_instance = FooCaller::new(foo_caller_contract_id, wallet.clone());
 
     // Calls the contract that calls the `FooContract` contract, also just
     // flips the bool value passed to it.
@@ -1032,22 +1222,15 @@ async fn test_contract_calling_contract() {
 async fn test_gas_errors() {
     // Generates the bindings from the an ABI definition inline.
     // The generated bindings can be accessed through `MyContract`.
-    abigen!(
-        MyContract,
-        "packages/fuels-abigen-macro/tests/test_projects/contract_test/out/debug/contract_test-abi.json"
-    );
+    abigen!(MyContract, project = "tests/test_projects/contract_test");
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
-    let contract_id = Contract::deploy(
-        "tests/test_projects/contract_test/out/debug/contract_test.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let contract_id = Contract::deploy(MyContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
 
-    let contract_instance = MyContract::new(contract_id.to_string(), wallet);
+    let contract_instance = MyContract::new(contract_id, wallet);
 
     // Test for insufficient gas.
     let result = contract_instance
@@ -1083,20 +1266,20 @@ async fn test_gas_errors() {
 async fn test_amount_and_asset_forwarding() {
     abigen!(
         TestFuelCoinContract,
-        "packages/fuels-abigen-macro/tests/test_projects/token_ops/out/debug/token_ops-abi.json"
+        project = "tests/test_projects/token_ops"
     );
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
     let id = Contract::deploy(
-        "tests/test_projects/token_ops/out/debug/token_ops.bin",
+        TestFuelCoinContract::BIN_PATH,
         &wallet,
         TxParameters::default(),
     )
     .await
     .unwrap();
 
-    let instance = TestFuelCoinContract::new(id.to_string(), wallet.clone());
+    let instance = TestFuelCoinContract::new(id, wallet.clone());
 
     let mut balance_result = instance.get_balance(id, id).call().await.unwrap();
     assert_eq!(balance_result.value, 0);
@@ -1170,22 +1353,15 @@ async fn test_amount_and_asset_forwarding() {
 
 #[tokio::test]
 async fn test_multiple_args() {
-    abigen!(
-        MyContract,
-        "packages/fuels-abigen-macro/tests/test_projects/contract_test/out/debug/contract_test-abi.json"
-    );
+    abigen!(MyContract, project = "tests/test_projects/contract_test");
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
-    let id = Contract::deploy(
-        "tests/test_projects/contract_test/out/debug/contract_test.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let id = Contract::deploy(MyContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
 
-    let instance = MyContract::new(id.to_string(), wallet.clone());
+    let instance = MyContract::new(id, wallet.clone());
 
     // Make sure we can call the contract with multiple arguments
     let response = instance.get(5, 6).call().await.unwrap();
@@ -1202,22 +1378,15 @@ async fn test_multiple_args() {
 
 #[tokio::test]
 async fn test_tuples() {
-    abigen!(
-        MyContract,
-        "packages/fuels-abigen-macro/tests/test_projects/tuples/out/debug/tuples-abi.json"
-    );
+    abigen!(MyContract, project = "tests/test_projects/tuples");
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
-    let id = Contract::deploy(
-        "tests/test_projects/tuples/out/debug/tuples.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let id = Contract::deploy(MyContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
 
-    let instance = MyContract::new(id.to_string(), wallet.clone());
+    let instance = MyContract::new(id, wallet.clone());
 
     let response = instance.returns_tuple((1, 2)).call().await.unwrap();
 
@@ -1227,7 +1396,7 @@ async fn test_tuples() {
     let my_struct_tuple = (
         42,
         Person {
-            name: "Jane".to_string(),
+            name: "Jane".try_into().unwrap(),
         },
     );
     let response = instance
@@ -1250,7 +1419,7 @@ async fn test_tuples() {
     assert_eq!(response.value, my_enum_tuple);
 
     let id = *ContractId::zeroed();
-    let my_b256_u8_tuple: ([u8; 32], u8) = (id, 10);
+    let my_b256_u8_tuple: (Bits256, u8) = (Bits256(id), 10);
 
     let response = instance
         .tuple_with_b256(my_b256_u8_tuple)
@@ -1265,30 +1434,23 @@ async fn test_tuples() {
 async fn test_arrays_with_custom_types() {
     // Generates the bindings from the an ABI definition inline.
     // The generated bindings can be accessed through `MyContract`.
-    abigen!(
-        MyContract,
-        "packages/fuels-abigen-macro/tests/test_projects/contract_test/out/debug/contract_test-abi.json"
-    );
+    abigen!(MyContract, project = "tests/test_projects/contract_test");
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
-    let contract_id = Contract::deploy(
-        "tests/test_projects/contract_test/out/debug/contract_test.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let contract_id = Contract::deploy(MyContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
 
     println!("Contract deployed @ {:x}", contract_id);
-    let contract_instance = MyContract::new(contract_id.to_string(), wallet);
+    let contract_instance = MyContract::new(contract_id, wallet);
 
-    let persons = vec![
+    let persons = [
         Person {
-            name: "John".to_string(),
+            name: "John".try_into().unwrap(),
         },
         Person {
-            name: "Jane".to_string(),
+            name: "Jane".try_into().unwrap(),
         },
     ];
 
@@ -1301,7 +1463,7 @@ async fn test_arrays_with_custom_types() {
     assert_eq!("John", result.value[0].name);
     assert_eq!("Jane", result.value[1].name);
 
-    let states = vec![State::A(), State::B()];
+    let states = [State::A(), State::B()];
 
     let result = contract_instance
         .array_of_enums(states.clone())
@@ -1317,20 +1479,16 @@ async fn test_arrays_with_custom_types() {
 async fn test_auth_msg_sender_from_sdk() {
     abigen!(
         AuthContract,
-        "packages/fuels-abigen-macro/tests/test_projects/auth_testing_contract/out/debug/auth_testing_contract-abi.json"
+        project = "tests/test_projects/auth_testing_contract"
     );
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
-    let id = Contract::deploy(
-        "tests/test_projects/auth_testing_contract/out/debug/auth_testing_contract.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let id = Contract::deploy(AuthContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
 
-    let auth_instance = AuthContract::new(id.to_string(), wallet.clone());
+    let auth_instance = AuthContract::new(id, wallet.clone());
 
     // Contract returns true if `msg_sender()` matches `wallet.address()`.
     let result = auth_instance
@@ -1346,20 +1504,15 @@ async fn test_auth_msg_sender_from_sdk() {
 async fn workflow_enum_inside_struct() {
     abigen!(
         MyContract,
-        "packages/fuels-abigen-macro/tests/test_projects/enum_inside_struct/out/debug\
-        /enum_inside_struct-abi.json"
+        project = "tests/test_projects/enum_inside_struct"
     );
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
-    let id = Contract::deploy(
-        "tests/test_projects/enum_inside_struct/out/debug/enum_inside_struct.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
-    let instance = MyContract::new(id.to_string(), wallet.clone());
+    let id = Contract::deploy(MyContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
+    let instance = MyContract::new(id, wallet.clone());
     let result = instance.return_enum_inside_struct(11).call().await.unwrap();
     let expected = Cocktail {
         the_thing_you_mix_in: Shaker::Mojito(222),
@@ -1382,20 +1535,16 @@ async fn workflow_enum_inside_struct() {
 async fn workflow_struct_inside_enum() {
     abigen!(
         MyContract,
-        "packages/fuels-abigen-macro/tests/test_projects/struct_inside_enum/out/debug/struct_inside_enum-abi.json"
+        project = "tests/test_projects/struct_inside_enum"
     );
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
-    let id = Contract::deploy(
-        "tests/test_projects/struct_inside_enum/out/debug/struct_inside_enum.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let id = Contract::deploy(MyContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
 
-    let instance = MyContract::new(id.to_string(), wallet.clone());
+    let instance = MyContract::new(id, wallet.clone());
     let result = instance.return_struct_inside_enum(11).call().await.unwrap();
     let expected = Shaker::Cosmopolitan(Recipe { ice: 22, sugar: 99 });
     assert_eq!(result.value, expected);
@@ -1410,22 +1559,15 @@ async fn workflow_struct_inside_enum() {
 
 #[tokio::test]
 async fn workflow_use_enum_input() {
-    abigen!(
-        MyContract,
-        "packages/fuels-abigen-macro/tests/test_projects/use_enum_input/out/debug/use_enum_input-abi.json"
-    );
+    abigen!(MyContract, project = "tests/test_projects/use_enum_input");
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
-    let id = Contract::deploy(
-        "tests/test_projects/use_enum_input/out/debug/use_enum_input.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let id = Contract::deploy(MyContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
 
-    let instance = MyContract::new(id.to_string(), wallet.clone());
+    let instance = MyContract::new(id, wallet.clone());
     let enum_input = Shaker::Cosmopolitan(255);
     let result = instance.use_enum_as_input(enum_input).call().await.unwrap();
     assert_eq!(result.value, 9876);
@@ -1435,19 +1577,15 @@ async fn workflow_use_enum_input() {
 async fn test_logd_receipts() {
     abigen!(
         LoggingContract,
-        "packages/fuels-abigen-macro/tests/test_projects/contract_logdata/out/debug/contract_logdata-abi.json"
+        project = "tests/test_projects/contract_logdata"
     );
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
-    let id = Contract::deploy(
-        "tests/test_projects/contract_logdata/out/debug/contract_logdata.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
-    let contract_instance = LoggingContract::new(id.to_string(), wallet.clone());
+    let id = Contract::deploy(LoggingContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
+    let contract_instance = LoggingContract::new(id, wallet.clone());
     let mut value = [0u8; 32];
     value[0] = 0xFF;
     value[1] = 0xEE;
@@ -1479,21 +1617,14 @@ async fn test_logd_receipts() {
 
 #[tokio::test]
 async fn unit_type_enums() {
-    abigen!(
-        MyContract,
-        "packages/fuels-abigen-macro/tests/test_projects/use_enum_input/out/debug/use_enum_input-abi.json"
-    );
+    abigen!(MyContract, project = "tests/test_projects/use_enum_input");
 
     let wallet = launch_provider_and_get_single_wallet().await;
-    let id = Contract::deploy(
-        "tests/test_projects/use_enum_input/out/debug/use_enum_input.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let id = Contract::deploy(MyContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
 
-    let instance = MyContract::new(id.to_string(), wallet.clone());
+    let instance = MyContract::new(id, wallet.clone());
     let unit_type_enum = BimBamBoum::Bim();
     let result = instance
         .use_unit_type_enum(unit_type_enum)
@@ -1563,20 +1694,16 @@ async fn test_wallet_balance_api() {
 async fn sway_native_types_support() {
     abigen!(
         MyContract,
-        "packages/fuels-abigen-macro/tests/test_projects/sway_native_types/out/debug/sway_native_types-abi.json"
+        project = "tests/test_projects/sway_native_types"
     );
 
     let wallet = launch_provider_and_get_single_wallet().await;
 
-    let id = Contract::deploy(
-        "tests/test_projects/sway_native_types/out/debug/sway_native_types.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let id = Contract::deploy(MyContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
 
-    let instance = MyContract::new(id.to_string(), wallet.clone());
+    let instance = MyContract::new(id, wallet.clone());
 
     let user = User {
         weight: 10,
@@ -1601,27 +1728,20 @@ async fn sway_native_types_support() {
 
 #[tokio::test]
 async fn test_transaction_script_workflow() {
-    abigen!(
-        MyContract,
-        "packages/fuels-abigen-macro/tests/test_projects/contract_test/out/debug/contract_test-abi.json"
-    );
+    abigen!(MyContract, project = "tests/test_projects/contract_test");
 
     let wallet = launch_provider_and_get_single_wallet().await;
     let client = &wallet.get_provider().unwrap().client;
 
-    let contract_id = Contract::deploy(
-        "tests/test_projects/contract_test/out/debug/contract_test.bin",
-        &wallet,
-        TxParameters::default(),
-    )
-    .await
-    .unwrap();
+    let contract_id = Contract::deploy(MyContract::BIN_PATH, &wallet, TxParameters::default())
+        .await
+        .unwrap();
 
-    let contract_instance = MyContract::new(contract_id.to_string(), wallet.clone());
+    let contract_instance = MyContract::new(contract_id, wallet.clone());
 
     let call_handler = contract_instance.initialize_counter(42);
 
-    let script = call_handler.get_script().await;
+    let script = call_handler.get_script().await.unwrap();
     assert!(script.tx.is_script());
 
     let receipts = script.call(client).await.unwrap();
@@ -1629,3 +1749,43 @@ async fn test_transaction_script_workflow() {
     let response = call_handler.get_response(receipts).unwrap();
     assert_eq!(response.value, 42);
 }
+
+#[tokio::test]
+async fn contract_call_signed_offline() {
+    use fuels::signers::partially_signed::PartiallySignedTransaction;
+
+    abigen!(MyContract, project = "tests/test_projects/contract_test");
+
+    // The cold wallet never gets a provider.
+    let cold_wallet = LocalWallet::new_random(None);
+    let coins = setup_single_asset_coins(cold_wallet.address(), BASE_ASSET_ID, 2, 1_000_000);
+    let (provider, _) = setup_test_provider(coins, Config::local_node()).await;
+
+    let mut deployer = cold_wallet.clone();
+    deployer.set_provider(provider.clone());
+    let contract_id = Contract::deploy(MyContract::BIN_PATH, &deployer, TxParameters::default())
+        .await
+        .unwrap();
+
+    // The coins paying for the call are fetched on a networked machine.
+    let inputs = provider
+        .get_asset_inputs_for_amount(&cold_wallet.address(), BASE_ASSET_ID, 1, 0)
+        .await
+        .unwrap();
+
+    // The call is built and signed without a provider.
+    let contract_instance = MyContract::new(contract_id, cold_wallet.clone());
+    let call_handler = contract_instance.initialize_counter(42);
+    let script = call_handler.get_script_with_inputs(inputs);
+
+    let mut pst = PartiallySignedTransaction::new(script.tx).unwrap();
+    pst.sign(&cold_wallet).await.unwrap();
+
+    let receipts = provider
+        .send_transaction(&pst.into_transaction().unwrap())
+        .await
+        .unwrap();
+
+    let response = call_handler.get_response(receipts).unwrap();
+    assert_eq!(response.value, 42);
+}
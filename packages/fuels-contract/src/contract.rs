@@ -2,16 +2,21 @@ use crate::{abi_decoder::ABIDecoder, abi_encoder::ABIEncoder, script::Script};
 use anyhow::Result;
 use fuel_gql_client::{
     client::FuelClient,
-    fuel_tx::{Contract as FuelContract, Output, Receipt, StorageSlot, Transaction},
+    fuel_tx::{Contract as FuelContract, Input, Output, Receipt, StorageSlot, Transaction},
     fuel_types::{Address, AssetId, ContractId, Salt},
 };
 use fuels_core::{
-    constants::{BASE_ASSET_ID, DEFAULT_SPENDABLE_COIN_AMOUNT},
+    constants::BASE_ASSET_ID,
     errors::Error,
     parameters::{CallParameters, TxParameters},
     Detokenize, ParamType, ReturnLocation, Selector, Token,
 };
-use fuels_signers::{provider::Provider, LocalWallet, Signer};
+use fuels_signers::{
+    provider::{Provider, ProviderError},
+    wallet::WalletError,
+    LocalWallet, Signer,
+};
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
@@ -25,9 +30,9 @@ pub struct CompiledContract {
 /// compiling, deploying, and running transactions against a contract.
 /// The contract has a wallet attribute, used to pay for transactions and sign them.
 /// It allows doing calls without passing a wallet/signer each time.
-pub struct Contract {
+pub struct Contract<S = LocalWallet> {
     pub compiled_contract: CompiledContract,
-    pub wallet: LocalWallet,
+    pub wallet: S,
 }
 
 /// CallResponse is a struct that is returned by a call to the contract. Its value field
@@ -56,14 +61,16 @@ impl<D> CallResponse<D> {
     }
 }
 
-impl Contract {
-    pub fn new(compiled_contract: CompiledContract, wallet: LocalWallet) -> Self {
+impl<S: Signer> Contract<S> {
+    pub fn new(compiled_contract: CompiledContract, wallet: S) -> Self {
         Self {
             compiled_contract,
             wallet,
         }
     }
+}
 
+impl Contract {
     pub fn compute_contract_id(compiled_contract: &CompiledContract) -> ContractId {
         let fuel_contract = FuelContract::from(compiled_contract.raw.clone());
         let root = fuel_contract.root();
@@ -74,6 +81,17 @@ impl Contract {
         )
     }
 
+    /// Gets the contract's balance of each of the given assets, keyed by
+    /// their `0x`-prefixed hex id. The node can't list the assets a contract
+    /// holds, so the assets to look up have to be given.
+    pub async fn get_balances(
+        provider: &Provider,
+        contract_id: &ContractId,
+        asset_ids: &[AssetId],
+    ) -> Result<HashMap<String, u64>, ProviderError> {
+        provider.get_contract_balances(contract_id, asset_ids).await
+    }
+
     /// Creates an ABI call based on a function selector and
     /// the encoding of its call arguments, which is a slice of Tokens.
     /// It returns a prepared ContractCall that can further be used to
@@ -87,15 +105,15 @@ impl Contract {
     ///     }
     /// }
     /// For more details see `code_gen/functions_gen.rs`.
-    /// Note that this needs a wallet because the contract instance needs a wallet for the calls
-    pub fn method_hash<D: Detokenize + Debug>(
-        provider: &Provider,
+    /// Note that this needs a wallet because the contract instance needs a wallet for the calls.
+    /// Any `Signer` with a provider can be used, it pays for the calls and signs them.
+    pub fn method_hash<D: Detokenize + Debug, S: Signer + Clone>(
         contract_id: ContractId,
-        wallet: &LocalWallet,
+        wallet: &S,
         signature: Selector,
         output_params: &[ParamType],
         args: &[Token],
-    ) -> Result<ContractCallHandler<D>, Error> {
+    ) -> Result<ContractCallHandler<D, S>, Error> {
         let mut encoder = ABIEncoder::new();
 
         let encoded_args = encoder.encode(args).unwrap();
@@ -120,11 +138,14 @@ impl Contract {
             output_params: output_params.to_vec(),
         };
 
+        let fuel_client = wallet.provider().map(|provider| provider.client.clone());
+
+        #[allow(deprecated)]
         Ok(ContractCallHandler {
             contract_call,
             tx_parameters,
             wallet: wallet.clone(),
-            fuel_client: provider.client.clone(),
+            fuel_client,
             datatype: PhantomData,
         })
     }
@@ -150,9 +171,9 @@ impl Contract {
     }
 
     /// Loads a compiled contract and deploys it to a running node
-    pub async fn deploy(
+    pub async fn deploy<S: Signer>(
         binary_filepath: &str,
-        wallet: &LocalWallet,
+        wallet: &S,
         params: TxParameters,
     ) -> Result<ContractId, Error> {
         let compiled_contract = Contract::load_sway_contract(binary_filepath).unwrap();
@@ -161,9 +182,9 @@ impl Contract {
     }
 
     /// Loads a compiled contract with salt and deploys it to a running node
-    pub async fn deploy_with_salt(
+    pub async fn deploy_with_salt<S: Signer>(
         binary_filepath: &str,
-        wallet: &LocalWallet,
+        wallet: &S,
         params: TxParameters,
         salt: Salt,
     ) -> Result<ContractId, Error> {
@@ -176,16 +197,22 @@ impl Contract {
     /// Deploys a compiled contract to a running node
     /// To deploy a contract, you need a wallet with enough assets to pay for deployment. This
     /// wallet will also receive the change.
-    pub async fn deploy_loaded(
+    pub async fn deploy_loaded<S: Signer>(
         compiled_contract: &CompiledContract,
-        wallet: &LocalWallet,
+        wallet: &S,
         params: TxParameters,
     ) -> Result<ContractId, Error> {
         let (mut tx, contract_id) =
             Self::contract_deployment_transaction(compiled_contract, wallet, params).await?;
-        wallet.sign_transaction(&mut tx).await?;
+        let provider = wallet.provider().ok_or(WalletError::NoProvider)?;
+        if let Err(e) = wallet.sign_transaction(&mut tx).await {
+            provider.release_coins(tx.inputs()).await;
+            return Err(Error::WalletError(e.to_string()));
+        }
 
-        match wallet.get_provider().unwrap().client.submit(&tx).await {
+        let submitted = provider.client.submit(&tx).await;
+        provider.release_coins(tx.inputs()).await;
+        match submitted {
             Ok(_) => Ok(contract_id),
             Err(e) => Err(Error::TransactionError(e.to_string())),
         }
@@ -204,9 +231,9 @@ impl Contract {
     }
 
     /// Crafts a transaction used to deploy a contract
-    pub async fn contract_deployment_transaction(
+    pub async fn contract_deployment_transaction<S: Signer>(
         compiled_contract: &CompiledContract,
-        wallet: &LocalWallet,
+        wallet: &S,
         params: TxParameters,
     ) -> Result<(Transaction, ContractId), Error> {
         let maturity = 0;
@@ -231,28 +258,35 @@ impl Contract {
         // the witness list.
         let coin_witness_index = 1;
 
-        let inputs = wallet
-            .get_asset_inputs_for_amount(
-                AssetId::default(),
-                DEFAULT_SPENDABLE_COIN_AMOUNT,
+        let provider = wallet.provider().ok_or(WalletError::NoProvider)?;
+        // At least one coin is spent, so that the transaction has a unique id.
+        let base_amount = params.gas_price.saturating_mul(params.gas_limit).max(1);
+
+        let tx = provider
+            .build_paid_transaction(
+                wallet,
+                &HashMap::new(),
+                &[],
+                base_amount,
                 coin_witness_index,
+                |inputs| {
+                    Transaction::create(
+                        params.gas_price,
+                        params.gas_limit,
+                        params.byte_price,
+                        maturity,
+                        bytecode_witness_index,
+                        compiled_contract.salt,
+                        static_contracts.clone(),
+                        storage_slots.clone(),
+                        inputs,
+                        outputs.clone(),
+                        witnesses.clone(),
+                    )
+                },
             )
             .await?;
 
-        let tx = Transaction::create(
-            params.gas_price,
-            params.gas_limit,
-            params.byte_price,
-            maturity,
-            bytecode_witness_index,
-            compiled_contract.salt,
-            static_contracts,
-            storage_slots,
-            inputs,
-            outputs,
-            witnesses,
-        );
-
         Ok((tx, contract_id))
     }
 }
@@ -319,18 +353,22 @@ impl ContractCall {
 
 #[derive(Debug)]
 #[must_use = "contract calls do nothing unless you `call` them"]
-/// Helper that handles submitting a call to a client and formatting the response
-pub struct ContractCallHandler<D> {
+/// Helper that handles submitting a call to a client and formatting the response.
+/// The call is paid for and signed by `wallet`, and submitted through its provider.
+pub struct ContractCallHandler<D, S = LocalWallet> {
     pub contract_call: ContractCall,
     pub tx_parameters: TxParameters,
-    pub wallet: LocalWallet,
-    pub fuel_client: FuelClient,
+    pub wallet: S,
+    /// The client of the wallet's provider when the call was created, if it had one.
+    #[deprecated(note = "calls are submitted through the provider of `wallet`")]
+    pub fuel_client: Option<FuelClient>,
     pub datatype: PhantomData<D>,
 }
 
-impl<D> ContractCallHandler<D>
+impl<D, S> ContractCallHandler<D, S>
 where
     D: Detokenize + Debug,
+    S: Signer + Clone,
 {
     /// Sets external contracts as dependencies to this contract's call.
     /// Effectively, this will be used to create Input::Contract/Output::Contract
@@ -388,22 +426,43 @@ where
     /// transaction.
     #[tracing::instrument]
     async fn call_or_simulate(self, simulate: bool) -> Result<CallResponse<D>, Error> {
-        let script = self.get_script().await;
+        let script = self.get_script().await?;
+        let provider = self.wallet.provider().ok_or(WalletError::NoProvider)?;
+        let inputs = script.tx.inputs().to_vec();
 
-        let receipts = if simulate {
-            script.simulate(&self.fuel_client).await?
+        let result = if simulate {
+            script.simulate(&provider.client).await
         } else {
-            script.call(&self.fuel_client).await?
+            script.call(&provider.client).await
         };
+        // The coins were either spent or, for a simulation or a rejected call, left untouched.
+        provider.release_coins(&inputs).await;
+        let receipts = result?;
         tracing::debug!(target: "receipts", "{:?}", receipts);
 
         self.get_response(receipts)
     }
 
-    pub async fn get_script(&self) -> Script {
+    /// Returns the signed script of the call. The coins paying for it stay reserved until
+    /// they are released with `Provider::release_coins`, or the script is sent with
+    /// `Provider::send_transaction`.
+    pub async fn get_script(&self) -> Result<Script, Error> {
         Script::from_contract_call(&self.contract_call, &self.tx_parameters, &self.wallet).await
     }
 
+    /// Returns the unsigned script of the call, paid for with the given coin inputs
+    /// of the wallet, pointing to witness `0`. It doesn't need a provider, so the
+    /// call can be prepared offline, signed on another machine and submitted later
+    /// with `Provider::send_transaction`. `get_response` decodes the receipts.
+    pub fn get_script_with_inputs(&self, coin_inputs: Vec<Input>) -> Script {
+        Script::from_contract_call_with_inputs(
+            &self.contract_call,
+            &self.tx_parameters,
+            self.wallet.address(),
+            coin_inputs,
+        )
+    }
+
     /// Call a contract's method on the node, in a state-modifying manner.
     pub async fn call(self) -> Result<CallResponse<D>, Error> {
         Self::call_or_simulate(self, false).await
@@ -1,6 +1,10 @@
 pub mod contract;
 pub mod script;
 
+// Used by the mocks generated by the abigen.
+#[doc(hidden)]
+pub use async_trait::async_trait;
+
 pub mod abi_encoder {
     pub use fuels_core::abi_encoder::*;
 }
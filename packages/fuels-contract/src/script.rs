@@ -1,7 +1,7 @@
 use anyhow::Result;
 use fuel_gql_client::fuel_tx::{Input, Output, UtxoId};
 use fuel_gql_client::fuel_types::{
-    bytes::padded_len_usize, AssetId, Bytes32, ContractId, Immediate18, Word,
+    bytes::padded_len_usize, Address, AssetId, Bytes32, ContractId, Immediate18, Word,
 };
 use fuel_gql_client::fuel_vm::consts::VM_TX_MEMORY;
 use fuel_gql_client::fuel_vm::{
@@ -12,12 +12,13 @@ use fuel_gql_client::{
     client::{types::TransactionStatus, FuelClient},
     fuel_tx::{Receipt, Transaction},
 };
-use fuels_core::constants::{DEFAULT_SPENDABLE_COIN_AMOUNT, WORD_SIZE};
+use fuels_core::constants::WORD_SIZE;
 use fuels_core::errors::Error;
 use fuels_core::parameters::TxParameters;
+use std::collections::HashMap;
 
 use crate::contract::ContractCall;
-use fuels_signers::{LocalWallet, Signer};
+use fuels_signers::{wallet::WalletError, Signer};
 
 #[derive(Default)]
 /// Specifies offsets of Opcode::CALL parameters stored in the script
@@ -48,10 +49,61 @@ impl Script {
     /// Creates a Script from a contract call. The internal Transaction is initialized
     /// with the actual script instructions, script data needed to perform the call
     /// and transaction inputs/outputs consisting of assets, external contract ids etc.
-    pub async fn from_contract_call(
+    /// The coins paying for the call are owned by `wallet`, which signs the transaction.
+    pub async fn from_contract_call<S: Signer>(
         call: &ContractCall,
         tx_parameters: &TxParameters,
-        wallet: &LocalWallet,
+        wallet: &S,
+    ) -> Result<Self, Error> {
+        let provider = wallet.provider().ok_or(WalletError::NoProvider)?;
+
+        let forwarded_base_amount = if call.call_parameters.asset_id == AssetId::default() {
+            call.call_parameters.amount
+        } else {
+            0
+        };
+        let mut needed = HashMap::from([(AssetId::default(), forwarded_base_amount)]);
+        if call.call_parameters.asset_id != AssetId::default() {
+            needed.insert(call.call_parameters.asset_id, call.call_parameters.amount);
+        }
+        // At least one coin is spent, so that identical calls have different transaction ids.
+        let base_amount = forwarded_base_amount
+            .saturating_add(
+                tx_parameters
+                    .gas_price
+                    .saturating_mul(tx_parameters.gas_limit),
+            )
+            .max(1);
+
+        let tx = provider
+            .build_paid_transaction(wallet, &needed, &[], base_amount, 0, |coin_inputs| {
+                Self::from_contract_call_with_inputs(
+                    call,
+                    tx_parameters,
+                    wallet.address(),
+                    coin_inputs,
+                )
+                .tx
+            })
+            .await?;
+        let mut script = Self::new(tx);
+        if let Err(e) = wallet.sign_transaction(&mut script.tx).await {
+            provider.release_coins(script.tx.inputs()).await;
+            return Err(Error::WalletError(e.to_string()));
+        }
+
+        Ok(script)
+    }
+
+    /// Creates a Script from a contract call, paid for with the given coin inputs
+    /// instead of coins fetched from a provider, so that it can be built offline.
+    /// The change of each asset spent goes to `owner`. The transaction isn't
+    /// signed: coin inputs owned by `owner` are expected to point to witness `0`.
+    pub fn from_contract_call_with_inputs(
+        call: &ContractCall,
+        tx_parameters: &TxParameters,
+        owner: Address,
+        coin_inputs: Vec<Input>,
     ) -> Self {
         let data_offset = Self::get_data_offset(1);
         let (script_data, call_param_offsets) =
@@ -69,46 +121,19 @@ impl Script {
         );
         inputs.push(self_contract_input);
 
-        let mut spendables = wallet
-            .get_spendable_coins(&AssetId::default(), DEFAULT_SPENDABLE_COIN_AMOUNT as u64)
-            .await
-            .unwrap();
-
-        // add default asset change if any inputs are being spent
-        if !spendables.is_empty() {
-            let change_output = Output::change(wallet.address(), 0, AssetId::default());
-            outputs.push(change_output);
-        }
-
-        if call.call_parameters.asset_id != AssetId::default() {
-            let alt_spendables = wallet
-                .get_spendable_coins(&call.call_parameters.asset_id, call.call_parameters.amount)
-                .await
-                .unwrap();
-
-            // add alt change if inputs are being spent
-            if !alt_spendables.is_empty() {
-                let change_output =
-                    Output::change(wallet.address(), 0, call.call_parameters.asset_id);
-                outputs.push(change_output);
+        // add a change output for each asset being spent
+        let mut spent_assets: Vec<AssetId> = vec![];
+        for input in &coin_inputs {
+            if let Input::CoinSigned { asset_id, .. } | Input::CoinPredicate { asset_id, .. } =
+                input
+            {
+                if !spent_assets.contains(asset_id) {
+                    spent_assets.push(*asset_id);
+                    outputs.push(Output::change(owner, 0, *asset_id));
+                }
             }
-
-            // add alt coins to inputs
-            spendables.extend(alt_spendables.into_iter());
-        }
-
-        for coin in spendables {
-            let input_coin = Input::coin_signed(
-                UtxoId::from(coin.utxo_id),
-                coin.owner.into(),
-                coin.amount.0,
-                coin.asset_id.into(),
-                0,
-                0,
-            );
-
-            inputs.push(input_coin);
         }
+        inputs.extend(coin_inputs);
 
         let n_inputs = inputs.len();
 
@@ -143,7 +168,7 @@ impl Script {
             outputs.extend(v);
         };
 
-        let mut tx = Transaction::script(
+        let tx = Transaction::script(
             tx_parameters.gas_price,
             tx_parameters.gas_limit,
             tx_parameters.byte_price,
@@ -154,7 +179,6 @@ impl Script {
             outputs,
             vec![],
         );
-        wallet.sign_transaction(&mut tx).await.unwrap();
 
         Script::new(tx)
     }
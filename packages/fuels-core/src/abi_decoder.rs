@@ -1,5 +1,5 @@
 use crate::errors::CodecError;
-use crate::{constants::WORD_SIZE, Bits256, ByteArray, ParamType, Token};
+use crate::{constants::WORD_SIZE, ByteArray, ParamType, Token};
 use core::convert::TryInto;
 use core::str;
 use fuel_types::bytes::padded_len;
@@ -116,7 +116,7 @@ impl ABIDecoder {
                 Ok(result)
             }
             ParamType::B256 => {
-                let b256: Bits256 = peek(data, offset, 32)?.try_into().unwrap();
+                let b256: [u8; 32] = peek(data, offset, 32)?.try_into().unwrap();
 
                 let result = DecodeResult {
                     token: Token::B256(b256),
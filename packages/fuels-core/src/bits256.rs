@@ -0,0 +1,197 @@
+use crate::errors::Error;
+use crate::{InvalidOutputType, Token, Tokenizable};
+use fuel_tx::{Address, AssetId, Bytes32, ContractId};
+use hex::FromHex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{Display, Formatter, LowerHex};
+use std::str::FromStr;
+
+/// The Rust-side representation of Sway's `b256`. This is a thin wrapper
+/// around `[u8; 32]` so that a `b256` is tokenized as a single `Token::B256`
+/// rather than as an array of 32 `u8`s, and so that it can be parsed from and
+/// displayed as hex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Bits256(pub [u8; 32]);
+
+impl Bits256 {
+    /// The number of hex characters needed to encode a `b256`, without the
+    /// optional `0x` prefix.
+    pub const HEX_ENC_LENGTH: usize = 64;
+
+    pub const fn zeroed() -> Self {
+        Self([0; 32])
+    }
+
+    /// Parses a `b256` from its hex encoding. The `0x` prefix is optional.
+    pub fn from_hex_str(hex: &str) -> Result<Self, Error> {
+        let hex = hex.strip_prefix("0x").unwrap_or(hex);
+
+        if hex.len() != Self::HEX_ENC_LENGTH {
+            return Err(Error::InvalidData(format!(
+                "the hex encoding of the b256 must have {} characters",
+                Self::HEX_ENC_LENGTH
+            )));
+        }
+
+        Ok(Self(<[u8; 32]>::from_hex(hex)?))
+    }
+}
+
+impl FromStr for Bits256 {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex_str(s)
+    }
+}
+
+impl Display for Bits256 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+impl LowerHex for Bits256 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+impl From<[u8; 32]> for Bits256 {
+    fn from(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Bits256> for [u8; 32] {
+    fn from(bits: Bits256) -> Self {
+        bits.0
+    }
+}
+
+impl AsRef<[u8]> for Bits256 {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+macro_rules! impl_fuel_type_conversions {
+    ($($fuel_type: ty),*) => {
+        $(
+            impl From<$fuel_type> for Bits256 {
+                fn from(value: $fuel_type) -> Self {
+                    Self(*value)
+                }
+            }
+
+            impl From<Bits256> for $fuel_type {
+                fn from(value: Bits256) -> Self {
+                    <$fuel_type>::new(value.0)
+                }
+            }
+        )*
+    };
+}
+
+impl_fuel_type_conversions!(Address, ContractId, AssetId, Bytes32);
+
+impl Tokenizable for Bits256 {
+    fn from_token(token: Token) -> Result<Self, InvalidOutputType> {
+        match token {
+            Token::B256(data) => Ok(Bits256(data)),
+            other => Err(InvalidOutputType(format!(
+                "Expected `b256`, got {:?}",
+                other
+            ))),
+        }
+    }
+
+    fn into_token(self) -> Token {
+        Token::B256(self.0)
+    }
+}
+
+impl Serialize for Bits256 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Bits256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        Self::from_hex_str(&hex).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEX: &str = "d57a9c46dfcc7f18207013e65b44e4cb4e2c2298f4ac457ba8f82743f31e90b5";
+
+    #[test]
+    fn can_be_parsed_with_or_without_prefix() {
+        let without_prefix: Bits256 = HEX.parse().unwrap();
+        let with_prefix: Bits256 = format!("0x{}", HEX).parse().unwrap();
+
+        assert_eq!(without_prefix, with_prefix);
+        assert_eq!(without_prefix.0[0], 0xd5);
+        assert_eq!(without_prefix.0[31], 0xb5);
+    }
+
+    #[test]
+    fn refuses_hex_of_wrong_length() {
+        let err = Bits256::from_hex_str(&HEX[1..]).expect_err("Should have failed on the length");
+
+        assert_eq!(
+            err.to_string(),
+            "Invalid data: the hex encoding of the b256 must have 64 characters"
+        );
+    }
+
+    #[test]
+    fn is_displayed_as_prefixed_hex() {
+        let bits: Bits256 = HEX.parse().unwrap();
+
+        assert_eq!(bits.to_string(), format!("0x{}", HEX));
+        assert_eq!(format!("{:x}", bits), HEX);
+        assert_eq!(format!("{:#x}", bits), format!("0x{}", HEX));
+    }
+
+    #[test]
+    fn converts_to_and_from_fuel_types() {
+        let bits = Bits256([7; 32]);
+
+        assert_eq!(Bits256::from(Address::from(bits)), bits);
+        assert_eq!(Bits256::from(ContractId::from(bits)), bits);
+        assert_eq!(Bits256::from(AssetId::from(bits)), bits);
+        assert_eq!(Bits256::from(Bytes32::from(bits)), bits);
+        assert_eq!(*ContractId::from(bits), [7; 32]);
+    }
+
+    #[test]
+    fn can_be_tokenized_and_detokenized() {
+        let bits = Bits256([1; 32]);
+
+        let token = bits.into_token();
+
+        assert_eq!(token, Token::B256([1; 32]));
+        assert_eq!(Bits256::from_token(token).unwrap(), bits);
+        assert!(Bits256::from_token(Token::U64(1)).is_err());
+    }
+
+    #[test]
+    fn can_be_serialized_and_deserialized() {
+        let bits: Bits256 = HEX.parse().unwrap();
+
+        let serialized = serde_json::to_string(&bits).unwrap();
+        assert_eq!(serialized, format!("\"0x{}\"", HEX));
+
+        let deserialized: Bits256 = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, bits);
+    }
+}
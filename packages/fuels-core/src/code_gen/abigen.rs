@@ -1,15 +1,22 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
 
 use crate::code_gen::bindings::ContractBindings;
+use crate::code_gen::calls_gen::expand_calls;
 use crate::code_gen::custom_types_gen::{
     expand_custom_enum, expand_custom_struct, extract_custom_type_name_from_abi_property,
+    TypeAttributes,
 };
-use crate::code_gen::functions_gen::expand_function;
+use crate::code_gen::forc_project::ForcProject;
+use crate::code_gen::functions_gen::{expand_function, expand_input_name};
+use crate::code_gen::mock_gen::expand_mock;
 use crate::errors::Error;
-use crate::json_abi::ABIParser;
+use crate::json_abi::{parse_param, ABIParser};
 use crate::source::Source;
-use crate::utils::ident;
-use fuels_types::{JsonABI, Property};
+use crate::utils::{ident, safe_ident};
+use fuels_types::{Function, JsonABI, Property, ENUM_KEYWORD, STRUCT_KEYWORD};
+use inflector::Inflector;
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 
@@ -20,6 +27,9 @@ pub struct Abigen {
     /// The parser used to transform the JSON format into `JsonABI`
     abi_parser: ABIParser,
 
+    /// Where the ABI was read from, e.g. the path to its file.
+    abi_origin: String,
+
     /// The contract name as an identifier.
     contract_name: Ident,
 
@@ -27,18 +37,72 @@ pub struct Abigen {
 
     custom_enums: HashMap<String, Property>,
 
-    /// Format the code using a locally installed copy of `rustfmt`.
+    /// The names given in Rust to ABI identifiers, see `with_renames`.
+    renames: HashMap<String, String>,
+
+    /// Format the generated code when writing it out.
     rustfmt: bool,
 
     /// Generate no-std safe code
     no_std: bool,
+
+    /// Generate a mock of the contract, see `with_mock`.
+    mock: bool,
+
+    /// Extra derives and attributes of the custom types.
+    type_attributes: TypeAttributes,
+
+    /// Custom types that are generated in a shared module rather than in the
+    /// contract's own module. Used by `MultiAbigen`.
+    shared_types: HashSet<String>,
+
+    /// The path of the module holding the `shared_types`.
+    shared_types_module: Option<TokenStream>,
+
+    /// The forc project the ABI was read from, if any. Its artifact paths
+    /// are embedded in the generated type.
+    forc_project: Option<ForcProject>,
 }
 
 impl Abigen {
     /// Creates a new contract with the given ABI JSON source.
     pub fn new<S: AsRef<str>>(contract_name: &str, abi_source: S) -> Result<Self, Error> {
-        let source = Source::parse(abi_source).unwrap();
-        let mut parsed_abi: JsonABI = serde_json::from_str(&source.get().unwrap())?;
+        let source =
+            Source::parse(abi_source).map_err(|e| Error::InvalidData(format!("{:#}", e)))?;
+
+        match source {
+            Source::Local(path) => Self::from_abi_file(contract_name, &path),
+            Source::String(abi) => Self::from_json(contract_name, &abi, "the inline ABI".into()),
+        }
+    }
+
+    /// Creates a new contract from the JSON ABI at `path`.
+    pub(crate) fn from_abi_file(contract_name: &str, path: &Path) -> Result<Self, Error> {
+        let abi = fs::read_to_string(path).map_err(|e| {
+            let suggestion = if path.join("Forc.toml").is_file() {
+                format!(
+                    "; it is a forc project, use `project = \"{}\"` to read its ABI",
+                    path.display()
+                )
+            } else {
+                String::new()
+            };
+            Error::InvalidData(format!(
+                "failed to read the ABI at {}: {}{}",
+                path.display(),
+                e,
+                suggestion
+            ))
+        })?;
+
+        Self::from_json(contract_name, &abi, path.display().to_string())
+    }
+
+    // `origin` is where the ABI comes from, used to point at the ABI in
+    // errors.
+    fn from_json(contract_name: &str, abi: &str, origin: String) -> Result<Self, Error> {
+        let mut parsed_abi: JsonABI = serde_json::from_str(abi)
+            .map_err(|e| Error::InvalidData(format!("{}: not a valid JSON ABI: {}", origin, e)))?;
 
         // Filter out outputs with empty returns. These are
         // generated by forc's json abi as `"name": ""` and `"type": "()"`
@@ -53,30 +117,128 @@ impl Abigen {
                 None => continue,
             };
         }
-        let custom_types = Abigen::get_custom_types(&parsed_abi);
+
+        Abigen::validate(&parsed_abi).map_err(|e| with_context(e, &origin))?;
+
+        let custom_types =
+            Abigen::get_custom_types(&parsed_abi).map_err(|e| with_context(e, &origin))?;
+        let (custom_structs, custom_enums) = custom_types
+            .into_iter()
+            .partition(|(_, p)| p.is_struct_type());
         Ok(Self {
-            custom_structs: custom_types
-                .clone()
-                .into_iter()
-                .filter(|(_, p)| p.is_struct_type())
-                .collect(),
-            custom_enums: custom_types
-                .into_iter()
-                .filter(|(_, p)| p.is_enum_type())
-                .collect(),
+            custom_structs,
+            custom_enums,
+            renames: HashMap::new(),
             abi: parsed_abi,
+            abi_origin: origin,
             contract_name: ident(contract_name),
             abi_parser: ABIParser::new(),
             rustfmt: true,
             no_std: false,
+            mock: false,
+            type_attributes: TypeAttributes::new(),
+            shared_types: HashSet::new(),
+            shared_types_module: None,
+            forc_project: None,
         })
     }
 
+    /// Creates a new contract from the artifacts of a built forc project.
+    pub fn from_forc_project(contract_name: &str, project: ForcProject) -> Result<Self, Error> {
+        let mut abigen = Self::from_abi_file(contract_name, &project.abi_path)?;
+        abigen.forc_project = Some(project);
+        Ok(abigen)
+    }
+
     pub fn no_std(mut self) -> Self {
         self.no_std = true;
         self
     }
 
+    /// Also generates the `<Name>Methods` trait, implemented by the contract
+    /// type, and `<Name>Mock`, an implementation of it that can be programmed
+    /// with the results of each call, for unit tests that don't need a node.
+    /// Has no effect on no-std code.
+    pub fn with_mock(mut self) -> Self {
+        self.mock = true;
+        self
+    }
+
+    /// Adds derives and attributes to the generated structs and enums, e.g.
+    /// to serialize them with `serde`. `Bits256` and `SizedAsciiString`
+    /// fields serialize as `0x`-prefixed hex strings and plain strings
+    /// respectively, the same as in [`DecodedValue::to_json`].
+    ///
+    /// [`DecodedValue::to_json`]: crate::DecodedValue::to_json
+    pub fn with_type_attributes(mut self, type_attributes: TypeAttributes) -> Self {
+        self.type_attributes = type_attributes;
+        self
+    }
+
+    /// Gives the ABI identifiers in `renames`, i.e. functions, arguments,
+    /// struct fields, enum variants and custom types, another name in the
+    /// generated code. Every identifier with that name is renamed. The ABI
+    /// itself, and so the encoding of the calls, is left unchanged.
+    ///
+    /// Useful when an identifier collides with a generated one, e.g. a
+    /// function named `new`.
+    pub fn with_renames(mut self, renames: HashMap<String, String>) -> Result<Self, Error> {
+        let abi_names = Abigen::get_identifiers(&self.abi);
+        for (abi_name, rust_name) in &renames {
+            if !abi_names.contains(abi_name.as_str()) {
+                return Err(Error::InvalidName(format!(
+                    "cannot rename `{}`: no function, argument, field, variant or type has that name in {}",
+                    abi_name, self.abi_origin
+                )));
+            }
+            if !is_valid_ident(rust_name) {
+                return Err(Error::InvalidName(format!(
+                    "cannot rename `{}` to `{}`: not a valid Rust identifier",
+                    abi_name, rust_name
+                )));
+            }
+        }
+
+        for function in &mut self.abi {
+            for prop in function
+                .inputs
+                .iter_mut()
+                .chain(function.outputs.iter_mut())
+            {
+                rename_property(prop, &renames);
+            }
+        }
+
+        let custom_types =
+            Abigen::get_custom_types(&self.abi).map_err(|e| with_context(e, &self.abi_origin))?;
+        (self.custom_structs, self.custom_enums) = custom_types
+            .into_iter()
+            .partition(|(_, p)| p.is_struct_type());
+        self.renames = renames;
+
+        Ok(self)
+    }
+
+    /// Imports the custom types named in `shared_types` from `module` instead
+    /// of generating them in the contract's module.
+    pub(crate) fn with_shared_types(
+        mut self,
+        module: TokenStream,
+        shared_types: HashSet<String>,
+    ) -> Self {
+        self.shared_types_module = Some(module);
+        self.shared_types = shared_types;
+        self
+    }
+
+    /// The custom types (both `struct`s and `enum`s) used by the ABI, by name.
+    pub(crate) fn custom_types(&self) -> impl Iterator<Item = (&String, &Property)> {
+        self.custom_structs
+            .iter()
+            .chain(self.custom_enums.iter())
+            .filter(|(name, prop)| !is_native_type(name, prop))
+    }
+
     /// Generates the contract bindings.
     pub fn generate(self) -> Result<ContractBindings, Error> {
         let rustfmt = self.rustfmt;
@@ -95,6 +257,9 @@ impl Abigen {
     /// set of `TokenStream`. This generated Rust code is the brought into scope
     /// after it is called through a procedural macro (`abigen!()` in our case).
     pub fn expand(&self) -> Result<TokenStream, Error> {
+        self.check_collisions()
+            .map_err(|e| with_context(e, &self.abi_origin))?;
+
         let name = &self.contract_name;
         let name_mod = ident(&format!(
             "{}_mod",
@@ -105,11 +270,72 @@ impl Abigen {
         let abi_structs = self.abi_structs()?;
         let abi_enums = self.abi_enums()?;
 
+        let type_includes = expand_type_includes(self.no_std);
+        let shared_types_include = self
+            .shared_types_module
+            .as_ref()
+            .map(|module| quote! { pub use #module::*; });
+
+        let artifact_paths = self.forc_project.as_ref().map(|project| {
+            let bin_path = project.bin_path.display().to_string();
+            let storage_slots_path = match &project.storage_slots_path {
+                Some(path) => {
+                    let path = path.display().to_string();
+                    quote! { Some(#path) }
+                }
+                None => quote! { None },
+            };
+
+            quote! {
+                /// The path to the contract's bytecode, as built by forc.
+                pub const BIN_PATH: &'static str = #bin_path;
+                /// The path to the contract's storage slots, as built by forc.
+                pub const STORAGE_SLOTS_PATH: Option<&'static str> = #storage_slots_path;
+            }
+        });
+
+        let functions: Vec<_> = self
+            .abi
+            .iter()
+            .map(|function| (self.function_ident(function), function))
+            .collect();
+
+        let calls = if self.no_std {
+            None
+        } else {
+            Some(
+                expand_calls(
+                    &self.contract_name,
+                    &functions,
+                    &self.abi_parser,
+                    &self.custom_enums,
+                    &self.custom_structs,
+                )
+                .map_err(|e| {
+                    with_context(e, &format!("{}: in the function calls", self.abi_origin))
+                })?,
+            )
+        };
+
+        let mock = if self.mock && !self.no_std {
+            Some(
+                expand_mock(
+                    &self.contract_name,
+                    &functions,
+                    &self.custom_enums,
+                    &self.custom_structs,
+                )
+                .map_err(|e| with_context(e, &format!("{}: in the mock", self.abi_origin)))?,
+            )
+        } else {
+            None
+        };
+
         let (includes, code) = if self.no_std {
             (
                 quote! {
-                    use alloc::{vec, vec::Vec};
-                    use fuels_core::{Detokenize, EnumSelector, InvalidOutputType, Parameterize, ParamType, Tokenizable, Token};
+                    #type_includes
+                    #shared_types_include
                 },
                 quote! {},
             )
@@ -117,25 +343,45 @@ impl Abigen {
             (
                 quote! {
                     use fuels::contract::contract::{Contract, ContractCallHandler};
-                    use fuels::signers::LocalWallet;
-                    use fuels::tx::{ContractId, Address};
-                    use fuels::core::{Detokenize, EnumSelector, InvalidOutputType, Parameterize, ParamType, Tokenizable, Token};
-                    use std::str::FromStr;
+                    use fuels::signers::{provider::ProviderError, wallet::WalletError, LocalWallet, Signer};
+                    #type_includes
+                    #shared_types_include
+                    use std::collections::HashMap;
                 },
                 quote! {
-                    pub struct #name {
+                    pub struct #name<S = LocalWallet> {
                         contract_id: ContractId,
-                        wallet: LocalWallet
+                        wallet: S
                     }
 
-                    impl #name {
-                        pub fn new(contract_id: String, wallet: LocalWallet)
-                        -> Self {
-                            let contract_id = ContractId::from_str(&contract_id).expect("Invalid contract id");
-                            Self{ contract_id, wallet }
+                    impl<S: Signer + Clone> #name<S> {
+                        #artifact_paths
+
+                        /// Creates an instance of the contract deployed at `contract_id`,
+                        /// calling it with `wallet`, which must have a provider.
+                        pub fn new(contract_id: impl Into<ContractId>, wallet: S) -> Self {
+                            Self{ contract_id: contract_id.into(), wallet }
+                        }
+
+                        /// Returns an instance of the same contract called with `wallet`
+                        /// instead.
+                        pub fn with_wallet<T: Signer + Clone>(&self, wallet: T) -> #name<T> {
+                            #name{ contract_id: self.contract_id, wallet }
+                        }
+
+                        /// Gets the contract's balance of each of `asset_ids`, keyed by
+                        /// their `0x`-prefixed hex id.
+                        pub async fn get_balances(&self, asset_ids: &[AssetId]) -> Result<HashMap<String, u64>, ProviderError> {
+                            let provider = self.wallet.provider().ok_or(WalletError::NoProvider)?;
+                            Contract::get_balances(provider, &self.contract_id, asset_ids).await
                         }
+
                         #contract_functions
                     }
+
+                    #calls
+
+                    #mock
                 },
             )
         };
@@ -162,13 +408,23 @@ impl Abigen {
     pub fn functions(&self) -> Result<TokenStream, Error> {
         let mut tokenized_functions = Vec::new();
 
-        for function in &self.abi {
+        for (idx, function) in self.abi.iter().enumerate() {
             let tokenized_fn = expand_function(
                 function,
+                &self.function_ident(function),
                 &self.abi_parser,
                 &self.custom_enums,
                 &self.custom_structs,
-            )?;
+            )
+            .map_err(|e| {
+                with_context(
+                    e,
+                    &format!(
+                        "{}: in function `{}`, at `[{}]`",
+                        self.abi_origin, function.name, idx
+                    ),
+                )
+            })?;
             tokenized_functions.push(tokenized_fn);
         }
 
@@ -181,16 +437,24 @@ impl Abigen {
         // Prevent expanding the same struct more than once
         let mut seen_struct: Vec<&str> = vec![];
 
-        for prop in self.custom_structs.values() {
-            // Skip custom type generation if the custom type is a Sway-native type.
-            // This means ABI methods receiving or returning a Sway-native type
-            // can receive or return that native type directly.
-            if prop.type_field.contains("ContractId") || prop.type_field.contains("Address") {
+        // Sort the structs by name so that the generated code is the same
+        // from one run to the next.
+        let mut custom_structs: Vec<_> = self.custom_structs.iter().collect();
+        custom_structs.sort_by_key(|(name, _)| *name);
+
+        for (name, prop) in custom_structs {
+            // Skip Sway-native types, as well as the types that are generated
+            // in a shared module.
+            if is_native_type(name, prop) || self.shared_types.contains(name) {
                 continue;
             }
 
             if !seen_struct.contains(&prop.type_field.as_str()) {
-                structs.extend(expand_custom_struct(prop)?);
+                structs.extend(
+                    expand_custom_struct(prop, &self.type_attributes).map_err(|e| {
+                        with_context(e, &format!("{}: in struct `{}`", self.abi_origin, name))
+                    })?,
+                );
                 seen_struct.push(&prop.type_field);
             }
         }
@@ -201,13 +465,197 @@ impl Abigen {
     fn abi_enums(&self) -> Result<TokenStream, Error> {
         let mut enums = TokenStream::new();
 
-        for (name, prop) in &self.custom_enums {
-            enums.extend(expand_custom_enum(name, prop)?);
+        let mut custom_enums: Vec<_> = self.custom_enums.iter().collect();
+        custom_enums.sort_by_key(|(name, _)| *name);
+
+        for (name, prop) in custom_enums {
+            if is_native_type(name, prop) || self.shared_types.contains(name) {
+                continue;
+            }
+            enums.extend(
+                expand_custom_enum(name, prop, &self.type_attributes).map_err(|e| {
+                    with_context(e, &format!("{}: in enum `{}`", self.abi_origin, name))
+                })?,
+            );
         }
 
         Ok(enums)
     }
 
+    // The name of the generated method calling `function`.
+    fn function_ident(&self, function: &Function) -> Ident {
+        safe_ident(self.renames.get(&function.name).unwrap_or(&function.name))
+    }
+
+    // Checks that no two ABI identifiers end up with the same name in the
+    // generated code, and that none of them collides with a generated item.
+    fn check_collisions(&self) -> Result<(), Error> {
+        let mut functions = Names::new("function");
+        if !self.no_std {
+            for method in RESERVED_METHODS {
+                functions.reserve(
+                    method,
+                    &format!("the generated `{}::{}`", self.contract_name, method),
+                );
+            }
+        }
+        // The functions are also the variants of `<Name>Function` and `<Name>Call`, in class
+        // case, so names that only differ by their case collide there.
+        let mut variants = Names::new("function");
+        for function in &self.abi {
+            let ident = self.function_ident(function);
+            if !self.no_std {
+                let variant = ident.to_string().trim_start_matches("r#").to_class_case();
+                variants.insert(
+                    &function.name,
+                    format!("{}Function::{}", self.contract_name, safe_ident(&variant)),
+                )?;
+            }
+            functions.insert(&function.name, ident)?;
+
+            let mut args = Names::new(&format!("argument of `{}`", function.name));
+            for (idx, arg) in function.inputs.iter().enumerate() {
+                args.insert(&arg.name, expand_input_name(idx, &arg.name))?;
+            }
+        }
+
+        let mut types = Names::new("type");
+        for generated in self.generated_types() {
+            types.reserve(&generated, &format!("the generated `{}`", generated));
+        }
+        for reserved in RESERVED_TYPES {
+            types.reserve(reserved, &format!("the SDK's `{}`", reserved));
+        }
+        if !self.no_std {
+            types.reserve(
+                "S",
+                &format!("the signer type parameter of `{}`", self.contract_name),
+            );
+        }
+        for (type_name, prop) in self.custom_types() {
+            types.insert(type_name, safe_ident(&type_name.to_class_case()))?;
+
+            let (kind, case): (_, fn(&str) -> String) = if prop.is_struct_type() {
+                ("field of struct", |name| name.to_snake_case())
+            } else {
+                ("variant of enum", |name| name.to_class_case())
+            };
+            let mut members = Names::new(&format!("{} `{}`", kind, type_name));
+            for member in prop.components.iter().flatten() {
+                members.insert(&member.name, safe_ident(&case(&member.name)))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // The names of the types generated next to the custom types.
+    fn generated_types(&self) -> Vec<String> {
+        let name = self.contract_name.to_string();
+        let mut generated = vec![name.clone()];
+        if !self.no_std {
+            for suffix in ["Function", "Call"] {
+                generated.push(format!("{}{}", name, suffix));
+            }
+        }
+        if self.mock && !self.no_std {
+            for suffix in ["Methods", "Mock"] {
+                generated.push(format!("{}{}", name, suffix));
+            }
+        }
+        generated
+    }
+
+    // All the identifiers found in the ABI: the names of the functions, of
+    // their arguments, and of the custom types along with their members.
+    fn get_identifiers(abi: &JsonABI) -> HashSet<String> {
+        fn collect(prop: &Property, identifiers: &mut HashSet<String>) {
+            identifiers.insert(prop.name.clone());
+            if prop.is_custom_type() {
+                for type_name in custom_type_names(&prop.type_field) {
+                    identifiers.insert(type_name.to_string());
+                }
+            }
+            for component in prop.components.iter().flatten() {
+                collect(component, identifiers);
+            }
+        }
+
+        let mut identifiers = HashSet::new();
+        for function in abi {
+            identifiers.insert(function.name.clone());
+            for prop in function.inputs.iter().chain(&function.outputs) {
+                collect(prop, &mut identifiers);
+            }
+        }
+        identifiers
+    }
+
+    // Checks that every function and type of the ABI can be expanded, so that
+    // a faulty ABI is reported along with the JSON location of the offending
+    // function or property, e.g. `[1].inputs[0].components[2]`.
+    fn validate(abi: &JsonABI) -> Result<(), Error> {
+        for (idx, function) in abi.iter().enumerate() {
+            let location = format!("[{}]", idx);
+            let in_function = |e| {
+                with_context(
+                    e,
+                    &format!("in function `{}`, at `{}`", function.name, location),
+                )
+            };
+
+            if !is_valid_ident(&function.name) {
+                return Err(in_function(Error::InvalidName(format!(
+                    "`{}` is not a valid Rust identifier",
+                    function.name
+                ))));
+            }
+
+            let properties = function
+                .inputs
+                .iter()
+                .enumerate()
+                .map(|(i, prop)| (format!("{}.inputs[{}]", location, i), prop))
+                .chain(
+                    function
+                        .outputs
+                        .iter()
+                        .enumerate()
+                        .map(|(i, prop)| (format!("{}.outputs[{}]", location, i), prop)),
+                );
+            for (location, prop) in properties {
+                Abigen::validate_property(prop, &location)
+                    .map_err(|e| with_context(e, &format!("in function `{}`", function.name)))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_property(prop: &Property, location: &str) -> Result<(), Error> {
+        // Report the innermost faulty property.
+        for (idx, component) in prop.components.iter().flatten().enumerate() {
+            Abigen::validate_property(component, &format!("{}.components[{}]", location, idx))?;
+        }
+
+        let at_location = |e| with_context(e, &format!("at `{}` (`{}`)", location, prop.name));
+
+        parse_param(prop).map_err(at_location)?;
+
+        if prop.is_struct_type() || prop.is_enum_type() || prop.has_custom_type_in_array() {
+            let name =
+                extract_custom_type_name_from_abi_property(prop, None).map_err(at_location)?;
+            if !is_valid_ident(&name) {
+                return Err(at_location(Error::InvalidName(format!(
+                    "`{}` is not a valid Rust identifier",
+                    name
+                ))));
+            }
+        }
+
+        Ok(())
+    }
+
     fn get_all_properties(abi: &JsonABI) -> Vec<&Property> {
         let mut all_properties: Vec<&Property> = vec![];
         for function in abi {
@@ -223,47 +671,46 @@ impl Abigen {
 
     // Extracts the custom type from a `Property`. This custom type lives
     // inside an array, in the form of `[struct | enum; length]`.
-    fn get_custom_type_in_array(prop: &Property) -> HashMap<String, &Property> {
+    fn get_custom_type_in_array(prop: &Property) -> Result<HashMap<String, &Property>, Error> {
         let mut custom_types = HashMap::new();
 
         // Custom type in an array looks like `[struct Person; 2]`.
         // The `components` will hold only one element, which is the custom type.
-        let array_custom_type = prop
-            .components
-            .as_ref()
-            .expect("array should have components")
-            .first()
-            .expect("components in array should have at least one component");
+        let array_custom_type = components_of(prop)?.first().ok_or_else(|| {
+            Error::MissingData(format!(
+                "the components of array `{}` are empty",
+                prop.type_field
+            ))
+        })?;
 
-        let custom_type_name = extract_custom_type_name_from_abi_property(array_custom_type, None)
-            .expect("failed to extract custom type name");
+        let custom_type_name = extract_custom_type_name_from_abi_property(array_custom_type, None)?;
 
         custom_types.insert(custom_type_name, array_custom_type);
 
-        custom_types
+        Ok(custom_types)
     }
 
     // Extracts the custom type from a `Property`. These custom types live
     // inside a tuple, in the form of `((struct | enum) <custom_type_name>, *)`.
-    fn get_custom_types_in_tuple(prop: &Property) -> HashMap<String, &Property> {
+    fn get_custom_types_in_tuple(prop: &Property) -> Result<HashMap<String, &Property>, Error> {
         let mut custom_types = HashMap::new();
 
         // Tuples can have `n` custom types within them.
-        for tuple_type in prop.components.as_ref().unwrap().iter() {
+        for tuple_type in components_of(prop)? {
             if tuple_type.is_struct_type() || tuple_type.is_enum_type() {
-                let custom_type_name = extract_custom_type_name_from_abi_property(tuple_type, None)
-                    .expect("failed to extract custom type name");
+                let custom_type_name =
+                    extract_custom_type_name_from_abi_property(tuple_type, None)?;
                 custom_types.insert(custom_type_name, tuple_type);
             }
         }
 
-        custom_types
+        Ok(custom_types)
     }
 
     /// Reads the parsed ABI and returns the custom types (either `struct` or `enum`) found in it.
     /// Custom types can be in the free form (`Struct Person`, `Enum State`), inside arrays (`[struct Person; 2]`, `[enum State; 2]`)), or
     /// inside tuples (`(struct Person, struct Address)`, `(enum State, enum Country)`).
-    fn get_custom_types(abi: &JsonABI) -> HashMap<String, Property> {
+    fn get_custom_types(abi: &JsonABI) -> Result<HashMap<String, Property>, Error> {
         let mut custom_types = HashMap::new();
         let mut nested_custom_types: Vec<Property> = Vec::new();
 
@@ -276,17 +723,16 @@ impl Abigen {
         for prop in all_custom_properties {
             let custom_type = match prop.has_custom_type_in_array() {
                 // Custom type lives inside array.
-                true => Abigen::get_custom_type_in_array(prop),
+                true => Abigen::get_custom_type_in_array(prop)?,
                 false => match prop.has_custom_type_in_tuple() {
                     // Custom type lives inside tuple.
-                    true => Abigen::get_custom_types_in_tuple(prop),
+                    true => Abigen::get_custom_types_in_tuple(prop)?,
                     // Free form custom type.
                     false => {
                         let mut custom_types = HashMap::new();
 
                         let custom_type_name =
-                            extract_custom_type_name_from_abi_property(prop, None)
-                                .expect("failed to extract custom type name");
+                            extract_custom_type_name_from_abi_property(prop, None)?;
 
                         custom_types.insert(custom_type_name, prop);
 
@@ -297,18 +743,12 @@ impl Abigen {
 
             for (custom_type_name, custom_type) in custom_type {
                 // Store the custom name and the custom type itself in the map.
-                custom_types
-                    .entry(custom_type_name)
-                    .or_insert_with(|| custom_type.clone());
+                insert_custom_type(&mut custom_types, custom_type_name, custom_type)?;
 
                 // Find inner {structs, enums} in case of nested custom types
-                for inner_component in custom_type
-                    .components
-                    .as_ref()
-                    .expect("Custom type should have components")
-                {
+                for inner_component in components_of(custom_type)? {
                     nested_custom_types
-                        .extend(Abigen::get_nested_custom_properties(inner_component));
+                        .extend(Abigen::get_nested_custom_properties(inner_component)?);
                 }
             }
         }
@@ -316,34 +756,246 @@ impl Abigen {
         for nested_custom_type in nested_custom_types {
             // A {struct, enum} can contain another {struct, enum}
             let nested_custom_type_name =
-                extract_custom_type_name_from_abi_property(&nested_custom_type, None)
-                    .expect("failed to extract nested custom type name");
-            custom_types
-                .entry(nested_custom_type_name)
-                .or_insert(nested_custom_type);
+                extract_custom_type_name_from_abi_property(&nested_custom_type, None)?;
+            insert_custom_type(
+                &mut custom_types,
+                nested_custom_type_name,
+                &nested_custom_type,
+            )?;
         }
 
-        custom_types
+        Ok(custom_types)
     }
 
     // Recursively gets inner properties defined in nested structs or nested enums
-    fn get_nested_custom_properties(prop: &Property) -> Vec<Property> {
+    fn get_nested_custom_properties(prop: &Property) -> Result<Vec<Property>, Error> {
         let mut props = Vec::new();
 
         if prop.is_custom_type() {
             props.push(prop.clone());
 
-            for inner_prop in prop
-                .components
-                .as_ref()
-                .expect("(inner) custom type should have components")
-            {
-                let inner = Abigen::get_nested_custom_properties(inner_prop);
+            for inner_prop in components_of(prop)? {
+                let inner = Abigen::get_nested_custom_properties(inner_prop)?;
                 props.extend(inner);
             }
         }
 
-        props
+        Ok(props)
+    }
+}
+
+/// The methods generated on the contract type, besides the contract's
+/// functions.
+const RESERVED_METHODS: [&str; 3] = ["get_balances", "new", "with_wallet"];
+
+/// Whether `prop` is one of the Sway-native types, which implement
+/// `Tokenizable` and `Parameterize` in the SDK. They aren't generated, so that
+/// ABI methods receiving or returning one of them can receive or return the
+/// SDK's type directly.
+///
+/// A custom type is only taken for a native one if it also has its layout, so
+/// that a user-defined type that happens to share its name is generated (and
+/// has to be renamed) instead of being decoded as something else.
+fn is_native_type(type_name: &str, prop: &Property) -> bool {
+    let components = prop.components.as_deref().unwrap_or_default();
+    match type_name {
+        "Address" | "AssetId" | "ContractId" => {
+            prop.is_struct_type() && matches!(components, [value] if value.type_field == "b256")
+        }
+        "Identity" => {
+            prop.is_enum_type()
+                && matches!(components, [address, contract_id]
+                    if address.name == "Address"
+                        && address.type_field == "struct Address"
+                        && is_native_type("Address", address)
+                        && contract_id.name == "ContractId"
+                        && contract_id.type_field == "struct ContractId"
+                        && is_native_type("ContractId", contract_id))
+        }
+        _ => false,
+    }
+}
+
+/// The types imported by the generated code.
+const RESERVED_TYPES: [&str; 20] = [
+    "Address",
+    "AssetId",
+    "Bits256",
+    "Contract",
+    "ContractCallHandler",
+    "ContractId",
+    "Detokenize",
+    "EnumSelector",
+    "HashMap",
+    "Identity",
+    "InvalidOutputType",
+    "LocalWallet",
+    "ParamType",
+    "Parameterize",
+    "ProviderError",
+    "Signer",
+    "SizedAsciiString",
+    "Token",
+    "Tokenizable",
+    "WalletError",
+];
+
+// Tracks the names that a kind of ABI identifier is given in the generated
+// code, to report two of them ending up with the same name.
+struct Names {
+    kind: String,
+    // The generated name, and a description of the item that has it.
+    taken: HashMap<String, String>,
+}
+
+impl Names {
+    fn new(kind: &str) -> Self {
+        Self {
+            kind: kind.to_string(),
+            taken: HashMap::new(),
+        }
+    }
+
+    fn reserve(&mut self, generated: &str, owner: &str) {
+        self.taken.insert(generated.to_string(), owner.to_string());
+    }
+
+    fn insert(&mut self, abi_name: &str, generated: impl ToString) -> Result<(), Error> {
+        let generated = generated.to_string();
+        match self.taken.get(&generated) {
+            Some(owner) => Err(Error::InvalidName(format!(
+                "{} `{}` is generated as `{}`, which collides with {}; \
+                use `rename(\"{}\" = \"...\")` to give it another name",
+                self.kind, abi_name, generated, owner, abi_name
+            ))),
+            None => {
+                let owner = format!("{} `{}`", self.kind, abi_name);
+                self.taken.insert(generated, owner);
+                Ok(())
+            }
+        }
+    }
+}
+
+// Renames the property, and the custom types it refers to, according to
+// `renames`. Its components are renamed as well.
+fn rename_property(prop: &mut Property, renames: &HashMap<String, String>) {
+    if let Some(new_name) = renames.get(&prop.name) {
+        prop.name = new_name.clone();
+    }
+
+    if prop.is_custom_type() {
+        for type_name in custom_type_names(&prop.type_field.clone()) {
+            if let Some(new_name) = renames.get(type_name) {
+                for keyword in [STRUCT_KEYWORD, ENUM_KEYWORD] {
+                    prop.type_field = replace_word(
+                        &prop.type_field,
+                        &format!("{}{}", keyword, type_name),
+                        &format!("{}{}", keyword, new_name),
+                    );
+                }
+            }
+        }
+    }
+
+    for component in prop.components.iter_mut().flatten() {
+        rename_property(component, renames);
+    }
+}
+
+// The names of the custom types in a type field, e.g. `Foo` and `Bar` in
+// `(struct Foo, [enum Bar; 2])`.
+fn custom_type_names(type_field: &str) -> Vec<&str> {
+    let mut names = vec![];
+    for keyword in [STRUCT_KEYWORD, ENUM_KEYWORD] {
+        for (idx, _) in type_field.match_indices(keyword) {
+            let rest = &type_field[idx + keyword.len()..];
+            let end = rest
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            names.push(&rest[..end]);
+        }
+    }
+    names
+}
+
+// Replaces the occurrences of `from` in `text` that aren't followed by an
+// identifier character.
+fn replace_word(text: &str, from: &str, to: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(idx) = rest.find(from) {
+        let after = &rest[idx + from.len()..];
+        let is_word = !after.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_');
+        result.push_str(&rest[..idx]);
+        result.push_str(if is_word { to } else { from });
+        rest = after;
+    }
+    result.push_str(rest);
+    result
+}
+
+// Two custom types can't share a name, unless they are the same type used in
+// more than one place.
+fn insert_custom_type(
+    custom_types: &mut HashMap<String, Property>,
+    name: String,
+    prop: &Property,
+) -> Result<(), Error> {
+    match custom_types.get(&name) {
+        Some(existing) if existing.type_field != prop.type_field => {
+            Err(Error::InvalidType(format!(
+                "`{}` and `{}` share the name `{}`",
+                existing.type_field, prop.type_field, name
+            )))
+        }
+        Some(existing) if existing.components != prop.components => Err(Error::InvalidType(
+            format!("`{}` has conflicting definitions", prop.type_field),
+        )),
+        Some(_) => Ok(()),
+        None => {
+            custom_types.insert(name, prop.clone());
+            Ok(())
+        }
+    }
+}
+
+fn components_of(prop: &Property) -> Result<&Vec<Property>, Error> {
+    prop.components
+        .as_ref()
+        .ok_or_else(|| Error::MissingData(format!("`{}` has no `components`", prop.type_field)))
+}
+
+fn is_valid_ident(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// Prefixes the message of `err` with `context`, keeping its kind.
+fn with_context(err: Error, context: &str) -> Error {
+    match err {
+        Error::InvalidName(msg) => Error::InvalidName(format!("{}: {}", context, msg)),
+        Error::InvalidType(msg) => Error::InvalidType(format!("{}: {}", context, msg)),
+        Error::MissingData(msg) => Error::MissingData(format!("{}: {}", context, msg)),
+        Error::InvalidData(msg) => Error::InvalidData(format!("{}: {}", context, msg)),
+        other => Error::InvalidData(format!("{}: {}", context, other)),
+    }
+}
+
+/// Expands the imports needed by generated custom types.
+pub(crate) fn expand_type_includes(no_std: bool) -> TokenStream {
+    if no_std {
+        quote! {
+            use alloc::{vec, vec::Vec};
+            use fuels_core::{Bits256, Detokenize, EnumSelector, Identity, InvalidOutputType, Parameterize, ParamType, SizedAsciiString, Tokenizable, Token};
+            use fuels_core::tx::{Address, AssetId, ContractId};
+        }
+    } else {
+        quote! {
+            use fuels::core::{Bits256, Detokenize, EnumSelector, Identity, InvalidOutputType, Parameterize, ParamType, SizedAsciiString, Tokenizable, Token};
+            use fuels::tx::{Address, AssetId, ContractId};
+        }
     }
 }
 
@@ -472,7 +1124,7 @@ mod tests {
                         },
                         {
                             "name":"b",
-                            "type":"u8[2]"
+                            "type":"[u8; 2]"
                         }
                         ]
                     }
@@ -711,6 +1363,54 @@ mod tests {
         assert_eq!(contract.custom_enums.len(), 1);
     }
 
+    #[test]
+    fn formatted_bindings_are_reproducible() {
+        let contract = r#"
+        [
+            {
+                "type":"contract",
+                "inputs":[
+                    {
+                        "name":"a",
+                        "type":"struct StructA",
+                        "components": [{ "name": "x", "type": "u64" }]
+                    },
+                    {
+                        "name":"b",
+                        "type":"struct StructB",
+                        "components": [{ "name": "y", "type": "bool" }]
+                    },
+                    {
+                        "name":"c",
+                        "type":"struct StructC",
+                        "components": [{ "name": "z", "type": "u32" }]
+                    }
+                ],
+                "name":"takes_structs",
+                "outputs":[]
+            }
+        ]
+        "#;
+
+        let generate = || {
+            let mut source = Vec::new();
+            Abigen::new("custom", contract)
+                .unwrap()
+                .generate()
+                .unwrap()
+                .write(&mut source)
+                .unwrap();
+            String::from_utf8(source).unwrap()
+        };
+
+        let source = generate();
+
+        assert!(source.contains("    pub struct StructA {\n        pub x: u64,\n    }\n"));
+        for _ in 0..10 {
+            assert_eq!(generate(), source);
+        }
+    }
+
     #[test]
     fn test_get_custom_types_nested_structs_and_enums() {
         let contract = r#"
@@ -984,4 +1684,378 @@ mod tests {
 
         let _bindings = contract.generate().unwrap();
     }
+
+    #[test]
+    fn unsupported_types_are_reported_with_their_location() {
+        let contract = r#"
+        [
+            {
+                "type":"function",
+                "inputs":[
+                    {
+                        "name":"ok",
+                        "type":"u64"
+                    },
+                    {
+                        "name":"value",
+                        "type":"struct MyStruct",
+                        "components":[
+                            {
+                                "name":"big",
+                                "type":"u128"
+                            }
+                        ]
+                    }
+                ],
+                "name":"takes_struct",
+                "outputs":[
+                    {
+                        "name":"",
+                        "type":"bol"
+                    }
+                ]
+            }
+        ]
+        "#;
+
+        let err = Abigen::new("custom", contract)
+            .err()
+            .expect("Should have failed on the `u128` field");
+
+        assert_eq!(
+            err.to_string(),
+            "Invalid type: the inline ABI: in function `takes_struct`: at `[0].inputs[1].components[0]` (`big`): \
+            unsupported type `u128`, expected one of `()`, `bool`, `u8`, `u16`, `u32`, `u64`, `byte`, `b256`, \
+            `str[N]`, `[T; N]`, a tuple, `struct <name>` or `enum <name>`"
+        );
+
+        let contract = contract.replace("u128", "u8");
+        let err = Abigen::new("custom", contract)
+            .err()
+            .expect("Should have failed on the `bol` output");
+
+        assert_eq!(
+            err.to_string(),
+            "Invalid type: the inline ABI: in function `takes_struct`: at `[0].outputs[0]` (``): \
+            unsupported type `bol`, did you mean `bool`?"
+        );
+    }
+
+    #[test]
+    fn missing_abi_files_are_reported_with_their_path() {
+        let project = tempfile::tempdir().unwrap();
+        let project_dir = project.path();
+        fs::write(project_dir.join("Forc.toml"), "").unwrap();
+
+        let err = Abigen::new("custom", project_dir.display().to_string())
+            .err()
+            .expect("Should have failed to read a directory");
+
+        let msg = err.to_string();
+        assert!(msg.starts_with(&format!(
+            "Invalid data: failed to read the ABI at {}: ",
+            project_dir.display()
+        )));
+        assert!(msg.ends_with(&format!(
+            "; it is a forc project, use `project = \"{}\"` to read its ABI",
+            project_dir.display()
+        )));
+    }
+
+    fn keyword_contract() -> &'static str {
+        r#"
+        [
+            {
+                "type":"function",
+                "inputs":[
+                    {
+                        "name":"type",
+                        "type":"u64"
+                    },
+                    {
+                        "name":"value",
+                        "type":"struct my_struct",
+                        "components":[
+                            {
+                                "name":"match",
+                                "type":"bool"
+                            }
+                        ]
+                    }
+                ],
+                "name":"new",
+                "outputs":[]
+            }
+        ]
+        "#
+    }
+
+    #[test]
+    fn collisions_with_generated_names_are_errors() {
+        let err = Abigen::new("custom", keyword_contract())
+            .unwrap()
+            .generate()
+            .expect_err("Should have failed on the `new` function");
+
+        assert_eq!(
+            err.to_string(),
+            "Invalid name: the inline ABI: function `new` is generated as `new`, which collides \
+            with the generated `custom::new`; use `rename(\"new\" = \"...\")` to give it another name"
+        );
+
+        let contract = keyword_contract().replace("my_struct", "Token");
+        let renames = HashMap::from([("new".to_string(), "create".to_string())]);
+        let err = Abigen::new("custom", contract)
+            .unwrap()
+            .with_renames(renames)
+            .unwrap()
+            .generate()
+            .expect_err("Should have failed on the `Token` struct");
+
+        assert!(
+            matches!(err, Error::InvalidName(msg) if msg.contains("collides with the SDK's `Token`"))
+        );
+
+        let contract = keyword_contract().replace("my_struct", "S");
+        let renames = HashMap::from([("new".to_string(), "create".to_string())]);
+        let err = Abigen::new("custom", contract)
+            .unwrap()
+            .with_renames(renames)
+            .unwrap()
+            .generate()
+            .expect_err("Should have failed on the `S` struct");
+
+        assert!(matches!(err, Error::InvalidName(msg)
+            if msg.contains("collides with the signer type parameter of `custom`")));
+    }
+
+    #[test]
+    fn functions_with_the_same_call_variant_are_errors() {
+        let contract = r#"
+        [
+            {
+                "type":"function",
+                "inputs":[],
+                "name":"foo_bar",
+                "outputs":[]
+            },
+            {
+                "type":"function",
+                "inputs":[],
+                "name":"fooBar",
+                "outputs":[]
+            }
+        ]
+        "#;
+
+        let err = Abigen::new("custom", contract)
+            .unwrap()
+            .generate()
+            .expect_err("Should have failed on the `FooBar` variant");
+
+        assert_eq!(
+            err.to_string(),
+            "Invalid name: the inline ABI: function `fooBar` is generated as \
+            `customFunction::FooBar`, which collides with function `foo_bar`; \
+            use `rename(\"fooBar\" = \"...\")` to give it another name"
+        );
+
+        let renames = HashMap::from([("fooBar".to_string(), "baz".to_string())]);
+        Abigen::new("custom", contract)
+            .unwrap()
+            .with_renames(renames)
+            .unwrap()
+            .generate()
+            .unwrap();
+    }
+
+    #[test]
+    fn renames_and_escapes_identifiers() {
+        let renames = HashMap::from([
+            ("new".to_string(), "create".to_string()),
+            ("my_struct".to_string(), "Renamed".to_string()),
+        ]);
+
+        let mut source = Vec::new();
+        Abigen::new("custom", keyword_contract())
+            .unwrap()
+            .with_renames(renames)
+            .unwrap()
+            .generate()
+            .unwrap()
+            .write(&mut source)
+            .unwrap();
+        let bindings = String::from_utf8(source).unwrap();
+
+        assert!(bindings.contains("pub fn create(&self, r#type: u64, value: Renamed)"));
+        assert!(bindings.contains("pub struct Renamed {\n        pub r#match: bool,\n    }"));
+        // The selector is still computed from the name in the ABI.
+        assert!(bindings.contains("Calls the contract's `new`"));
+
+        let renames = HashMap::from([("unknown".to_string(), "known".to_string())]);
+        let err = Abigen::new("custom", keyword_contract())
+            .unwrap()
+            .with_renames(renames)
+            .err()
+            .expect("Should have failed on the unknown name");
+        assert!(
+            matches!(err, Error::InvalidName(msg) if msg.starts_with("cannot rename `unknown`"))
+        );
+    }
+
+    #[test]
+    fn custom_types_with_the_same_name_are_errors() {
+        let contract = r#"
+        [
+            {
+                "type":"function",
+                "inputs":[
+                    {
+                        "name":"a",
+                        "type":"struct MyStruct",
+                        "components":[{ "name":"x", "type":"u64" }]
+                    },
+                    {
+                        "name":"b",
+                        "type":"struct my_struct",
+                        "components":[{ "name":"x", "type":"u64" }]
+                    }
+                ],
+                "name":"takes_structs",
+                "outputs":[]
+            }
+        ]
+        "#;
+
+        let err = Abigen::new("custom", contract)
+            .unwrap()
+            .generate()
+            .expect_err("Should have failed on `my_struct`");
+        assert!(matches!(err, Error::InvalidName(msg)
+            if msg.contains("type `my_struct` is generated as `MyStruct`, which collides with type `MyStruct`")
+                || msg.contains("type `MyStruct` is generated as `MyStruct`, which collides with type `my_struct`")));
+
+        let contract = contract.replace(
+            r#""type":"struct my_struct",
+                        "components":[{ "name":"x", "type":"u64" }]"#,
+            r#""type":"struct MyStruct",
+                        "components":[{ "name":"y", "type":"u64" }]"#,
+        );
+        let err = Abigen::new("custom", contract)
+            .err()
+            .expect("Should have failed on the second `MyStruct`");
+        assert_eq!(
+            err.to_string(),
+            "Invalid type: the inline ABI: `struct MyStruct` has conflicting definitions"
+        );
+    }
+
+    #[test]
+    fn sway_native_types_are_not_generated() {
+        let contract = r#"
+        [
+            {
+                "type":"function",
+                "inputs":[
+                    {
+                        "name":"recipient",
+                        "type":"enum Identity",
+                        "components":[
+                            {
+                                "name":"Address",
+                                "type":"struct Address",
+                                "components":[{ "name":"value", "type":"b256" }]
+                            },
+                            {
+                                "name":"ContractId",
+                                "type":"struct ContractId",
+                                "components":[{ "name":"value", "type":"b256" }]
+                            }
+                        ]
+                    },
+                    {
+                        "name":"asset",
+                        "type":"struct AssetId",
+                        "components":[{ "name":"value", "type":"b256" }]
+                    },
+                    {
+                        "name":"book",
+                        "type":"struct AddressBook",
+                        "components":[{ "name":"size", "type":"u64" }]
+                    }
+                ],
+                "name":"transfer",
+                "outputs":[]
+            }
+        ]
+        "#;
+
+        let mut source = Vec::new();
+        Abigen::new("custom", contract)
+            .unwrap()
+            .generate()
+            .unwrap()
+            .write(&mut source)
+            .unwrap();
+        let bindings = String::from_utf8(source).unwrap();
+
+        assert!(bindings.contains(
+            "recipient: Identity,\n            asset: AssetId,\n            book: AddressBook,"
+        ));
+        assert!(bindings.contains("pub struct AddressBook {"));
+        for native_type in [
+            "struct Address ",
+            "struct AssetId",
+            "struct ContractId",
+            "enum Identity",
+        ] {
+            assert!(!bindings.contains(&format!("pub {}", native_type)));
+        }
+    }
+
+    #[test]
+    fn types_named_like_native_types_with_another_layout_are_generated() {
+        let contract = r#"
+        [
+            {
+                "type":"function",
+                "inputs":[
+                    {
+                        "name":"address",
+                        "type":"struct Address",
+                        "components":[
+                            { "name":"street", "type":"str[8]" },
+                            { "name":"number", "type":"u64" }
+                        ]
+                    }
+                ],
+                "name":"deliver",
+                "outputs":[]
+            }
+        ]
+        "#;
+
+        let err = Abigen::new("custom", contract)
+            .unwrap()
+            .generate()
+            .expect_err("Should have failed on the name collision");
+        assert!(err.to_string().contains(
+            "type `Address` is generated as `Address`, which collides with the SDK's `Address`"
+        ));
+
+        let renames = HashMap::from([("Address".to_string(), "PostalAddress".to_string())]);
+        let mut source = Vec::new();
+        Abigen::new("custom", contract)
+            .unwrap()
+            .with_renames(renames)
+            .unwrap()
+            .generate()
+            .unwrap()
+            .write(&mut source)
+            .unwrap();
+        let bindings = String::from_utf8(source).unwrap();
+
+        assert!(bindings.contains("pub struct PostalAddress {"));
+        assert!(bindings.contains("address: PostalAddress"));
+    }
 }
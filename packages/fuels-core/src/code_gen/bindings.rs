@@ -1,14 +1,16 @@
 use crate::errors::Error;
-use crate::rustfmt;
+use crate::formatter;
 use proc_macro2::TokenStream;
 use std::{fs::File, io::Write, path::Path};
 
 /// Type-safe contract bindings generated by a `Builder`. This type can be
 /// either written to file or into a token stream for use in a procedural macro.
+#[derive(Debug)]
 pub struct ContractBindings {
     /// The TokenStream representing the contract bindings.
     pub tokens: TokenStream,
-    /// The output options used for serialization.
+    /// Whether to format the code when writing it out. The code is formatted
+    /// in-process, with [`formatter::format`].
     pub rustfmt: bool,
 }
 
@@ -18,17 +20,13 @@ impl ContractBindings {
     where
         W: Write,
     {
-        let source = {
-            let raw = self.tokens.to_string();
-
-            if self.rustfmt {
-                rustfmt::format(&raw).unwrap_or(raw)
-            } else {
-                raw
-            }
+        let source = if self.rustfmt {
+            formatter::format(&self.tokens)?
+        } else {
+            self.tokens.to_string()
         };
 
-        w.write_all(source.as_bytes()).unwrap();
+        w.write_all(source.as_bytes())?;
         Ok(())
     }
 
@@ -37,7 +35,7 @@ impl ContractBindings {
     where
         P: AsRef<Path>,
     {
-        let file = File::create(path).unwrap();
+        let file = File::create(path)?;
         self.write(file)
     }
 
@@ -0,0 +1,282 @@
+use crate::abi_encoder::ABIEncoder;
+use crate::code_gen::functions_gen::{
+    expand_function_parameters, expand_param_type, expand_selector,
+};
+use crate::errors::Error;
+use crate::json_abi::ABIParser;
+use crate::utils::{ident, safe_ident};
+use fuels_types::{Function, Property};
+use inflector::Inflector;
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::quote;
+use std::collections::HashMap;
+
+// Functions used by the Abigen to expand the metadata of the contract's
+// functions, and the decoding of the calls made to them.
+
+/// Expands:
+/// - `<Name>Function`, a fieldless enum of the contract's functions, giving
+///   each one's name, Sway signature, selector and inputs;
+/// - `<Name>Call`, an enum holding a call to one of the functions along with
+///   its typed arguments;
+/// - `<Name>::decode_call_data`, which turns call data back into a
+///   `<Name>Call`.
+///
+/// `functions` are the contract's functions, along with the names of the
+/// methods generated for them.
+pub(crate) fn expand_calls(
+    contract_name: &Ident,
+    functions: &[(Ident, &Function)],
+    abi_parser: &ABIParser,
+    custom_enums: &HashMap<String, Property>,
+    custom_structs: &HashMap<String, Property>,
+) -> Result<TokenStream, Error> {
+    let function_name = ident(&format!("{}Function", contract_name));
+    let call_name = ident(&format!("{}Call", contract_name));
+
+    let mut variants = Vec::with_capacity(functions.len());
+    let mut names = Vec::with_capacity(functions.len());
+    let mut signatures = Vec::with_capacity(functions.len());
+    let mut selectors = Vec::with_capacity(functions.len());
+    let mut input_names = Vec::with_capacity(functions.len());
+    let mut input_types = Vec::with_capacity(functions.len());
+    let mut call_variants = Vec::with_capacity(functions.len());
+    let mut call_functions = Vec::with_capacity(functions.len());
+    let mut decoders = Vec::with_capacity(functions.len());
+
+    for (name, function) in functions {
+        let (arg_names, arg_types): (Vec<_>, Vec<_>) =
+            expand_function_parameters(function, custom_enums, custom_structs)?
+                .into_iter()
+                .unzip();
+
+        let method = name.to_string();
+        let variant = safe_ident(&method.trim_start_matches("r#").to_class_case());
+
+        let signature = abi_parser.build_fn_selector(&function.name, &function.inputs)?;
+        let selector = expand_selector(ABIEncoder::encode_function_selector(signature.as_bytes()));
+        let abi_names = function.inputs.iter().map(|input| &input.name);
+        let param_types = function
+            .inputs
+            .iter()
+            .map(expand_param_type)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (call, call_pattern) = if arg_names.is_empty() {
+            (
+                quote! { #call_name::#variant },
+                quote! { #call_name::#variant },
+            )
+        } else {
+            (
+                quote! { #call_name::#variant { #( #arg_names ),* } },
+                quote! { #call_name::#variant { .. } },
+            )
+        };
+        let invalid_arguments = arg_names
+            .iter()
+            .map(|arg| format!("invalid argument `{}` of `{}`: {{}}", arg, function.name));
+        let missing_arguments = arg_names
+            .iter()
+            .map(|arg| format!("missing argument `{}` of `{}`", arg, function.name));
+
+        let abi_name = &function.name;
+        variants.push(variant.clone());
+        names.push(quote! { #function_name::#variant => #abi_name });
+        signatures.push(quote! { #function_name::#variant => #signature });
+        selectors.push(quote! { #function_name::#variant => #selector });
+        input_names.push(quote! { #function_name::#variant => &[#( #abi_names ),*] });
+        input_types.push(quote! { #function_name::#variant => vec![#( #param_types ),*] });
+        call_functions.push(quote! { #call_pattern => #function_name::#variant });
+        decoders.push(quote! {
+            #function_name::#variant => {
+                #(
+                    let #arg_names = <#arg_types>::from_token(
+                        tokens.next().ok_or_else(|| fuels::core::errors::Error::InvalidData(#missing_arguments.to_string()))?,
+                    )
+                    .map_err(|e| fuels::core::errors::Error::InvalidData(format!(#invalid_arguments, e.0)))?;
+                )*
+                Ok(#call)
+            }
+        });
+        call_variants.push(if arg_names.is_empty() {
+            quote! { #variant }
+        } else {
+            quote! { #variant { #( #arg_names: #arg_types ),* } }
+        });
+    }
+
+    let function_doc = format!(
+        "The functions of the `{}` contract, along with their Sway signature, selector and inputs.",
+        contract_name
+    );
+    let call_doc = format!(
+        "A call to one of the functions of the `{}` contract, along with its arguments.",
+        contract_name
+    );
+    let len = Literal::usize_unsuffixed(variants.len());
+
+    Ok(quote! {
+        #[doc = #function_doc]
+        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+        pub enum #function_name {
+            #( #variants ),*
+        }
+
+        impl #function_name {
+            /// All the functions of the contract, in the order of the ABI.
+            pub const ALL: [Self; #len] = [#( Self::#variants ),*];
+
+            /// The name of the function in the ABI.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    #( #names, )*
+                }
+            }
+
+            /// The Sway signature of the function, e.g. `increment(u64)`,
+            /// from which its selector is computed.
+            pub fn signature(&self) -> &'static str {
+                match self {
+                    #( #signatures, )*
+                }
+            }
+
+            /// The selector identifying the function in call data.
+            pub fn selector(&self) -> [u8; 8] {
+                match self {
+                    #( #selectors, )*
+                }
+            }
+
+            /// The names of the function's inputs in the ABI.
+            pub fn input_names(&self) -> &'static [&'static str] {
+                match self {
+                    #( #input_names, )*
+                }
+            }
+
+            /// The types of the function's inputs, as used to encode and decode
+            /// its arguments.
+            pub fn input_types(&self) -> Vec<ParamType> {
+                match self {
+                    #( #input_types, )*
+                }
+            }
+
+            /// The function identified by `selector`, if the contract has one.
+            pub fn from_selector(selector: &[u8]) -> Option<Self> {
+                Self::ALL
+                    .iter()
+                    .copied()
+                    .find(|function| function.selector().as_slice() == selector)
+            }
+        }
+
+        #[doc = #call_doc]
+        #[derive(Clone, Debug, Eq, PartialEq)]
+        pub enum #call_name {
+            #( #call_variants ),*
+        }
+
+        impl #call_name {
+            /// The function called.
+            pub fn function(&self) -> #function_name {
+                match self {
+                    #( #call_functions, )*
+                }
+            }
+        }
+
+        impl<S: Signer + Clone> #contract_name<S> {
+            /// Decodes the call data of a call to one of the contract's functions,
+            /// i.e. the function's selector followed by its encoded arguments.
+            #[allow(unused_mut)]
+            pub fn decode_call_data(call_data: &[u8]) -> Result<#call_name, fuels::core::errors::Error> {
+                if call_data.len() < 8 {
+                    return Err(fuels::core::errors::Error::InvalidData(format!(
+                        "call data of {} bytes is too short to hold a function selector",
+                        call_data.len()
+                    )));
+                }
+                let (selector, data) = call_data.split_at(8);
+
+                let function = #function_name::from_selector(selector).ok_or_else(|| {
+                    fuels::core::errors::Error::InvalidData(format!(
+                        "unknown function selector 0x{}",
+                        selector.iter().map(|byte| format!("{:02x}", byte)).collect::<String>()
+                    ))
+                })?;
+                let mut tokens = fuels::core::abi_decoder::ABIDecoder::new()
+                    .decode(&function.input_types(), data)?
+                    .into_iter();
+
+                match function {
+                    #( #decoders )*
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_functions_calls_and_decoding() {
+        let abi = [
+            Function {
+                type_field: "function".to_string(),
+                inputs: vec![Property {
+                    name: "amount".to_string(),
+                    type_field: "u64".to_string(),
+                    components: None,
+                }],
+                name: "increment".to_string(),
+                outputs: vec![],
+            },
+            Function {
+                type_field: "function".to_string(),
+                inputs: vec![],
+                name: "reset".to_string(),
+                outputs: vec![],
+            },
+        ];
+
+        let functions: Vec<_> = abi
+            .iter()
+            .map(|function| (safe_ident(&function.name), function))
+            .collect();
+        let tokens = expand_calls(
+            &ident("Counter"),
+            &functions,
+            &ABIParser::new(),
+            &Default::default(),
+            &Default::default(),
+        )
+        .unwrap();
+        let code = crate::formatter::format(&tokens).unwrap();
+
+        for expected in [
+            "pub enum CounterFunction {\n    Increment,\n    Reset,\n}",
+            "pub const ALL: [Self; 2] = [Self::Increment, Self::Reset];",
+            "CounterFunction::Increment => \"increment(u64)\",",
+            "CounterFunction::Increment => [0, 0, 0, 0, 229, 67, 198, 102],",
+            "CounterFunction::Increment => &[\"amount\"],",
+            "CounterFunction::Increment => vec![ParamType::U64],",
+            "pub enum CounterCall {\n    Increment { amount: u64 },\n    Reset,\n}",
+            "CounterCall::Increment { .. } => CounterFunction::Increment,",
+            "Ok(CounterCall::Increment { amount })",
+            "missing argument `amount` of `increment`",
+            "Ok(CounterCall::Reset)",
+        ] {
+            assert!(
+                code.contains(expected),
+                "`{}` not found in:\n{}",
+                expected,
+                code
+            );
+        }
+    }
+}
@@ -1,25 +1,114 @@
 use crate::errors::Error;
 use crate::json_abi::parse_param;
 use crate::types::expand_type;
-use crate::utils::ident;
+use crate::utils::{ident, parse_tokens, safe_ident};
 use crate::ParamType;
 use fuels_types::{CustomType, Property};
 use inflector::Inflector;
 use proc_macro2::TokenStream;
 use quote::quote;
+use syn::parse::Parser;
+use syn::{Attribute, Path};
 
 /// Functions used by the Abigen to expand custom types defined in an ABI spec.
 
+/// Derives and attributes added to every generated struct and enum, on top
+/// of the `Clone, Debug, Eq, PartialEq` derives they always have, e.g.
+/// `serde::Serialize` or `#[serde(rename_all = "camelCase")]`.
+///
+/// Paths are resolved from within the generated module, so crates should be
+/// named in full, i.e. `serde::Serialize` rather than an imported
+/// `Serialize`. Enums can't derive `Default`, so when it is asked for, they
+/// implement it by defaulting to their first variant instead.
+#[derive(Debug, Clone, Default)]
+pub struct TypeAttributes {
+    derives: Vec<String>,
+    attributes: Vec<String>,
+}
+
+impl TypeAttributes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a derive, given as the path of the derive macro, e.g. `Hash` or
+    /// `serde::Serialize`.
+    pub fn add_derive(&mut self, derive: &str) -> Result<(), Error> {
+        let path: Path = syn::parse_str(derive).map_err(|e| {
+            Error::InvalidData(format!("`{}` is not a valid derive: {}", derive, e))
+        })?;
+        self.derives.push(quote!(#path).to_string());
+        Ok(())
+    }
+
+    /// Adds one or more outer attributes, e.g. `#[serde(rename_all = "camelCase")]`.
+    pub fn add_attribute(&mut self, attribute: &str) -> Result<(), Error> {
+        let attributes = Attribute::parse_outer
+            .parse_str(attribute)
+            .ok()
+            .filter(|attributes| !attributes.is_empty())
+            .ok_or_else(|| {
+                Error::InvalidData(format!(
+                    "`{}` is not a valid attribute, expected e.g. `#[serde(rename_all = \"camelCase\")]`",
+                    attribute
+                ))
+            })?;
+        self.attributes.extend(
+            attributes
+                .iter()
+                .map(|attribute| quote!(#attribute).to_string()),
+        );
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.derives.is_empty() && self.attributes.is_empty()
+    }
+
+    // The derives and attributes, as put on a struct, or on an enum when
+    // `is_enum`. `Default` is left out of the derives of enums.
+    fn expand(&self, is_enum: bool) -> Result<TokenStream, Error> {
+        let derives = self
+            .derives
+            .iter()
+            .filter(|derive| !(is_enum && is_default(derive)))
+            .map(|derive| parse_tokens(derive))
+            .collect::<Result<Vec<_>, _>>()?;
+        let attributes = self
+            .attributes
+            .iter()
+            .map(|attribute| parse_tokens(attribute))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let derives = (!derives.is_empty()).then(|| quote! { #[derive(#( #derives ),*)] });
+        Ok(quote! {
+            #derives
+            #( #attributes )*
+        })
+    }
+
+    fn derives_default(&self) -> bool {
+        self.derives.iter().any(|derive| is_default(derive))
+    }
+}
+
+fn is_default(derive: &str) -> bool {
+    derive.rsplit("::").next().map(str::trim) == Some("Default")
+}
+
 /// Transforms a custom type defined in [`Property`] into a [`TokenStream`]
 /// that represents that same type as a Rust-native struct.
-pub fn expand_custom_struct(prop: &Property) -> Result<TokenStream, Error> {
+pub fn expand_custom_struct(
+    prop: &Property,
+    type_attributes: &TypeAttributes,
+) -> Result<TokenStream, Error> {
     let struct_name = &extract_custom_type_name_from_abi_property(prop, Some(CustomType::Struct))?
         .to_class_case();
-    let struct_ident = ident(struct_name);
+    let struct_ident = safe_ident(struct_name);
     let components = prop
         .components
         .as_ref()
-        .expect("Fail to extract components from custom type");
+        .ok_or_else(|| Error::MissingData(format!("`{}` has no `components`", prop.type_field)))?;
     let mut fields = Vec::with_capacity(components.len());
 
     // Holds a TokenStream representing the process of
@@ -37,13 +126,13 @@ pub fn expand_custom_struct(prop: &Property) -> Result<TokenStream, Error> {
     // 1. A struct field declaration like `pub #field_name: #component_name`
     // 2. The creation of a token and its insertion into a vector of Tokens.
     for (idx, component) in components.iter().enumerate() {
-        let field_name = ident(&component.name.to_snake_case());
+        let field_name = safe_ident(&component.name.to_snake_case());
         let param_type = parse_param(component)?;
 
         match param_type {
             // Case where a struct takes another struct
             ParamType::Struct(_params) => {
-                let inner_struct_ident = ident(
+                let inner_struct_ident = safe_ident(
                     &extract_custom_type_name_from_abi_property(
                         component,
                         Some(CustomType::Struct),
@@ -52,9 +141,9 @@ pub fn expand_custom_struct(prop: &Property) -> Result<TokenStream, Error> {
                 );
 
                 fields.push(quote! {pub #field_name: #inner_struct_ident});
-                args.push(
-                    quote! {#field_name: #inner_struct_ident::new_from_tokens(&tokens[#idx..])},
-                );
+                args.push(quote! {
+                    #field_name: #inner_struct_ident::new_from_tokens(tokens.get(#idx..).unwrap_or_default())?
+                });
                 struct_fields_tokens.push(quote! { tokens.push(self.#field_name.into_token()) });
                 param_types.push(
                     quote! { types.push(ParamType::Struct(#inner_struct_ident::param_types())) },
@@ -62,57 +151,55 @@ pub fn expand_custom_struct(prop: &Property) -> Result<TokenStream, Error> {
             }
             // The struct contains a nested enum
             ParamType::Enum(_params) => {
-                let enum_name = ident(
+                let enum_name = safe_ident(
                     &extract_custom_type_name_from_abi_property(component, Some(CustomType::Enum))?
                         .to_class_case(),
                 );
                 fields.push(quote! {pub #field_name: #enum_name});
-                args.push(quote! {#field_name: #enum_name::new_from_tokens(&tokens[#idx..])});
+                args.push(quote! {
+                    #field_name: #enum_name::new_from_tokens(tokens.get(#idx..).unwrap_or_default())?
+                });
                 struct_fields_tokens.push(quote! { tokens.push(self.#field_name.into_token()) });
                 param_types.push(quote! { types.push(ParamType::Enum(#enum_name::param_types())) });
             }
             _ => {
                 let ty = expand_type(&param_type)?;
 
-                let mut param_type_string = param_type.to_string();
+                let param_type_string = param_type.to_string();
 
-                let param_type_string_ident_tok: proc_macro2::TokenStream =
-                    param_type_string.parse().unwrap();
+                let param_type_string_ident_tok = parse_tokens(&param_type_string)?;
 
                 param_types.push(quote! { types.push(ParamType::#param_type_string_ident_tok) });
 
-                if let ParamType::Array(..) = param_type {
-                    param_type_string = "Array".to_string();
-                }
-                if let ParamType::String(..) = param_type {
-                    param_type_string = "String".to_string();
-                }
-
-                let param_type_string_ident = ident(&param_type_string);
-
                 // Field declaration
                 fields.push(quote! { pub #field_name: #ty});
 
                 // `new_from_token()` instantiations
-                let expected_str = format!(
-                    "Failed to run `new_from_tokens()` for custom {} struct \
-                (tokens have wrong order and/or wrong types)",
-                    struct_name
+                let missing_str = format!(
+                    "Failed to run `new_from_tokens()` for custom {} struct: no token for field `{}`",
+                    struct_name, component.name
+                );
+                let invalid_str = format!(
+                    "Failed to run `new_from_tokens()` for custom {} struct: invalid token for field `{}`: {{}}",
+                    struct_name, component.name
                 );
                 args.push(quote! {
-                    #field_name: <#ty>::from_token(tokens[#idx].clone()).expect(#expected_str)
+                    #field_name: <#ty>::from_token(
+                        tokens.get(#idx).cloned().ok_or_else(|| InvalidOutputType(#missing_str.to_string()))?
+                    ).map_err(|e| InvalidOutputType(format!(#invalid_str, e.0)))?
                 });
 
                 // Token creation and insertion
                 match param_type {
-                    ParamType::Array(_t, _s) => {
-                        struct_fields_tokens.push(
-                            quote! {tokens.push(Token::#param_type_string_ident(vec![self.#field_name.into_token()]))},
-                        );
+                    // Arrays, `Bits256` and `SizedAsciiString` know how to
+                    // turn themselves into the right `Token`
+                    ParamType::Array(..) | ParamType::B256 | ParamType::String(_) => {
+                        struct_fields_tokens
+                            .push(quote! {tokens.push(self.#field_name.into_token())});
                     }
                     // Primitive type
                     _ => {
-                        // Token creation and insertion
+                        let param_type_string_ident = ident(&param_type_string);
                         struct_fields_tokens.push(
                             quote! {tokens.push(Token::#param_type_string_ident(self.#field_name))},
                         );
@@ -124,8 +211,11 @@ pub fn expand_custom_struct(prop: &Property) -> Result<TokenStream, Error> {
 
     // Actual creation of the struct, using the inner TokenStreams from above to produce the
     // TokenStream that represents the whole struct + methods declaration.
+    let type_attributes = type_attributes.expand(false)?;
+
     Ok(quote! {
         #[derive(Clone, Debug, Eq, PartialEq)]
+        #type_attributes
         pub struct #struct_ident {
             #( #fields ),*
         }
@@ -137,10 +227,10 @@ pub fn expand_custom_struct(prop: &Property) -> Result<TokenStream, Error> {
                 types
             }
 
-            fn new_from_tokens(tokens: &[Token]) -> Self {
-                Self {
+            fn new_from_tokens(tokens: &[Token]) -> Result<Self, InvalidOutputType> {
+                Ok(Self {
                     #( #args ),*
-                }
+                })
             }
         }
 
@@ -154,9 +244,7 @@ pub fn expand_custom_struct(prop: &Property) -> Result<TokenStream, Error> {
 
             fn from_token(token: Token)  -> Result<Self, InvalidOutputType> {
                 match token {
-                    Token::Struct(data) => {
-                        Ok(#struct_ident::new_from_tokens(&data))
-                    }
+                    Token::Struct(data) => #struct_ident::new_from_tokens(&data),
                     other => Err(InvalidOutputType(format!("Expected `T`, got {:?}", other))),
                 }
             }
@@ -166,11 +254,15 @@ pub fn expand_custom_struct(prop: &Property) -> Result<TokenStream, Error> {
 
 /// Transforms a custom enum defined in [`Property`] into a [`TokenStream`]
 /// that represents that same type as a Rust-native enum.
-pub fn expand_custom_enum(name: &str, prop: &Property) -> Result<TokenStream, Error> {
+pub fn expand_custom_enum(
+    name: &str,
+    prop: &Property,
+    type_attributes: &TypeAttributes,
+) -> Result<TokenStream, Error> {
     let components = prop
         .components
         .as_ref()
-        .expect("Fail to extract components from custom type");
+        .ok_or_else(|| Error::MissingData(format!("`{}` has no `components`", prop.type_field)))?;
     let mut enum_variants = Vec::with_capacity(components.len());
 
     // Holds a TokenStream representing the process of creating an enum [`Token`].
@@ -181,11 +273,32 @@ pub fn expand_custom_enum(name: &str, prop: &Property) -> Result<TokenStream, Er
     let mut args = Vec::new();
 
     let enum_name = name.to_class_case();
-    let enum_ident = ident(&enum_name);
+    let enum_ident = safe_ident(&enum_name);
     let mut param_types = Vec::new();
 
+    // `Default` can't be derived for enums, it's implemented by defaulting to
+    // the first variant instead.
+    let default_impl = match components.first() {
+        Some(first) if type_attributes.derives_default() => {
+            let variant_name = safe_ident(&first.name.to_class_case());
+            let value = if parse_param(first)? == ParamType::Unit {
+                quote! { #enum_ident::#variant_name() }
+            } else {
+                quote! { #enum_ident::#variant_name(Default::default()) }
+            };
+            quote! {
+                impl Default for #enum_ident {
+                    fn default() -> Self {
+                        #value
+                    }
+                }
+            }
+        }
+        _ => TokenStream::new(),
+    };
+
     for (discriminant, component) in components.iter().enumerate() {
-        let variant_name = ident(&component.name.to_class_case());
+        let variant_name = safe_ident(&component.name.to_class_case());
         let dis = discriminant as u8;
 
         let param_type = parse_param(component)?;
@@ -193,7 +306,11 @@ pub fn expand_custom_enum(name: &str, prop: &Property) -> Result<TokenStream, Er
             // Case where an enum takes another enum
             ParamType::Enum(_params) => {
                 // TODO: Support nested enums
-                unimplemented!()
+                return Err(Error::InvalidType(format!(
+                    "variant `{}` of enum `{}` holds an enum, which isn't supported yet; \
+                    wrap it in a struct instead",
+                    component.name, name
+                )));
             }
             ParamType::Struct(_params) => {
                 let inner_struct_name = &extract_custom_type_name_from_abi_property(
@@ -201,7 +318,7 @@ pub fn expand_custom_enum(name: &str, prop: &Property) -> Result<TokenStream, Er
                     Some(CustomType::Struct),
                 )?
                 .to_class_case();
-                let inner_struct_ident = ident(inner_struct_name);
+                let inner_struct_ident = safe_ident(inner_struct_name);
                 // Enum variant declaration
                 enum_variants.push(quote! { #variant_name(#inner_struct_ident)});
 
@@ -211,17 +328,10 @@ pub fn expand_custom_enum(name: &str, prop: &Property) -> Result<TokenStream, Er
                     (#dis, inner_struct.into_token())
                 });
 
-                // This is used for creating a new instance with `inner_struct::new_from_tokens()`
-                // based on tokens received
-                let expected_str = format!(
-                    "Failed to run `new_from_tokens` for custom {} enum type",
-                    enum_name
-                );
+                // This is used for creating a new instance with `inner_struct::from_token()`
+                // based on the token received
                 args.push(quote! {
-                    (#dis, token) => {
-                        let variant_content = <#inner_struct_ident>::from_tokens(vec![token]).expect(#expected_str);
-                    #enum_ident::#variant_name(variant_content)
-                        }
+                    (#dis, token) => Ok(#enum_ident::#variant_name(<#inner_struct_ident>::from_token(token)?)),
                 });
 
                 // This is used to get the correct nested types of the enum
@@ -239,25 +349,36 @@ pub fn expand_custom_enum(name: &str, prop: &Property) -> Result<TokenStream, Er
                     #enum_ident::#variant_name() => (#dis, Token::Unit)
                 });
                 param_types.push(quote! { types.push(ParamType::Unit) });
-                args.push(quote! {(#dis, token) => #enum_ident::#variant_name(),});
+                args.push(quote! {(#dis, _) => Ok(#enum_ident::#variant_name()),});
             }
             // Elementary type
             _ => {
                 let ty = expand_type(&param_type)?;
-                let param_type_string = ident(&param_type.to_string());
 
                 // Enum variant declaration
                 enum_variants.push(quote! { #variant_name(#ty)});
 
                 // Token creation
-                enum_selector_builder.push(quote! {
-                    #enum_ident::#variant_name(value) => (#dis, Token::#param_type_string(value))
-                });
-                param_types.push(quote! { types.push(ParamType::#param_type_string) });
+                match param_type {
+                    // Arrays, `Bits256` and `SizedAsciiString` know how to
+                    // turn themselves into the right `Token`
+                    ParamType::Array(..) | ParamType::B256 | ParamType::String(_) => {
+                        enum_selector_builder.push(quote! {
+                            #enum_ident::#variant_name(value) => (#dis, value.into_token())
+                        })
+                    }
+                    _ => {
+                        let param_type_string = ident(&param_type.to_string());
+                        enum_selector_builder.push(quote! {
+                            #enum_ident::#variant_name(value) => (#dis, Token::#param_type_string(value))
+                        })
+                    }
+                }
+
+                let param_type_string_tok = parse_tokens(&param_type.to_string())?;
+                param_types.push(quote! { types.push(ParamType::#param_type_string_tok) });
                 args.push(
-                    quote! {(#dis, token) => #enum_ident::#variant_name(<#ty>::from_tokens(vec![token])
-                    .expect(&format!("Failed to run `new_from_tokens` for custom {} enum type",
-                            #enum_name))),},
+                    quote! {(#dis, token) => Ok(#enum_ident::#variant_name(<#ty>::from_token(token)?)),},
                 );
             }
         }
@@ -266,12 +387,17 @@ pub fn expand_custom_enum(name: &str, prop: &Property) -> Result<TokenStream, Er
     // Actual creation of the enum, using the inner TokenStreams from above
     // to produce the TokenStream that represents the whole enum + methods
     // declaration.
+    let type_attributes = type_attributes.expand(true)?;
+
     Ok(quote! {
         #[derive(Clone, Debug, Eq, PartialEq)]
+        #type_attributes
         pub enum #enum_ident {
             #( #enum_variants ),*
         }
 
+        #default_impl
+
         impl Parameterize for #enum_ident {
             fn param_types() -> Vec<ParamType> {
                 let mut types = Vec::new();
@@ -279,27 +405,23 @@ pub fn expand_custom_enum(name: &str, prop: &Property) -> Result<TokenStream, Er
                 types
             }
 
-            fn new_from_tokens(tokens: &[Token]) -> Self {
-                if tokens.is_empty() {
-                    panic!("Empty tokens array received in `{}::new_from_tokens`",
-                        #enum_name);
-                }
+            fn new_from_tokens(tokens: &[Token]) -> Result<Self, InvalidOutputType> {
                 // For some reason sometimes we receive arrays that have multiple elements, with the
                 // first token being a `Token::Enum`. We only consider that `Enum` token in that
                 // case
                 // TODO: figure out what is actually happening and if this is normal
-                match tokens[0].clone() {
-                    Token::Enum(content) => {
-                        if let enum_selector = *content {
-                            return match enum_selector {
-                                #( #args )*
-                                (_, _) => panic!("Failed to match with discriminant selector {:?}", enum_selector)
-                            };
-                        } else {
-                            panic!("The EnumSelector `{:?}` didn't have a match", content);
-                        }
-                     },
-                    _ => panic!("This should contain an `Enum` token, found `{:?}`", tokens),
+                match tokens.first() {
+                    Some(Token::Enum(content)) => match (**content).clone() {
+                        #( #args )*
+                        (discriminant, _) => Err(InvalidOutputType(format!(
+                            "Discriminant {} doesn't match any variant of `{}`",
+                            discriminant, #enum_name
+                        ))),
+                    },
+                    _ => Err(InvalidOutputType(format!(
+                        "Expected an `Enum` token in `{}::new_from_tokens`, got {:?}",
+                        #enum_name, tokens
+                    ))),
                 }
             }
 
@@ -317,7 +439,7 @@ pub fn expand_custom_enum(name: &str, prop: &Property) -> Result<TokenStream, Er
 
             fn from_token(token: Token)  -> Result<Self, InvalidOutputType> {
                 if let Token::Enum(_) = token {
-                    Ok(#enum_ident::new_from_tokens(&[token]))
+                    #enum_ident::new_from_tokens(&[token])
                 } else {
                     Err(InvalidOutputType("Enum token doesn't contain inner tokens."
                         .to_string()))
@@ -353,11 +475,10 @@ pub fn extract_custom_type_name_from_abi_property(
     let type_field: Vec<&str> = type_field.split_whitespace().collect();
 
     if type_field.len() != 2 {
-        return Err(Error::MissingData(
-            r#"The declared type was not in the format `{enum,struct} name`"#
-                .parse()
-                .unwrap(),
-        ));
+        return Err(Error::MissingData(format!(
+            "The declared type `{}` was not in the format `{{enum,struct}} name`",
+            prop.type_field
+        )));
     };
 
     if let Some(expected_type) = expected {
@@ -454,10 +575,10 @@ mod tests {
                 },
             ]),
         };
-        let result = expand_custom_enum("matcha_tea", &p);
+        let result = expand_custom_enum("matcha_tea", &p, &TypeAttributes::new());
         let expected = TokenStream::from_str(
             r#"
-            # [derive (Clone , Debug , Eq , PartialEq)] pub enum MatchaTea { LongIsland (u64) , MoscowMule (bool) } impl Parameterize for MatchaTea { fn param_types () -> Vec < ParamType > { let mut types = Vec :: new () ; types . push (ParamType :: U64) ; types . push (ParamType :: Bool) ; types } fn new_from_tokens (tokens : & [Token]) -> Self { if tokens . is_empty () { panic ! ("Empty tokens array received in `{}::new_from_tokens`" , "MatchaTea") ; } match tokens [0] . clone () { Token :: Enum (content) => { if let enum_selector = * content { return match enum_selector { (0u8 , token) => MatchaTea :: LongIsland (< u64 > :: from_tokens (vec ! [token]) . expect (& format ! ("Failed to run `new_from_tokens` for custom {} enum type" , "MatchaTea"))) , (1u8 , token) => MatchaTea :: MoscowMule (< bool > :: from_tokens (vec ! [token]) . expect (& format ! ("Failed to run `new_from_tokens` for custom {} enum type" , "MatchaTea"))) , (_ , _) => panic ! ("Failed to match with discriminant selector {:?}" , enum_selector) } ; } else { panic ! ("The EnumSelector `{:?}` didn't have a match" , content) ; } } , _ => panic ! ("This should contain an `Enum` token, found `{:?}`" , tokens) , } } } impl Tokenizable for MatchaTea { fn into_token (self) -> Token { let (dis , tok) = match self { MatchaTea :: LongIsland (value) => (0u8 , Token :: U64 (value)) , MatchaTea :: MoscowMule (value) => (1u8 , Token :: Bool (value)) , } ; let selector = (dis , tok) ; Token :: Enum (Box :: new (selector)) } fn from_token (token : Token) -> Result < Self , InvalidOutputType > { if let Token :: Enum (_) = token { Ok (MatchaTea :: new_from_tokens (& [token])) } else { Err (InvalidOutputType ("Enum token doesn't contain inner tokens." . to_string ())) } } }
+            # [derive (Clone , Debug , Eq , PartialEq)] pub enum MatchaTea { LongIsland (u64) , MoscowMule (bool) } impl Parameterize for MatchaTea { fn param_types () -> Vec < ParamType > { let mut types = Vec :: new () ; types . push (ParamType :: U64) ; types . push (ParamType :: Bool) ; types } fn new_from_tokens (tokens : & [Token]) -> Result < Self , InvalidOutputType > { match tokens . first () { Some (Token :: Enum (content)) => match (* * content) . clone () { (0u8 , token) => Ok (MatchaTea :: LongIsland (< u64 > :: from_token (token) ?)) , (1u8 , token) => Ok (MatchaTea :: MoscowMule (< bool > :: from_token (token) ?)) , (discriminant , _) => Err (InvalidOutputType (format ! ("Discriminant {} doesn't match any variant of `{}`" , discriminant , "MatchaTea"))) , } , _ => Err (InvalidOutputType (format ! ("Expected an `Enum` token in `{}::new_from_tokens`, got {:?}" , "MatchaTea" , tokens))) , } } } impl Tokenizable for MatchaTea { fn into_token (self) -> Token { let (dis , tok) = match self { MatchaTea :: LongIsland (value) => (0u8 , Token :: U64 (value)) , MatchaTea :: MoscowMule (value) => (1u8 , Token :: Bool (value)) , } ; let selector = (dis , tok) ; Token :: Enum (Box :: new (selector)) } fn from_token (token : Token) -> Result < Self , InvalidOutputType > { if let Token :: Enum (_) = token { MatchaTea :: new_from_tokens (& [token]) } else { Err (InvalidOutputType ("Enum token doesn't contain inner tokens." . to_string ())) } } }
             "#,
         );
         let expected = expected.unwrap().to_string();
@@ -495,11 +616,11 @@ mod tests {
             type_field: String::from("enum CityComponent"),
             components: Some(enum_components),
         };
-        let result = expand_custom_enum("Amsterdam", &p).unwrap();
+        let result = expand_custom_enum("Amsterdam", &p, &TypeAttributes::new()).unwrap();
 
         let expected = TokenStream::from_str(
             r#"
-            # [derive (Clone , Debug , Eq , PartialEq)] pub enum Amsterdam { Infrastructure (Building) , Service (u32) } impl Parameterize for Amsterdam { fn param_types () -> Vec < ParamType > { let mut types = Vec :: new () ; types . push (ParamType :: Struct (Building :: param_types ())) ; types . push (ParamType :: U32) ; types } fn new_from_tokens (tokens : & [Token]) -> Self { if tokens . is_empty () { panic ! ("Empty tokens array received in `{}::new_from_tokens`" , "Amsterdam") ; } match tokens [0] . clone () { Token :: Enum (content) => { if let enum_selector = * content { return match enum_selector { (0u8 , token) => { let variant_content = < Building > :: from_tokens (vec ! [token]) . expect ("Failed to run `new_from_tokens` for custom Amsterdam enum type") ; Amsterdam :: Infrastructure (variant_content) } (1u8 , token) => Amsterdam :: Service (< u32 > :: from_tokens (vec ! [token]) . expect (& format ! ("Failed to run `new_from_tokens` for custom {} enum type" , "Amsterdam"))) , (_ , _) => panic ! ("Failed to match with discriminant selector {:?}" , enum_selector) } ; } else { panic ! ("The EnumSelector `{:?}` didn't have a match" , content) ; } } , _ => panic ! ("This should contain an `Enum` token, found `{:?}`" , tokens) , } } } impl Tokenizable for Amsterdam { fn into_token (self) -> Token { let (dis , tok) = match self { Amsterdam :: Infrastructure (inner_struct) => (0u8 , inner_struct . into_token ()) , Amsterdam :: Service (value) => (1u8 , Token :: U32 (value)) , } ; let selector = (dis , tok) ; Token :: Enum (Box :: new (selector)) } fn from_token (token : Token) -> Result < Self , InvalidOutputType > { if let Token :: Enum (_) = token { Ok (Amsterdam :: new_from_tokens (& [token])) } else { Err (InvalidOutputType ("Enum token doesn't contain inner tokens." . to_string ())) } } }
+            # [derive (Clone , Debug , Eq , PartialEq)] pub enum Amsterdam { Infrastructure (Building) , Service (u32) } impl Parameterize for Amsterdam { fn param_types () -> Vec < ParamType > { let mut types = Vec :: new () ; types . push (ParamType :: Struct (Building :: param_types ())) ; types . push (ParamType :: U32) ; types } fn new_from_tokens (tokens : & [Token]) -> Result < Self , InvalidOutputType > { match tokens . first () { Some (Token :: Enum (content)) => match (* * content) . clone () { (0u8 , token) => Ok (Amsterdam :: Infrastructure (< Building > :: from_token (token) ?)) , (1u8 , token) => Ok (Amsterdam :: Service (< u32 > :: from_token (token) ?)) , (discriminant , _) => Err (InvalidOutputType (format ! ("Discriminant {} doesn't match any variant of `{}`" , discriminant , "Amsterdam"))) , } , _ => Err (InvalidOutputType (format ! ("Expected an `Enum` token in `{}::new_from_tokens`, got {:?}" , "Amsterdam" , tokens))) , } } } impl Tokenizable for Amsterdam { fn into_token (self) -> Token { let (dis , tok) = match self { Amsterdam :: Infrastructure (inner_struct) => (0u8 , inner_struct . into_token ()) , Amsterdam :: Service (value) => (1u8 , Token :: U32 (value)) , } ; let selector = (dis , tok) ; Token :: Enum (Box :: new (selector)) } fn from_token (token : Token) -> Result < Self , InvalidOutputType > { if let Token :: Enum (_) = token { Amsterdam :: new_from_tokens (& [token]) } else { Err (InvalidOutputType ("Enum token doesn't contain inner tokens." . to_string ())) } } }
             "#,
         )
         .unwrap();
@@ -507,7 +628,6 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "not implemented")]
     // Enum cannot contain enum at the moment
     fn test_expand_custom_enum_with_enum() {
         let p = Property {
@@ -523,7 +643,12 @@ mod tests {
                 }]),
             }]),
         };
-        let _ = expand_custom_enum("dragon", &p);
+        let err = expand_custom_enum("dragon", &p, &TypeAttributes::new())
+            .expect_err("Should have failed on the nested enum");
+
+        assert!(
+            matches!(err, Error::InvalidType(msg) if msg.starts_with("variant `long_island` of enum `dragon` holds an enum"))
+        );
     }
 
     #[test]
@@ -551,14 +676,87 @@ mod tests {
         };
         let expected = TokenStream::from_str(
             r#"
-            # [derive (Clone , Debug , Eq , PartialEq)] pub struct Cocktail { pub long_island : bool , pub cosmopolitan : u64 , pub mojito : u32 } impl Parameterize for Cocktail { fn param_types () -> Vec < ParamType > { let mut types = Vec :: new () ; types . push (ParamType :: Bool) ; types . push (ParamType :: U64) ; types . push (ParamType :: U32) ; types } fn new_from_tokens (tokens : & [Token]) -> Self { Self { long_island : < bool > :: from_token (tokens [0usize] . clone ()) . expect ("Failed to run `new_from_tokens()` for custom Cocktail struct (tokens have wrong order and/or wrong types)") , cosmopolitan : < u64 > :: from_token (tokens [1usize] . clone ()) . expect ("Failed to run `new_from_tokens()` for custom Cocktail struct (tokens have wrong order and/or wrong types)") , mojito : < u32 > :: from_token (tokens [2usize] . clone ()) . expect ("Failed to run `new_from_tokens()` for custom Cocktail struct (tokens have wrong order and/or wrong types)") } } } impl Tokenizable for Cocktail { fn into_token (self) -> Token { let mut tokens = Vec :: new () ; tokens . push (Token :: Bool (self . long_island)) ; tokens . push (Token :: U64 (self . cosmopolitan)) ; tokens . push (Token :: U32 (self . mojito)) ; Token :: Struct (tokens) } fn from_token (token : Token) -> Result < Self , InvalidOutputType > { match token { Token :: Struct (data) => { Ok (Cocktail :: new_from_tokens (& data)) } other => Err (InvalidOutputType (format ! ("Expected `T`, got {:?}" , other))) , } } }
+            # [derive (Clone , Debug , Eq , PartialEq)] pub struct Cocktail { pub long_island : bool , pub cosmopolitan : u64 , pub mojito : u32 } impl Parameterize for Cocktail { fn param_types () -> Vec < ParamType > { let mut types = Vec :: new () ; types . push (ParamType :: Bool) ; types . push (ParamType :: U64) ; types . push (ParamType :: U32) ; types } fn new_from_tokens (tokens : & [Token]) -> Result < Self , InvalidOutputType > { Ok (Self { long_island : < bool > :: from_token (tokens . get (0usize) . cloned () . ok_or_else (|| InvalidOutputType ("Failed to run `new_from_tokens()` for custom Cocktail struct: no token for field `long_island`" . to_string ())) ?) . map_err (| e | InvalidOutputType (format ! ("Failed to run `new_from_tokens()` for custom Cocktail struct: invalid token for field `long_island`: {}" , e . 0))) ? , cosmopolitan : < u64 > :: from_token (tokens . get (1usize) . cloned () . ok_or_else (|| InvalidOutputType ("Failed to run `new_from_tokens()` for custom Cocktail struct: no token for field `cosmopolitan`" . to_string ())) ?) . map_err (| e | InvalidOutputType (format ! ("Failed to run `new_from_tokens()` for custom Cocktail struct: invalid token for field `cosmopolitan`: {}" , e . 0))) ? , mojito : < u32 > :: from_token (tokens . get (2usize) . cloned () . ok_or_else (|| InvalidOutputType ("Failed to run `new_from_tokens()` for custom Cocktail struct: no token for field `mojito`" . to_string ())) ?) . map_err (| e | InvalidOutputType (format ! ("Failed to run `new_from_tokens()` for custom Cocktail struct: invalid token for field `mojito`: {}" , e . 0))) ? }) } } impl Tokenizable for Cocktail { fn into_token (self) -> Token { let mut tokens = Vec :: new () ; tokens . push (Token :: Bool (self . long_island)) ; tokens . push (Token :: U64 (self . cosmopolitan)) ; tokens . push (Token :: U32 (self . mojito)) ; Token :: Struct (tokens) } fn from_token (token : Token) -> Result < Self , InvalidOutputType > { match token { Token :: Struct (data) => Cocktail :: new_from_tokens (& data) , other => Err (InvalidOutputType (format ! ("Expected `T`, got {:?}" , other))) , } } }
             "#,
         );
         let expected = expected.unwrap().to_string();
-        let result = expand_custom_struct(&p);
+        let result = expand_custom_struct(&p, &TypeAttributes::new());
         assert_eq!(result.unwrap().to_string(), expected);
     }
 
+    #[test]
+    fn test_expand_custom_struct_with_string() {
+        let p = Property {
+            name: String::from("unused"),
+            type_field: String::from("struct cocktail"),
+            components: Some(vec![Property {
+                name: String::from("name"),
+                type_field: String::from("str[4]"),
+                components: None,
+            }]),
+        };
+        let expected = TokenStream::from_str(
+            r#"
+            # [derive (Clone , Debug , Eq , PartialEq)] pub struct Cocktail { pub name : SizedAsciiString < 4 > } impl Parameterize for Cocktail { fn param_types () -> Vec < ParamType > { let mut types = Vec :: new () ; types . push (ParamType :: String (4)) ; types } fn new_from_tokens (tokens : & [Token]) -> Result < Self , InvalidOutputType > { Ok (Self { name : < SizedAsciiString < 4 > > :: from_token (tokens . get (0usize) . cloned () . ok_or_else (|| InvalidOutputType ("Failed to run `new_from_tokens()` for custom Cocktail struct: no token for field `name`" . to_string ())) ?) . map_err (| e | InvalidOutputType (format ! ("Failed to run `new_from_tokens()` for custom Cocktail struct: invalid token for field `name`: {}" , e . 0))) ? }) } } impl Tokenizable for Cocktail { fn into_token (self) -> Token { let mut tokens = Vec :: new () ; tokens . push (self . name . into_token ()) ; Token :: Struct (tokens) } fn from_token (token : Token) -> Result < Self , InvalidOutputType > { match token { Token :: Struct (data) => Cocktail :: new_from_tokens (& data) , other => Err (InvalidOutputType (format ! ("Expected `T`, got {:?}" , other))) , } } }
+            "#,
+        );
+        let expected = expected.unwrap().to_string();
+        let result = expand_custom_struct(&p, &TypeAttributes::new());
+        assert_eq!(result.unwrap().to_string(), expected);
+    }
+
+    #[test]
+    fn test_expand_custom_struct_with_array_and_b256() {
+        let p = Property {
+            name: String::from("unused"),
+            type_field: String::from("struct cocktail"),
+            components: Some(vec![
+                Property {
+                    name: String::from("ingredients"),
+                    type_field: String::from("[u8; 2]"),
+                    components: Some(vec![Property {
+                        name: String::from("__array_element"),
+                        type_field: String::from("u8"),
+                        components: None,
+                    }]),
+                },
+                Property {
+                    name: String::from("recipe_hash"),
+                    type_field: String::from("b256"),
+                    components: None,
+                },
+            ]),
+        };
+        let expected = TokenStream::from_str(
+            r#"
+            # [derive (Clone , Debug , Eq , PartialEq)] pub struct Cocktail { pub ingredients : [u8 ; 2] , pub recipe_hash : Bits256 } impl Parameterize for Cocktail { fn param_types () -> Vec < ParamType > { let mut types = Vec :: new () ; types . push (ParamType :: Array (Box :: new (ParamType :: U8) , 2)) ; types . push (ParamType :: B256) ; types } fn new_from_tokens (tokens : & [Token]) -> Result < Self , InvalidOutputType > { Ok (Self { ingredients : < [u8 ; 2] > :: from_token (tokens . get (0usize) . cloned () . ok_or_else (|| InvalidOutputType ("Failed to run `new_from_tokens()` for custom Cocktail struct: no token for field `ingredients`" . to_string ())) ?) . map_err (| e | InvalidOutputType (format ! ("Failed to run `new_from_tokens()` for custom Cocktail struct: invalid token for field `ingredients`: {}" , e . 0))) ? , recipe_hash : < Bits256 > :: from_token (tokens . get (1usize) . cloned () . ok_or_else (|| InvalidOutputType ("Failed to run `new_from_tokens()` for custom Cocktail struct: no token for field `recipe_hash`" . to_string ())) ?) . map_err (| e | InvalidOutputType (format ! ("Failed to run `new_from_tokens()` for custom Cocktail struct: invalid token for field `recipe_hash`: {}" , e . 0))) ? }) } } impl Tokenizable for Cocktail { fn into_token (self) -> Token { let mut tokens = Vec :: new () ; tokens . push (self . ingredients . into_token ()) ; tokens . push (self . recipe_hash . into_token ()) ; Token :: Struct (tokens) } fn from_token (token : Token) -> Result < Self , InvalidOutputType > { match token { Token :: Struct (data) => Cocktail :: new_from_tokens (& data) , other => Err (InvalidOutputType (format ! ("Expected `T`, got {:?}" , other))) , } } }
+            "#,
+        );
+        let expected = expected.unwrap().to_string();
+        let result = expand_custom_struct(&p, &TypeAttributes::new());
+        assert_eq!(result.unwrap().to_string(), expected);
+    }
+
+    #[test]
+    fn test_expand_custom_enum_with_string() {
+        let p = Property {
+            name: String::from("unused"),
+            type_field: String::from("unused"),
+            components: Some(vec![Property {
+                name: String::from("label"),
+                type_field: String::from("str[3]"),
+                components: None,
+            }]),
+        };
+        let result = expand_custom_enum("tag", &p, &TypeAttributes::new())
+            .unwrap()
+            .to_string();
+
+        assert!(result.contains("Label (SizedAsciiString < 3 >)"));
+        assert!(result.contains("Tag :: Label (value) => (0u8 , value . into_token ())"));
+        assert!(result.contains("types . push (ParamType :: String (3))"));
+    }
+
     #[test]
     fn test_expand_custom_struct_with_struct() {
         let p = Property {
@@ -590,11 +788,80 @@ mod tests {
         };
         let expected = TokenStream::from_str(
             r#"
-            # [derive (Clone , Debug , Eq , PartialEq)] pub struct Cocktail { pub long_island : Shaker , pub mojito : u32 } impl Parameterize for Cocktail { fn param_types () -> Vec < ParamType > { let mut types = Vec :: new () ; types . push (ParamType :: Struct (Shaker :: param_types ())) ; types . push (ParamType :: U32) ; types }  fn new_from_tokens (tokens : & [Token]) -> Self { Self { long_island : Shaker :: new_from_tokens (& tokens [0usize ..]) , mojito : < u32 > :: from_token (tokens [1usize] . clone ()) . expect ("Failed to run `new_from_tokens()` for custom Cocktail struct (tokens have wrong order and/or wrong types)") } } } impl Tokenizable for Cocktail { fn into_token (self) -> Token { let mut tokens = Vec :: new () ; tokens . push (self . long_island . into_token ()) ; tokens . push (Token :: U32 (self . mojito)) ; Token :: Struct (tokens) } fn from_token (token : Token) -> Result < Self , InvalidOutputType > { match token { Token :: Struct (data) => { Ok (Cocktail :: new_from_tokens (& data)) } other => Err (InvalidOutputType (format ! ("Expected `T`, got {:?}" , other))) , } } }
+            # [derive (Clone , Debug , Eq , PartialEq)] pub struct Cocktail { pub long_island : Shaker , pub mojito : u32 } impl Parameterize for Cocktail { fn param_types () -> Vec < ParamType > { let mut types = Vec :: new () ; types . push (ParamType :: Struct (Shaker :: param_types ())) ; types . push (ParamType :: U32) ; types } fn new_from_tokens (tokens : & [Token]) -> Result < Self , InvalidOutputType > { Ok (Self { long_island : Shaker :: new_from_tokens (tokens . get (0usize ..) . unwrap_or_default ()) ? , mojito : < u32 > :: from_token (tokens . get (1usize) . cloned () . ok_or_else (|| InvalidOutputType ("Failed to run `new_from_tokens()` for custom Cocktail struct: no token for field `mojito`" . to_string ())) ?) . map_err (| e | InvalidOutputType (format ! ("Failed to run `new_from_tokens()` for custom Cocktail struct: invalid token for field `mojito`: {}" , e . 0))) ? }) } } impl Tokenizable for Cocktail { fn into_token (self) -> Token { let mut tokens = Vec :: new () ; tokens . push (self . long_island . into_token ()) ; tokens . push (Token :: U32 (self . mojito)) ; Token :: Struct (tokens) } fn from_token (token : Token) -> Result < Self , InvalidOutputType > { match token { Token :: Struct (data) => Cocktail :: new_from_tokens (& data) , other => Err (InvalidOutputType (format ! ("Expected `T`, got {:?}" , other))) , } } }
             "#,
         );
         let expected = expected.unwrap().to_string();
-        let result = expand_custom_struct(&p);
+        let result = expand_custom_struct(&p, &TypeAttributes::new());
         assert_eq!(result.unwrap().to_string(), expected);
     }
+
+    #[test]
+    fn test_expand_custom_types_with_extra_derives_and_attributes() {
+        let mut type_attributes = TypeAttributes::new();
+        type_attributes.add_derive("serde::Serialize").unwrap();
+        type_attributes.add_derive("Default").unwrap();
+        type_attributes
+            .add_attribute(r#"#[serde(rename_all = "camelCase")]"#)
+            .unwrap();
+
+        let p = Property {
+            name: String::from("unused"),
+            type_field: String::from("struct cocktail"),
+            components: Some(vec![Property {
+                name: String::from("recipe_hash"),
+                type_field: String::from("b256"),
+                components: None,
+            }]),
+        };
+        let result = expand_custom_struct(&p, &type_attributes)
+            .unwrap()
+            .to_string();
+        assert!(result.starts_with(
+            r#"# [derive (Clone , Debug , Eq , PartialEq)] # [derive (serde :: Serialize , Default)] # [serde (rename_all = "camelCase")] pub struct Cocktail"#
+        ));
+
+        let p = Property {
+            name: String::from("unused"),
+            type_field: String::from("enum glass"),
+            components: Some(vec![
+                Property {
+                    name: String::from("empty"),
+                    type_field: String::from("()"),
+                    components: None,
+                },
+                Property {
+                    name: String::from("full"),
+                    type_field: String::from("u64"),
+                    components: None,
+                },
+            ]),
+        };
+        let result = expand_custom_enum("glass", &p, &type_attributes)
+            .unwrap()
+            .to_string();
+        assert!(result.starts_with(
+            r#"# [derive (Clone , Debug , Eq , PartialEq)] # [derive (serde :: Serialize)] # [serde (rename_all = "camelCase")] pub enum Glass"#
+        ));
+        assert!(result
+            .contains("impl Default for Glass { fn default () -> Self { Glass :: Empty () } }"));
+    }
+
+    #[test]
+    fn test_invalid_derives_and_attributes_are_rejected() {
+        let mut type_attributes = TypeAttributes::new();
+
+        let err = type_attributes
+            .add_derive("serde::")
+            .expect_err("Should have failed on the incomplete path");
+        assert!(matches!(err, Error::InvalidData(_)));
+
+        let err = type_attributes
+            .add_attribute("serde(rename_all = \"camelCase\")")
+            .expect_err("Should have failed on the missing `#[..]`");
+        assert!(err.to_string().starts_with(
+            "Invalid data: `serde(rename_all = \"camelCase\")` is not a valid attribute"
+        ));
+        assert!(type_attributes.is_empty());
+    }
 }
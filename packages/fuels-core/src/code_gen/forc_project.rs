@@ -0,0 +1,176 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::errors::Error;
+
+/// The build profile a forc project was built with, i.e. which of
+/// `out/debug` and `out/release` its artifacts are read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildProfile {
+    Debug,
+    Release,
+}
+
+impl Default for BuildProfile {
+    fn default() -> Self {
+        BuildProfile::Debug
+    }
+}
+
+impl BuildProfile {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BuildProfile::Debug => "debug",
+            BuildProfile::Release => "release",
+        }
+    }
+}
+
+impl FromStr for BuildProfile {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "debug" => Ok(BuildProfile::Debug),
+            "release" => Ok(BuildProfile::Release),
+            other => Err(Error::InvalidData(format!(
+                "unknown build profile `{}`, expected `debug` or `release`",
+                other
+            ))),
+        }
+    }
+}
+
+/// The artifacts of a built forc project, as found in its
+/// `out/<profile>` directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForcProject {
+    /// The name of the project, taken from its `<name>-abi.json`.
+    pub name: String,
+    pub abi_path: PathBuf,
+    pub bin_path: PathBuf,
+    /// Only contracts declaring storage have a `<name>-storage_slots.json`.
+    pub storage_slots_path: Option<PathBuf>,
+}
+
+impl ForcProject {
+    /// Finds the artifacts of the forc project in `project_dir`, built with
+    /// the given `profile`.
+    pub fn load(project_dir: impl AsRef<Path>, profile: BuildProfile) -> Result<Self, Error> {
+        let project_dir = project_dir.as_ref();
+        let out_dir = project_dir.join("out").join(profile.as_str());
+
+        let entries = fs::read_dir(&out_dir).map_err(|e| {
+            Error::InvalidData(format!(
+                "failed to read the {} build output of the forc project at {} (has it been built?): {}",
+                profile.as_str(),
+                project_dir.display(),
+                e
+            ))
+        })?;
+
+        let names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let file_name = entry.file_name().into_string().ok()?;
+                Some(file_name.strip_suffix("-abi.json")?.to_string())
+            })
+            .collect();
+
+        let name = match names.as_slice() {
+            [name] => name.clone(),
+            [] => {
+                return Err(Error::InvalidData(format!(
+                    "no `*-abi.json` file found in {}",
+                    out_dir.display()
+                )))
+            }
+            _ => {
+                return Err(Error::InvalidData(format!(
+                    "more than one `*-abi.json` file found in {}",
+                    out_dir.display()
+                )))
+            }
+        };
+
+        let bin_path = out_dir.join(format!("{}.bin", name));
+        if !bin_path.is_file() {
+            return Err(Error::InvalidData(format!(
+                "expected the bytecode of `{}` at {}",
+                name,
+                bin_path.display()
+            )));
+        }
+
+        let storage_slots_path = out_dir.join(format!("{}-storage_slots.json", name));
+
+        Ok(Self {
+            abi_path: out_dir.join(format!("{}-abi.json", name)),
+            bin_path,
+            storage_slots_path: storage_slots_path.is_file().then(|| storage_slots_path),
+            name,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn project_dir(files: &[&str]) -> TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for file in files {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn finds_the_artifacts_of_the_selected_profile() {
+        let project = project_dir(&[
+            "out/debug/my_contract-abi.json",
+            "out/debug/my_contract.bin",
+            "out/release/my_contract-abi.json",
+            "out/release/my_contract.bin",
+            "out/release/my_contract-storage_slots.json",
+        ]);
+
+        let dir = project.path();
+
+        let debug = ForcProject::load(dir, BuildProfile::Debug).unwrap();
+        let release = ForcProject::load(dir, "release".parse().unwrap()).unwrap();
+
+        assert_eq!(
+            debug,
+            ForcProject {
+                name: "my_contract".to_string(),
+                abi_path: dir.join("out/debug/my_contract-abi.json"),
+                bin_path: dir.join("out/debug/my_contract.bin"),
+                storage_slots_path: None,
+            }
+        );
+        assert_eq!(release.bin_path, dir.join("out/release/my_contract.bin"));
+        assert_eq!(
+            release.storage_slots_path,
+            Some(dir.join("out/release/my_contract-storage_slots.json"))
+        );
+    }
+
+    #[test]
+    fn missing_artifacts_are_an_error() {
+        let project = project_dir(&["out/debug/my_contract-abi.json"]);
+
+        let missing_bin = ForcProject::load(project.path(), BuildProfile::Debug);
+        let missing_profile = ForcProject::load(project.path(), BuildProfile::Release);
+
+        assert!(missing_bin
+            .unwrap_err()
+            .to_string()
+            .starts_with("Invalid data: expected the bytecode of `my_contract` at"));
+        assert!(matches!(missing_profile, Err(Error::InvalidData(_))));
+    }
+}
@@ -4,11 +4,11 @@ use crate::code_gen::docs_gen::expand_doc;
 use crate::errors::Error;
 use crate::json_abi::{parse_param, ABIParser};
 use crate::types::expand_type;
-use crate::utils::{ident, safe_ident};
+use crate::utils::{parse_tokens, safe_ident};
 use crate::{ParamType, Selector};
 use fuels_types::{CustomType, Function, Property, ENUM_KEYWORD, STRUCT_KEYWORD};
 use inflector::Inflector;
-use proc_macro2::{Literal, TokenStream};
+use proc_macro2::{Ident, Literal, TokenStream};
 use quote::quote;
 use std::collections::HashMap;
 
@@ -22,20 +22,23 @@ use std::collections::HashMap;
 /// and the function parameters that will be used in the actual contract call.
 ///
 /// [`Contract`]: crate::contract::Contract
+///
+/// `name` is the name of the generated method, usually the function's name
+/// escaped with `safe_ident`.
 pub fn expand_function(
     function: &Function,
+    name: &Ident,
     abi_parser: &ABIParser,
     custom_enums: &HashMap<String, Property>,
     custom_structs: &HashMap<String, Property>,
 ) -> Result<TokenStream, Error> {
-    let name = safe_ident(&function.name);
     let fn_signature = abi_parser.build_fn_selector(&function.name, &function.inputs);
 
     let encoded = ABIEncoder::encode_function_selector(fn_signature?.as_bytes());
 
     let tokenized_signature = expand_selector(encoded);
     let tokenized_output = expand_fn_outputs(&function.outputs)?;
-    let result = quote! { ContractCallHandler<#tokenized_output> };
+    let result = quote! { ContractCallHandler<#tokenized_output, S> };
 
     let (input, arg) = expand_function_arguments(function, custom_enums, custom_structs)?;
 
@@ -45,42 +48,44 @@ pub fn expand_function(
         hex::encode(encoded)
     ));
 
-    // Here we turn `ParamType`s into a custom stringified version that's identical
-    // to how we would declare a `ParamType` in Rust code. Which will then
-    // be used to be tokenized and passed onto `method_hash()`.
-    let mut output_params = vec![];
-    for output in &function.outputs {
-        let mut param_type_str: String = "ParamType::".to_owned();
-        let p = parse_param(output).unwrap();
-        param_type_str.push_str(&p.to_string());
-
-        let tok: proc_macro2::TokenStream = param_type_str.parse().unwrap();
-
-        output_params.push(tok);
-    }
+    let output_params = function
+        .outputs
+        .iter()
+        .map(expand_param_type)
+        .collect::<Result<Vec<_>, _>>()?;
 
     let output_params_token = quote! { &[#( #output_params ),*] };
 
     Ok(quote! {
         #doc
         pub fn #name(&self #input) -> #result {
-            Contract::method_hash(&self.wallet.get_provider().expect("Provider not set up"), self.contract_id, &self.wallet,
+            Contract::method_hash(self.contract_id, &self.wallet,
                 #tokenized_signature, #output_params_token, #arg).expect("method not found (this should never happen)")
         }
     })
 }
 
-fn expand_selector(selector: Selector) -> TokenStream {
+/// Expands the `ParamType` of `param`, i.e. the Rust expression building it.
+pub(crate) fn expand_param_type(param: &Property) -> Result<TokenStream, Error> {
+    // Here we turn `ParamType`s into a custom stringified version that's identical
+    // to how we would declare a `ParamType` in Rust code. Which will then
+    // be used to be tokenized, e.g. passed onto `method_hash()`.
+    let param_type_str = format!("ParamType::{}", parse_param(param)?);
+
+    parse_tokens(&param_type_str)
+}
+
+pub(crate) fn expand_selector(selector: Selector) -> TokenStream {
     let bytes = selector.iter().copied().map(Literal::u8_unsuffixed);
     quote! { [#( #bytes ),*] }
 }
 
 /// Expands the output of a function, i.e. what comes after `->` in a function signature.
-fn expand_fn_outputs(outputs: &[Property]) -> Result<TokenStream, Error> {
+pub(crate) fn expand_fn_outputs(outputs: &[Property]) -> Result<TokenStream, Error> {
     match outputs.len() {
         0 => Ok(quote! { () }),
         1 => {
-            let output = outputs.first().expect("Outputs shouldn't not be empty");
+            let output = &outputs[0];
 
             // If it's a primitive type, simply parse and expand.
             if !output.is_custom_type() {
@@ -90,59 +95,52 @@ fn expand_fn_outputs(outputs: &[Property]) -> Result<TokenStream, Error> {
             // If it's a {struct, enum} as the type of a function's output, use its tokenized name only.
             match output.is_struct_type() {
                 true => {
-                    let parsed_custom_type_name = extract_custom_type_name_from_abi_property(
+                    parse_tokens(&extract_custom_type_name_from_abi_property(
                         output,
                         Some(CustomType::Struct),
-                    )?
-                    .parse()
-                    .expect("Custom type name should be a valid Rust identifier");
-
-                    Ok(parsed_custom_type_name)
+                    )?)
                 }
                 false => match output.is_enum_type() {
                     true => {
-                        let parsed_custom_type_name = extract_custom_type_name_from_abi_property(
+                        parse_tokens(&extract_custom_type_name_from_abi_property(
                             output,
                             Some(CustomType::Enum),
-                        )?
-                        .parse()
-                        .expect("Custom type name should be a valid Rust identifier");
-
-                        Ok(parsed_custom_type_name)
+                        )?)
                     }
                     false => match output.has_custom_type_in_array() {
                         true => {
-                            let parsed_custom_type_name: TokenStream =
-                                extract_custom_type_name_from_abi_property(
+                            let parsed_custom_type_name =
+                                parse_tokens(&extract_custom_type_name_from_abi_property(
                                     output,
-                                    Some(
-                                        output
-                                            .get_custom_type()
-                                            .expect("Custom type in array should be set"),
-                                    ),
-                                )?
-                                .parse()
-                                .unwrap();
-
-                            Ok(quote! { ::std::vec::Vec<#parsed_custom_type_name> })
+                                    output.get_custom_type(),
+                                )?)?;
+
+                            let size = match parse_param(output)? {
+                                ParamType::Array(_, size) => Literal::usize_unsuffixed(size),
+                                other => {
+                                    return Err(Error::InvalidType(format!(
+                                        "Expected an array of custom types, got {:?}",
+                                        other
+                                    )))
+                                }
+                            };
+
+                            Ok(quote! { [#parsed_custom_type_name; #size] })
                         }
                         false => match output.has_custom_type_in_tuple() {
                             // If custom type is inside a tuple `(struct | enum <name>, ...)`,
                             // the type signature should be only `(<name>, ...)`.
                             // To do that, we remove the `STRUCT_KEYWORD` and `ENUM_KEYWORD` from it.
-                            true => {
-                                let tuple_type_signature: TokenStream = output
+                            true => parse_tokens(
+                                &output
                                     .type_field
                                     .replace(STRUCT_KEYWORD, "")
-                                    .replace(ENUM_KEYWORD, "")
-                                    .parse()
-                                    .expect("could not parse tuple type signature");
-
-                                Ok(tuple_type_signature)
-                            }
-                            false => {
-                                panic!("{}", format!("Output is of custom type, but not an enum, struct or enum/struct inside an array/tuple. This shouldn't never happen. Output received: {:?}", output));
-                            }
+                                    .replace(ENUM_KEYWORD, ""),
+                            ),
+                            false => Err(Error::InvalidType(format!(
+                                "output of type `{}` is neither a struct, an enum, nor an array or tuple of them",
+                                output.type_field
+                            ))),
                         },
                     },
                 },
@@ -168,8 +166,30 @@ fn expand_function_arguments(
     custom_enums: &HashMap<String, Property>,
     custom_structs: &HashMap<String, Property>,
 ) -> Result<(TokenStream, TokenStream), Error> {
-    let mut args = vec![];
-    let mut call_args = vec![];
+    let (names, types): (Vec<_>, Vec<_>) =
+        expand_function_parameters(fun, custom_enums, custom_structs)?
+            .into_iter()
+            .unzip();
+
+    // The final TokenStream of the argument declaration in a function declaration
+    let args = quote! { #( , #names: #types )* };
+
+    // The final TokenStream of the arguments being passed in a function call
+    // It'll look like `&[my_arg.into_token(), another_arg.into_token()]`
+    // as the [`Contract`] `method_hash` function expects a slice of Tokens
+    // in order to encode the call.
+    let call_args = quote! { &[ #(#names.into_token(), )* ] };
+
+    Ok((args, call_args))
+}
+
+/// Expands the name and the type of each of the arguments of a function.
+pub(crate) fn expand_function_parameters(
+    fun: &Function,
+    custom_enums: &HashMap<String, Property>,
+    custom_structs: &HashMap<String, Property>,
+) -> Result<Vec<(TokenStream, TokenStream)>, Error> {
+    let mut params = vec![];
 
     for (i, param) in fun.inputs.iter().enumerate() {
         // For each [`Property`] in a function input we expand:
@@ -185,13 +205,13 @@ fn expand_function_arguments(
             true => {
                 if param.is_enum_type() {
                     let name =
-                        extract_custom_type_name_from_abi_property(param, Some(CustomType::Enum))
-                            .expect("couldn't extract enum name from ABI property");
+                        extract_custom_type_name_from_abi_property(param, Some(CustomType::Enum))?;
                     custom_enums.get(&name)
                 } else if param.is_struct_type() {
-                    let name =
-                        extract_custom_type_name_from_abi_property(param, Some(CustomType::Struct))
-                            .expect("couldn't extract struct name from ABI property");
+                    let name = extract_custom_type_name_from_abi_property(
+                        param,
+                        Some(CustomType::Struct),
+                    )?;
                     custom_structs.get(&name)
                 } else {
                     match param.has_custom_type_in_array() {
@@ -200,8 +220,7 @@ fn expand_function_arguments(
                                 let name = extract_custom_type_name_from_abi_property(
                                     param,
                                     Some(custom_type),
-                                )
-                                .expect("couldn't extract custom type name from ABI property");
+                                )?;
 
                                 match custom_type {
                                     CustomType::Enum => custom_enums.get(&name),
@@ -226,45 +245,31 @@ fn expand_function_arguments(
 
         // If it's a tuple, don't expand it, just use the type signature as it is (minus the string "struct " | "enum ").
         let tok = if let ParamType::Tuple(_tuple) = kind {
-            let toks = build_expanded_tuple_params(param)
-                .expect("failed to build expanded tuple parameters");
-
-            toks.parse::<TokenStream>().unwrap()
+            parse_tokens(&build_expanded_tuple_params(param)?)?
         } else {
             expand_input_param(fun, &param.name, &parse_param(param)?, &custom_property)?
         };
 
-        // Add the TokenStream to argument declarations
-        args.push(quote! { #name: #tok });
-
-        // This `name` TokenStream is also added to the call arguments
-        call_args.push(name);
+        params.push((name, tok));
     }
 
-    // The final TokenStream of the argument declaration in a function declaration
-    let args = quote! { #( , #args )* };
-
-    // The final TokenStream of the arguments being passed in a function call
-    // It'll look like `&[my_arg.into_token(), another_arg.into_token()]`
-    // as the [`Contract`] `method_hash` function expects a slice of Tokens
-    // in order to encode the call.
-    let call_args = quote! { &[ #(#call_args.into_token(), )* ] };
-
-    Ok((args, call_args))
+    Ok(params)
 }
 
 // Builds a string "(type_1,type_2,type_3,...,type_n,)"
 // Where each type has been expanded through `expand_type()`
 // Except if it's a custom type, when just its name suffices.
 // For example, a tuple coming as "(b256, struct Person)"
-// Should be expanded as "([u8; 32], Person,)".
+// Should be expanded as "(Bits256, Person,)".
 fn build_expanded_tuple_params(tuple_param: &Property) -> Result<String, Error> {
     let mut toks: String = "(".to_string();
-    for component in tuple_param
-        .components
-        .as_ref()
-        .expect("tuple parameter should have components")
-    {
+    let components = tuple_param.components.as_ref().ok_or_else(|| {
+        Error::MissingData(format!(
+            "tuple `{}` has no `components`",
+            tuple_param.type_field
+        ))
+    })?;
+    for component in components {
         if !component.is_custom_type() {
             let p = parse_param(component)?;
             let tok = expand_type(&p)?;
@@ -285,7 +290,7 @@ fn build_expanded_tuple_params(tuple_param: &Property) -> Result<String, Error>
 /// Expands a positional identifier string that may be empty.
 ///
 /// Note that this expands the parameter name with `safe_ident`, meaning that
-/// identifiers that are reserved keywords get `_` appended to them.
+/// identifiers that are reserved keywords are escaped.
 pub fn expand_input_name(index: usize, name: &str) -> TokenStream {
     let name_str = match name {
         "" => format!("p{}", index),
@@ -306,16 +311,22 @@ fn expand_input_param(
     custom_type_property: &Option<&Property>,
 ) -> Result<TokenStream, Error> {
     match kind {
-        ParamType::Array(ty, _) => {
+        ParamType::Array(ty, size) => {
             let ty = expand_input_param(fun, param, ty, custom_type_property)?;
+            let size = Literal::usize_unsuffixed(*size);
             Ok(quote! {
-                ::std::vec::Vec<#ty>
+                [#ty; #size]
             })
         }
         ParamType::Enum(_) => {
-            let ident = ident(
+            let ident = safe_ident(
                 &extract_custom_type_name_from_abi_property(
-                    custom_type_property.expect("Custom type property not found for enum"),
+                    custom_type_property.ok_or_else(|| {
+                        Error::MissingData(format!(
+                            "no definition found for the enum of `{}` in `{}`",
+                            param, fun.name
+                        ))
+                    })?,
                     Some(CustomType::Enum),
                 )?
                 .to_class_case(),
@@ -323,9 +334,14 @@ fn expand_input_param(
             Ok(quote! { #ident })
         }
         ParamType::Struct(_) => {
-            let ident = ident(
+            let ident = safe_ident(
                 &extract_custom_type_name_from_abi_property(
-                    custom_type_property.expect("Custom type property not found for struct"),
+                    custom_type_property.ok_or_else(|| {
+                        Error::MissingData(format!(
+                            "no definition found for the struct of `{}` in `{}`",
+                            param, fun.name
+                        ))
+                    })?,
                     Some(CustomType::Struct),
                 )?
                 .to_class_case(),
@@ -357,6 +373,7 @@ mod tests {
         });
         let result = expand_function(
             &the_function,
+            &safe_ident(&the_function.name),
             &ABIParser::new(),
             &Default::default(),
             &Default::default(),
@@ -364,9 +381,8 @@ mod tests {
         let expected = TokenStream::from_str(
             r#"
 #[doc = "Calls the contract's `HelloWorld` (0x0000000097d4de45) function"]
-pub fn HelloWorld(&self, bimbam: bool) -> ContractCallHandler<()> {
+pub fn HelloWorld(&self, bimbam: bool) -> ContractCallHandler<(), S> {
     Contract::method_hash(
-        &self.wallet.get_provider().expect("Provider not set up"),
         self.contract_id,
         &self.wallet,
         [0, 0, 0, 0, 151, 212, 222, 69],
@@ -464,7 +480,13 @@ pub fn HelloWorld(&self, bimbam: bool) -> ContractCallHandler<()> {
             },
         );
         let abi_parser = ABIParser::new();
-        let result = expand_function(&the_function, &abi_parser, &custom_enums, &custom_structs);
+        let result = expand_function(
+            &the_function,
+            &safe_ident(&the_function.name),
+            &abi_parser,
+            &custom_enums,
+            &custom_structs,
+        );
         // Some more editing was required because it is not rustfmt-compatible (adding/removing parentheses or commas)
         let expected = TokenStream::from_str(
             r#"
@@ -472,9 +494,8 @@ pub fn HelloWorld(&self, bimbam: bool) -> ContractCallHandler<()> {
 pub fn hello_world(
     &self,
     the_only_allowed_input: SomeWeirdFrenchCuisine
-) -> ContractCallHandler<(CoolIndieGame , EntropyCirclesEnum)> {
+) -> ContractCallHandler<(CoolIndieGame , EntropyCirclesEnum), S> {
     Contract::method_hash(
-        &self.wallet.get_provider().expect("Provider not set up"),
         self.contract_id,
         &self.wallet,
         [0, 0, 0, 0, 118, 178, 90, 36],
@@ -708,9 +729,11 @@ pub fn hello_world(
         let result = expand_input_name(1080, "");
         assert_eq!(result.to_string(), "p1080");
         let result = expand_input_name(0, "if");
-        assert_eq!(result.to_string(), "if_");
-        let result = expand_input_name(0, "let");
-        assert_eq!(result.to_string(), "let_");
+        assert_eq!(result.to_string(), "r#if");
+        let result = expand_input_name(0, "type");
+        assert_eq!(result.to_string(), "r#type");
+        let result = expand_input_name(0, "self");
+        assert_eq!(result.to_string(), "self_");
     }
 
     // --- expand_input_param ---
@@ -722,13 +745,13 @@ pub fn hello_world(
         let result = expand_input_param(&def, "unused", &ParamType::U64, &None);
         assert_eq!(result.unwrap().to_string(), "u64");
         let result = expand_input_param(&def, "unused", &ParamType::String(10), &None);
-        assert_eq!(result.unwrap().to_string(), "String");
+        assert_eq!(result.unwrap().to_string(), "SizedAsciiString < 10 >");
     }
     #[test]
     fn test_expand_input_param_array() {
         let array_type = ParamType::Array(Box::new(ParamType::U64), 10);
         let result = expand_input_param(&Function::default(), "unused", &array_type, &None);
-        assert_eq!(result.unwrap().to_string(), ":: std :: vec :: Vec < u64 >");
+        assert_eq!(result.unwrap().to_string(), "[u64 ; 10]");
     }
     #[test]
     fn test_expand_input_param_custom_type() {
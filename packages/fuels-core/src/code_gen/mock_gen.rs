@@ -0,0 +1,206 @@
+use crate::code_gen::functions_gen::{expand_fn_outputs, expand_function_parameters};
+use crate::errors::Error;
+use crate::utils::{ident, safe_ident};
+use fuels_types::{Function, Property};
+use inflector::Inflector;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use std::collections::HashMap;
+
+// Functions used by the Abigen to expand a mock of the contract.
+
+/// Expands the `<Name>Methods` trait, holding one `async` method per contract
+/// function, along with its two implementations: `<Name>`, which calls the
+/// deployed contract, and `<Name>Mock`, which returns the results it was
+/// programmed with and records the calls made to it, as `<Name>Call`s (see
+/// `calls_gen`). Code written against the
+/// trait can then be unit tested without a node or compiled Sway binaries.
+///
+/// `functions` are the contract's functions, along with the names of the
+/// methods generated for them.
+pub(crate) fn expand_mock(
+    contract_name: &Ident,
+    functions: &[(Ident, &Function)],
+    custom_enums: &HashMap<String, Property>,
+    custom_structs: &HashMap<String, Property>,
+) -> Result<TokenStream, Error> {
+    let trait_name = ident(&format!("{}Methods", contract_name));
+    let mock_name = ident(&format!("{}Mock", contract_name));
+    let call_name = ident(&format!("{}Call", contract_name));
+
+    let mut trait_fns = Vec::with_capacity(functions.len());
+    let mut contract_fns = Vec::with_capacity(functions.len());
+    let mut mock_fns = Vec::with_capacity(functions.len());
+    let mut result_fields = Vec::with_capacity(functions.len());
+    let mut expect_fns = Vec::with_capacity(functions.len());
+
+    for (name, function) in functions {
+        let (arg_names, arg_types): (Vec<_>, Vec<_>) =
+            expand_function_parameters(function, custom_enums, custom_structs)?
+                .into_iter()
+                .unzip();
+        let output = expand_fn_outputs(&function.outputs)?;
+
+        let method = name.to_string();
+        let method = method.trim_start_matches("r#");
+        let variant = safe_ident(&method.to_class_case());
+        let results = ident(&format!("{}_results", method.to_snake_case()));
+        let expect = ident(&format!("expect_{}", method.to_snake_case()));
+
+        let signature = quote! {
+            async fn #name(&self #(, #arg_names: #arg_types)*) -> Result<#output, fuels::core::errors::Error>
+        };
+        let call = if arg_names.is_empty() {
+            quote! { #call_name::#variant }
+        } else {
+            quote! { #call_name::#variant { #( #arg_names ),* } }
+        };
+        let unexpected_call = format!(
+            "unexpected call to `{}` on `{}`, no result was programmed with `{}`",
+            method, mock_name, expect
+        );
+        let expect_doc = format!(
+            "Queues the result of a call to `{}`. Results are returned in the order they were queued.",
+            method
+        );
+
+        trait_fns.push(quote! { #signature; });
+
+        contract_fns.push(quote! {
+            #signature {
+                Ok(#contract_name::#name(self #(, #arg_names)*).call().await?.value)
+            }
+        });
+
+        mock_fns.push(quote! {
+            #signature {
+                self.calls.lock().unwrap().push(#call);
+                self.#results.lock().unwrap().pop_front().unwrap_or_else(|| {
+                    Err(fuels::core::errors::Error::ContractCallError(#unexpected_call.to_string(), vec![]))
+                })
+            }
+        });
+
+        result_fields.push(quote! {
+            #results: std::sync::Mutex<std::collections::VecDeque<Result<#output, fuels::core::errors::Error>>>
+        });
+
+        expect_fns.push(quote! {
+            #[doc = #expect_doc]
+            pub fn #expect(&self, result: Result<#output, fuels::core::errors::Error>) -> &Self {
+                self.#results.lock().unwrap().push_back(result);
+                self
+            }
+        });
+    }
+
+    let trait_doc = format!(
+        "The functions of the `{}` contract, implemented by both `{}` and `{}`.",
+        contract_name, contract_name, mock_name
+    );
+    let mock_doc = format!(
+        "A stand-in for `{}` that doesn't need a node. Program the result of each call \
+        with the `expect_*` methods, a revert being an `Err(Error::ContractCallError(..))`, \
+        and check the calls that were made with `calls`.",
+        contract_name
+    );
+
+    Ok(quote! {
+        #[doc = #trait_doc]
+        #[fuels::contract::async_trait]
+        pub trait #trait_name {
+            #( #trait_fns )*
+        }
+
+        #[fuels::contract::async_trait]
+        impl<S: fuels::signers::Signer + Clone> #trait_name for #contract_name<S> {
+            #( #contract_fns )*
+        }
+
+        #[doc = #mock_doc]
+        #[derive(Default)]
+        pub struct #mock_name {
+            calls: std::sync::Mutex<Vec<#call_name>>,
+            #( #result_fields ),*
+        }
+
+        impl #mock_name {
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// The calls made to the mock so far, oldest first.
+            pub fn calls(&self) -> Vec<#call_name> {
+                self.calls.lock().unwrap().clone()
+            }
+
+            #( #expect_fns )*
+        }
+
+        #[fuels::contract::async_trait]
+        impl #trait_name for #mock_name {
+            #( #mock_fns )*
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_trait_calls_and_mock() {
+        let abi = [
+            Function {
+                type_field: "function".to_string(),
+                inputs: vec![Property {
+                    name: "amount".to_string(),
+                    type_field: "u64".to_string(),
+                    components: None,
+                }],
+                name: "increment".to_string(),
+                outputs: vec![Property {
+                    name: "".to_string(),
+                    type_field: "bool".to_string(),
+                    components: None,
+                }],
+            },
+            Function {
+                type_field: "function".to_string(),
+                inputs: vec![],
+                name: "reset".to_string(),
+                outputs: vec![],
+            },
+        ];
+
+        let functions: Vec<_> = abi
+            .iter()
+            .map(|function| (safe_ident(&function.name), function))
+            .collect();
+        let tokens = expand_mock(
+            &ident("Counter"),
+            &functions,
+            &Default::default(),
+            &Default::default(),
+        )
+        .unwrap();
+        let code = crate::formatter::format(&tokens).unwrap();
+
+        for expected in [
+            "pub trait CounterMethods {",
+            "async fn increment(&self, amount: u64) -> Result<bool, fuels::core::errors::Error>;",
+            "impl<S: fuels::signers::Signer + Clone> CounterMethods for Counter<S> {",
+            "Ok(Counter::increment(self, amount).call().await?.value)",
+            "pub fn expect_reset(",
+            "self.calls.lock().unwrap().push(CounterCall::Increment { amount });",
+            "impl CounterMethods for CounterMock {",
+        ] {
+            assert!(
+                code.contains(expected),
+                "`{}` not found in:\n{}",
+                expected,
+                code
+            );
+        }
+    }
+}
@@ -2,6 +2,14 @@
 //! from a fuel/sway ABI.
 pub mod abigen;
 pub mod bindings;
+pub mod calls_gen;
 pub mod custom_types_gen;
 pub mod docs_gen;
+pub mod forc_project;
 pub mod functions_gen;
+pub mod mock_gen;
+pub mod multi_abigen;
+
+pub use custom_types_gen::TypeAttributes;
+pub use forc_project::{BuildProfile, ForcProject};
+pub use multi_abigen::MultiAbigen;
@@ -0,0 +1,470 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::code_gen::abigen::{expand_type_includes, Abigen};
+use crate::code_gen::bindings::ContractBindings;
+use crate::code_gen::custom_types_gen::{expand_custom_enum, expand_custom_struct, TypeAttributes};
+use crate::code_gen::forc_project::{BuildProfile, ForcProject};
+use crate::errors::Error;
+use crate::utils::ident;
+use fuels_types::Property;
+use inflector::Inflector;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// The name of the module holding the custom types that are used by more than
+/// one contract.
+pub const SHARED_TYPES_MODULE: &str = "shared_types";
+
+/// Generates the bindings of several contracts at once, e.g. from a `build.rs`:
+///
+/// ```ignore
+/// let bindings = MultiAbigen::new()
+///     .add_contract("MyContract", "abis/my_contract-abi.json")
+///     .add_forc_project("../other_contract")
+///     .generate()?;
+///
+/// bindings.emit_rerun_if_changed();
+/// bindings.write_to_file(Path::new(&env::var("OUT_DIR")?).join("bindings.rs"))?;
+/// ```
+///
+/// Each contract gets its own module, named after the contract in snake case.
+/// Custom types that appear in more than one ABI are generated once, in a
+/// [`SHARED_TYPES_MODULE`] module, so that they can be passed from one
+/// contract to another. They are re-exported by the module of every contract
+/// using them.
+#[derive(Debug, Clone, Default)]
+pub struct MultiAbigen {
+    contracts: Vec<ContractSource>,
+    no_std: bool,
+    type_attributes: TypeAttributes,
+}
+
+#[derive(Debug, Clone)]
+enum ContractSource {
+    Abi { name: String, abi_path: PathBuf },
+    ForcProject(PathBuf),
+}
+
+impl MultiAbigen {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a contract named `name` whose JSON ABI is found at `abi_path`.
+    pub fn add_contract(mut self, name: &str, abi_path: impl AsRef<Path>) -> Self {
+        self.contracts.push(ContractSource::Abi {
+            name: name.to_string(),
+            abi_path: abi_path.as_ref().to_path_buf(),
+        });
+        self
+    }
+
+    /// Adds the contract built by the forc project in `project_dir`. The
+    /// contract is named after the project, e.g. `my_contract` becomes
+    /// `MyContract`.
+    pub fn add_forc_project(mut self, project_dir: impl AsRef<Path>) -> Self {
+        self.contracts.push(ContractSource::ForcProject(
+            project_dir.as_ref().to_path_buf(),
+        ));
+        self
+    }
+
+    pub fn no_std(mut self) -> Self {
+        self.no_std = true;
+        self
+    }
+
+    /// Adds derives and attributes to the custom types of every contract,
+    /// shared ones included. See [`Abigen::with_type_attributes`].
+    pub fn with_type_attributes(mut self, type_attributes: TypeAttributes) -> Self {
+        self.type_attributes = type_attributes;
+        self
+    }
+
+    /// Generates the bindings of all the contracts into a single set of
+    /// bindings.
+    pub fn generate(self) -> Result<MultiContractBindings, Error> {
+        let contracts = self
+            .contracts
+            .iter()
+            .map(ContractSource::resolve)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        check_for_name_collisions(&contracts)?;
+
+        let abigens = contracts
+            .iter()
+            .map(|(name, abi_path)| {
+                let abigen = Abigen::from_abi_file(name, abi_path)?
+                    .with_type_attributes(self.type_attributes.clone());
+                Ok(if self.no_std { abigen.no_std() } else { abigen })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let shared_types = find_shared_types(&contracts, &abigens)?;
+        let shared_type_names: HashSet<String> = shared_types.keys().cloned().collect();
+        let shared_types_module = ident(SHARED_TYPES_MODULE);
+
+        let mut contract_modules = Vec::with_capacity(abigens.len());
+        for ((name, _), abigen) in contracts.iter().zip(abigens) {
+            let module = ident(&name.to_snake_case());
+            let bindings = abigen
+                .with_shared_types(
+                    quote! { super::super::#shared_types_module },
+                    shared_type_names.clone(),
+                )
+                .expand()?;
+
+            contract_modules.push(quote! {
+                pub mod #module {
+                    #bindings
+                }
+            });
+        }
+
+        let shared_types_tokens =
+            expand_shared_types(&shared_types, &self.type_attributes, self.no_std)?;
+
+        Ok(MultiContractBindings {
+            bindings: ContractBindings {
+                tokens: quote! {
+                    pub mod #shared_types_module {
+                        #shared_types_tokens
+                    }
+
+                    #( #contract_modules )*
+                },
+                rustfmt: true,
+            },
+            abi_paths: contracts
+                .into_iter()
+                .map(|(_, abi_path)| abi_path)
+                .collect(),
+        })
+    }
+}
+
+impl ContractSource {
+    /// Returns the name of the contract and the path to its ABI.
+    fn resolve(&self) -> Result<(String, PathBuf), Error> {
+        match self {
+            ContractSource::Abi { name, abi_path } => Ok((name.clone(), abi_path.clone())),
+            ContractSource::ForcProject(project_dir) => {
+                let project = ForcProject::load(project_dir, BuildProfile::Debug)?;
+                Ok((project.name.to_class_case(), project.abi_path))
+            }
+        }
+    }
+}
+
+// Two contracts generating the same module, or a contract generating the
+// shared types module, would otherwise fail to compile with a confusing error.
+fn check_for_name_collisions(contracts: &[(String, PathBuf)]) -> Result<(), Error> {
+    let mut modules: HashMap<String, &str> = HashMap::new();
+
+    for (name, _) in contracts {
+        let module = name.to_snake_case();
+
+        if module == SHARED_TYPES_MODULE {
+            return Err(Error::InvalidName(format!(
+                "contract `{}` would be generated in module `{}`, which is reserved for the types shared between contracts",
+                name, module
+            )));
+        }
+
+        if let Some(other) = modules.insert(module.clone(), name) {
+            return Err(Error::InvalidName(format!(
+                "contracts `{}` and `{}` would both be generated in module `{}`",
+                other, name, module
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Finds the custom types that are used by more than one contract. Fails if
+/// two contracts define a type with the same name differently.
+fn find_shared_types(
+    contracts: &[(String, PathBuf)],
+    abigens: &[Abigen],
+) -> Result<HashMap<String, Property>, Error> {
+    // Type name -> (first contract using it, its definition, number of contracts using it)
+    let mut seen: HashMap<String, (&str, &Property, usize)> = HashMap::new();
+
+    for ((contract_name, _), abigen) in contracts.iter().zip(abigens) {
+        for (type_name, prop) in abigen.custom_types() {
+            match seen.get_mut(type_name) {
+                Some((first_contract, first_prop, count)) => {
+                    // The `name` of the property is the name of the argument or
+                    // field it was found in, which is irrelevant here.
+                    if first_prop.type_field != prop.type_field
+                        || first_prop.components != prop.components
+                    {
+                        return Err(Error::InvalidType(format!(
+                            "type `{}` is defined differently in the ABIs of `{}` and `{}`",
+                            type_name, first_contract, contract_name
+                        )));
+                    }
+                    *count += 1;
+                }
+                None => {
+                    seen.insert(type_name.clone(), (contract_name, prop, 1));
+                }
+            }
+        }
+    }
+
+    Ok(seen
+        .into_iter()
+        .filter(|(_, (_, _, count))| *count > 1)
+        .map(|(type_name, (_, prop, _))| (type_name, prop.clone()))
+        .collect())
+}
+
+fn expand_shared_types(
+    shared_types: &HashMap<String, Property>,
+    type_attributes: &TypeAttributes,
+    no_std: bool,
+) -> Result<TokenStream, Error> {
+    // Sort the types by name so that the generated code is the same from one
+    // run to the next.
+    let mut shared_types: Vec<_> = shared_types.iter().collect();
+    shared_types.sort_by_key(|(name, _)| *name);
+
+    let types = shared_types
+        .into_iter()
+        .map(|(name, prop)| {
+            if prop.is_enum_type() {
+                expand_custom_enum(name, prop, type_attributes)
+            } else {
+                expand_custom_struct(prop, type_attributes)
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let includes = expand_type_includes(no_std);
+
+    Ok(quote! {
+        #![allow(clippy::enum_variant_names)]
+        #![allow(dead_code)]
+        #![allow(unused_imports)]
+
+        #includes
+
+        #( #types )*
+    })
+}
+
+/// The bindings generated by [`MultiAbigen`], along with the ABIs they were
+/// generated from.
+#[derive(Debug)]
+pub struct MultiContractBindings {
+    pub bindings: ContractBindings,
+    /// The ABI files the bindings were generated from.
+    pub abi_paths: Vec<PathBuf>,
+}
+
+impl MultiContractBindings {
+    /// Prints a `cargo:rerun-if-changed` line for each ABI, so that a build
+    /// script generating the bindings is re-run whenever an ABI changes.
+    pub fn emit_rerun_if_changed(&self) {
+        for line in self.rerun_if_changed_lines() {
+            println!("{}", line);
+        }
+    }
+
+    pub fn rerun_if_changed_lines(&self) -> Vec<String> {
+        self.abi_paths
+            .iter()
+            .map(|path| format!("cargo:rerun-if-changed={}", path.display()))
+            .collect()
+    }
+
+    /// Writes the bindings to the specified file. The file is meant to be
+    /// brought into scope with `include!`.
+    pub fn write_to_file<P>(&self, path: P) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        self.bindings.write_to_file(path)
+    }
+
+    pub fn into_tokens(self) -> TokenStream {
+        self.bindings.into_tokens()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::{tempdir, TempDir};
+
+    fn write(dir: &TempDir, file: &str, contents: &str) -> PathBuf {
+        let path = dir.path().join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn abi_taking(fn_name: &str, type_field: &str, components: &str) -> String {
+        format!(
+            r#"[
+                {{
+                    "type": "contract",
+                    "inputs": [
+                        {{
+                            "name": "value",
+                            "type": "{}",
+                            "components": {}
+                        }}
+                    ],
+                    "name": "{}",
+                    "outputs": []
+                }}
+            ]"#,
+            type_field, components, fn_name
+        )
+    }
+
+    const PERSON: &str = r#"[{ "name": "age", "type": "u64" }]"#;
+
+    #[test]
+    fn types_used_by_several_contracts_are_generated_once() {
+        let dir = tempdir().unwrap();
+        let a = write(
+            &dir,
+            "a.json",
+            &abi_taking("take_person", "struct Person", PERSON),
+        );
+        let b = write(
+            &dir,
+            "b.json",
+            &abi_taking("give_person", "struct Person", PERSON),
+        );
+        let c = write(
+            &dir,
+            "c.json",
+            &abi_taking(
+                "take_other",
+                "struct Other",
+                r#"[{ "name": "x", "type": "bool" }]"#,
+            ),
+        );
+
+        let bindings = MultiAbigen::new()
+            .add_contract("ContractA", &a)
+            .add_contract("ContractB", &b)
+            .add_contract("ContractC", &c)
+            .generate()
+            .unwrap();
+
+        let code = bindings.into_tokens().to_string();
+
+        assert_eq!(code.matches("pub struct Person").count(), 1);
+        assert_eq!(code.matches("pub struct Other").count(), 1);
+        assert!(code.contains("pub mod shared_types { # ! [allow (clippy :: enum_variant_names)] # ! [allow (dead_code)] # ! [allow (unused_imports)] use fuels :: core :: { Bits256"));
+        assert!(code.contains("pub mod contract_a {"));
+        assert!(code.contains("pub use super :: super :: shared_types :: * ;"));
+    }
+
+    #[test]
+    fn emits_rerun_if_changed_for_every_abi() {
+        let dir = tempdir().unwrap();
+        let a = write(
+            &dir,
+            "a.json",
+            &abi_taking("take_person", "struct Person", PERSON),
+        );
+
+        let bindings = MultiAbigen::new()
+            .add_contract("ContractA", &a)
+            .generate()
+            .unwrap();
+
+        assert_eq!(
+            bindings.rerun_if_changed_lines(),
+            vec![format!("cargo:rerun-if-changed={}", a.display())]
+        );
+    }
+
+    #[test]
+    fn finds_the_abi_of_a_forc_project() {
+        let dir = tempdir().unwrap();
+        let abi = write(
+            &dir,
+            "my_contract/out/debug/my_contract-abi.json",
+            &abi_taking("take_person", "struct Person", PERSON),
+        );
+        write(&dir, "my_contract/out/debug/my_contract.bin", "");
+
+        let bindings = MultiAbigen::new()
+            .add_forc_project(dir.path().join("my_contract"))
+            .generate()
+            .unwrap();
+
+        assert_eq!(bindings.abi_paths, vec![abi]);
+        assert!(bindings
+            .into_tokens()
+            .to_string()
+            .contains("pub mod my_contract { pub use mycontract_mod :: * ;"));
+    }
+
+    #[test]
+    fn differing_definitions_of_a_type_are_an_error() {
+        let dir = tempdir().unwrap();
+        let a = write(
+            &dir,
+            "a.json",
+            &abi_taking("take_person", "struct Person", PERSON),
+        );
+        let b = write(
+            &dir,
+            "b.json",
+            &abi_taking(
+                "give_person",
+                "struct Person",
+                r#"[{ "name": "age", "type": "u32" }]"#,
+            ),
+        );
+
+        let err = MultiAbigen::new()
+            .add_contract("ContractA", &a)
+            .add_contract("ContractB", &b)
+            .generate()
+            .expect_err("Should have failed on the differing definitions");
+
+        assert_eq!(
+            err.to_string(),
+            "Invalid type: type `Person` is defined differently in the ABIs of `ContractA` and `ContractB`"
+        );
+    }
+
+    #[test]
+    fn contract_name_collisions_are_an_error() {
+        let dir = tempdir().unwrap();
+        let a = write(
+            &dir,
+            "a.json",
+            &abi_taking("take_person", "struct Person", PERSON),
+        );
+
+        let err = MultiAbigen::new()
+            .add_contract("MyContract", &a)
+            .add_contract("my_contract", &a)
+            .generate()
+            .expect_err("Should have failed on the module name collision");
+        assert_eq!(
+            err.to_string(),
+            "Invalid name: contracts `MyContract` and `my_contract` would both be generated in module `my_contract`"
+        );
+
+        let err = MultiAbigen::new()
+            .add_contract("SharedTypes", &a)
+            .generate()
+            .expect_err("Should have failed on the reserved module name");
+        assert!(matches!(err, Error::InvalidName(_)));
+    }
+}
@@ -0,0 +1,404 @@
+use crate::errors::Error;
+use crate::{Bits256, Token};
+use fuels_types::{Property, ENUM_KEYWORD, STRUCT_KEYWORD};
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::fmt::Write;
+
+/// A decoded value labelled with the names found in the JSON ABI.
+///
+/// While a [`Token`] only knows the shape of the data (e.g.
+/// `Struct([U64(1), Bool(true)])`), a `DecodedValue` is built by walking the
+/// [`Property`] tree that describes it, so struct fields, enum variants and
+/// custom type names are kept around. This makes decoded calls and logs
+/// readable without cross-referencing the ABI by hand.
+///
+/// It renders as a single line with `{}`, as indented text with `{:#}`, and
+/// as JSON with [`DecodedValue::to_json`]. It also implements `Serialize`,
+/// with the same layout as the JSON.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodedValue {
+    Unit,
+    Bool(bool),
+    /// Any of `u8`, `u16`, `u32`, `u64` and `byte`.
+    Number(u64),
+    B256(Bits256),
+    String(String),
+    Array(Vec<DecodedValue>),
+    Tuple(Vec<DecodedValue>),
+    Struct {
+        name: String,
+        fields: Vec<(String, DecodedValue)>,
+    },
+    Enum {
+        name: String,
+        variant: String,
+        value: Box<DecodedValue>,
+    },
+}
+
+impl DecodedValue {
+    /// Labels `token` using the `Property` that describes it in the JSON ABI.
+    pub fn from_token(property: &Property, token: Token) -> Result<Self, Error> {
+        Self::label(Some(property), token)
+    }
+
+    /// Labels a list of tokens, e.g. the result of decoding a function's
+    /// outputs, using the list of properties that describe them.
+    pub fn from_tokens(properties: &[Property], tokens: Vec<Token>) -> Result<Vec<Self>, Error> {
+        if properties.len() != tokens.len() {
+            return Err(Error::InvalidData(format!(
+                "expected {} tokens to label, got {}",
+                properties.len(),
+                tokens.len()
+            )));
+        }
+
+        properties
+            .iter()
+            .zip(tokens)
+            .map(|(property, token)| Self::from_token(property, token))
+            .collect()
+    }
+
+    // `property` is `None` when the ABI doesn't describe the value any
+    // further, e.g. the elements of an array of primitives. The token alone
+    // is enough in that case, only the names are lost.
+    fn label(property: Option<&Property>, token: Token) -> Result<Self, Error> {
+        let components = property.and_then(|p| p.components.as_deref());
+
+        let value = match token {
+            Token::Unit => DecodedValue::Unit,
+            Token::Bool(value) => DecodedValue::Bool(value),
+            Token::U8(value) | Token::Byte(value) => DecodedValue::Number(value.into()),
+            Token::U16(value) => DecodedValue::Number(value.into()),
+            Token::U32(value) => DecodedValue::Number(value.into()),
+            Token::U64(value) => DecodedValue::Number(value),
+            Token::B256(value) => DecodedValue::B256(Bits256(value)),
+            Token::String(value) => DecodedValue::String(value),
+            Token::Array(tokens) => {
+                let element = components.and_then(|c| c.first());
+                DecodedValue::Array(
+                    tokens
+                        .into_iter()
+                        .map(|token| Self::label(element, token))
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+            Token::Tuple(tokens) => DecodedValue::Tuple(
+                tokens
+                    .into_iter()
+                    .enumerate()
+                    .map(|(idx, token)| Self::label(components.and_then(|c| c.get(idx)), token))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Token::Struct(tokens) => {
+                let fields = tokens
+                    .into_iter()
+                    .enumerate()
+                    .map(|(idx, token)| {
+                        let field = components.and_then(|c| c.get(idx));
+                        let name = field.map_or_else(|| idx.to_string(), |f| f.name.clone());
+                        Ok((name, Self::label(field, token)?))
+                    })
+                    .collect::<Result<_, Error>>()?;
+
+                DecodedValue::Struct {
+                    name: custom_type_name(property, STRUCT_KEYWORD),
+                    fields,
+                }
+            }
+            Token::Enum(selector) => {
+                let (discriminant, token) = *selector;
+                let variant = match components {
+                    Some(components) => {
+                        Some(components.get(discriminant as usize).ok_or_else(|| {
+                            Error::InvalidData(format!(
+                                "discriminant {} is out of range for an enum with {} variants",
+                                discriminant,
+                                components.len()
+                            ))
+                        })?)
+                    }
+                    None => None,
+                };
+
+                DecodedValue::Enum {
+                    name: custom_type_name(property, ENUM_KEYWORD),
+                    variant: variant.map_or_else(|| discriminant.to_string(), |v| v.name.clone()),
+                    value: Box::new(Self::label(variant, token)?),
+                }
+            }
+        };
+
+        Ok(value)
+    }
+
+    /// Renders the value as JSON. Structs become objects, with their fields in
+    /// ABI order, enums become an object with the variant as its single key,
+    /// and `b256`s become `0x`-prefixed hex strings.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("a DecodedValue is always valid JSON")
+    }
+
+    fn write(&self, out: &mut String, pretty: bool, indent: usize) -> fmt::Result {
+        // Writes a `, `-separated (or, when pretty printing, one per line)
+        // list of items between `open` and `close`.
+        fn write_list<T>(
+            out: &mut String,
+            pretty: bool,
+            indent: usize,
+            (open, close): (&str, &str),
+            items: &[T],
+            mut write_item: impl FnMut(&mut String, &T) -> fmt::Result,
+        ) -> fmt::Result {
+            if items.is_empty() {
+                out.push_str(open.trim());
+                out.push_str(close.trim());
+                return Ok(());
+            }
+            out.push_str(if pretty { open.trim() } else { open });
+            for (idx, item) in items.iter().enumerate() {
+                if pretty {
+                    write!(out, "\n{:width$}", "", width = (indent + 1) * 4)?;
+                } else if idx > 0 {
+                    out.push_str(", ");
+                }
+                write_item(out, item)?;
+                if pretty {
+                    out.push(',');
+                }
+            }
+            if pretty {
+                write!(out, "\n{:width$}{}", "", close.trim(), width = indent * 4)
+            } else {
+                out.push_str(close);
+                Ok(())
+            }
+        }
+
+        match self {
+            DecodedValue::Unit => out.push_str("()"),
+            DecodedValue::Bool(value) => write!(out, "{}", value)?,
+            DecodedValue::Number(value) => write!(out, "{}", value)?,
+            DecodedValue::B256(value) => write!(out, "{}", value)?,
+            DecodedValue::String(value) => write!(out, "{:?}", value)?,
+            DecodedValue::Array(values) => {
+                write_list(out, pretty, indent, ("[", "]"), values, |out, value| {
+                    value.write(out, pretty, indent + 1)
+                })?
+            }
+            DecodedValue::Tuple(values) => {
+                write_list(out, pretty, indent, ("(", ")"), values, |out, value| {
+                    value.write(out, pretty, indent + 1)
+                })?
+            }
+            DecodedValue::Struct { name, fields } => {
+                if !name.is_empty() {
+                    write!(out, "{} ", name)?;
+                }
+                write_list(
+                    out,
+                    pretty,
+                    indent,
+                    ("{ ", " }"),
+                    fields,
+                    |out, (name, value)| {
+                        write!(out, "{}: ", name)?;
+                        value.write(out, pretty, indent + 1)
+                    },
+                )?
+            }
+            DecodedValue::Enum { variant, value, .. } => {
+                out.push_str(variant);
+                if **value != DecodedValue::Unit {
+                    out.push('(');
+                    value.write(out, pretty, indent)?;
+                    out.push(')');
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for DecodedValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut out = String::new();
+        self.write(&mut out, f.alternate(), 0)?;
+        f.write_str(&out)
+    }
+}
+
+// Serialized by hand rather than through a `serde_json::Value`, whose
+// objects don't keep the order of their keys.
+impl Serialize for DecodedValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            DecodedValue::Unit => serializer.serialize_unit(),
+            DecodedValue::Bool(value) => serializer.serialize_bool(*value),
+            DecodedValue::Number(value) => serializer.serialize_u64(*value),
+            DecodedValue::B256(value) => value.serialize(serializer),
+            DecodedValue::String(value) => serializer.serialize_str(value),
+            DecodedValue::Array(values) | DecodedValue::Tuple(values) => {
+                let mut seq = serializer.serialize_seq(Some(values.len()))?;
+                for value in values {
+                    seq.serialize_element(value)?;
+                }
+                seq.end()
+            }
+            DecodedValue::Struct { fields, .. } => {
+                let mut map = serializer.serialize_map(Some(fields.len()))?;
+                for (name, value) in fields {
+                    map.serialize_entry(name, value)?;
+                }
+                map.end()
+            }
+            DecodedValue::Enum { variant, value, .. } => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(variant, value)?;
+                map.end()
+            }
+        }
+    }
+}
+
+fn custom_type_name(property: Option<&Property>, keyword: &str) -> String {
+    property
+        .and_then(|p| p.type_field.strip_prefix(keyword))
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn property(name: &str, type_field: &str, components: Option<Vec<Property>>) -> Property {
+        Property {
+            name: name.to_string(),
+            type_field: type_field.to_string(),
+            components,
+        }
+    }
+
+    fn my_struct_property() -> Property {
+        property(
+            "value",
+            "struct MyStruct",
+            Some(vec![
+                property("id", "u64", None),
+                property("flag", "bool", None),
+                property(
+                    "kind",
+                    "enum Kind",
+                    Some(vec![
+                        property("Empty", "()", None),
+                        property("Tagged", "str[3]", None),
+                    ]),
+                ),
+            ]),
+        )
+    }
+
+    fn my_struct_token() -> Token {
+        Token::Struct(vec![
+            Token::U64(1),
+            Token::Bool(true),
+            Token::Enum(Box::new((1, Token::String("abc".to_string())))),
+        ])
+    }
+
+    #[test]
+    fn labels_struct_fields_and_enum_variants() {
+        let decoded = DecodedValue::from_token(&my_struct_property(), my_struct_token()).unwrap();
+
+        let expected = DecodedValue::Struct {
+            name: "MyStruct".to_string(),
+            fields: vec![
+                ("id".to_string(), DecodedValue::Number(1)),
+                ("flag".to_string(), DecodedValue::Bool(true)),
+                (
+                    "kind".to_string(),
+                    DecodedValue::Enum {
+                        name: "Kind".to_string(),
+                        variant: "Tagged".to_string(),
+                        value: Box::new(DecodedValue::String("abc".to_string())),
+                    },
+                ),
+            ],
+        };
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn labels_arrays_of_custom_types() {
+        let array = property("", "[struct MyStruct; 2]", Some(vec![my_struct_property()]));
+        let token = Token::Array(vec![my_struct_token(), my_struct_token()]);
+
+        let decoded = DecodedValue::from_token(&array, token).unwrap();
+
+        assert_eq!(
+            decoded.to_string(),
+            r#"[MyStruct { id: 1, flag: true, kind: Tagged("abc") }, MyStruct { id: 1, flag: true, kind: Tagged("abc") }]"#
+        );
+    }
+
+    #[test]
+    fn renders_as_pretty_text() {
+        let decoded = DecodedValue::from_token(&my_struct_property(), my_struct_token()).unwrap();
+
+        let expected = r#"MyStruct {
+    id: 1,
+    flag: true,
+    kind: Tagged("abc"),
+}"#;
+        assert_eq!(format!("{:#}", decoded), expected);
+    }
+
+    #[test]
+    fn renders_as_json() {
+        let decoded = DecodedValue::from_token(&my_struct_property(), my_struct_token()).unwrap();
+
+        // Fields keep their ABI order, which isn't the alphabetical one.
+        assert_eq!(
+            decoded.to_json(),
+            r#"{"id":1,"flag":true,"kind":{"Tagged":"abc"}}"#
+        );
+    }
+
+    #[test]
+    fn unit_variants_are_rendered_without_parentheses() {
+        let token = Token::Struct(vec![
+            Token::U64(1),
+            Token::Bool(false),
+            Token::Enum(Box::new((0, Token::Unit))),
+        ]);
+
+        let decoded = DecodedValue::from_token(&my_struct_property(), token).unwrap();
+
+        assert_eq!(
+            decoded.to_string(),
+            "MyStruct { id: 1, flag: false, kind: Empty }"
+        );
+    }
+
+    #[test]
+    fn out_of_range_discriminant_is_an_error() {
+        let token = Token::Struct(vec![
+            Token::U64(1),
+            Token::Bool(false),
+            Token::Enum(Box::new((5, Token::Unit))),
+        ]);
+
+        let err = DecodedValue::from_token(&my_struct_property(), token)
+            .expect_err("Should have failed on the discriminant");
+
+        assert_eq!(
+            err.to_string(),
+            "Invalid data: discriminant 5 is out of range for an enum with 2 variants"
+        );
+    }
+}
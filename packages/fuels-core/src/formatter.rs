@@ -0,0 +1,51 @@
+//! This module implements in-process formatting of generated code, so that
+//! the output doesn't depend on a locally installed copy of `rustfmt`.
+
+use crate::errors::Error;
+use proc_macro2::TokenStream;
+
+/// Format the given tokens as a Rust source file. The output only depends on
+/// the tokens, i.e. formatting the same tokens always yields the same bytes.
+pub fn format(tokens: &TokenStream) -> Result<String, Error> {
+    let file: syn::File = syn::parse2(tokens.clone()).map_err(|e| {
+        Error::InvalidData(format!("generated code is not a valid Rust file: {}", e))
+    })?;
+
+    Ok(prettyplease::unparse(&file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::quote;
+
+    #[test]
+    fn formats_tokens_as_a_source_file() {
+        let tokens = quote! {
+            pub struct MyStruct { pub foo: u64, pub bar: bool }
+            impl MyStruct { pub fn foo(&self) -> u64 { self.foo } }
+        };
+
+        let expected = "\
+pub struct MyStruct {
+    pub foo: u64,
+    pub bar: bool,
+}
+impl MyStruct {
+    pub fn foo(&self) -> u64 {
+        self.foo
+    }
+}
+";
+        assert_eq!(format(&tokens).unwrap(), expected);
+    }
+
+    #[test]
+    fn refuses_invalid_code() {
+        let tokens = quote! { pub struct };
+
+        let err = format(&tokens).expect_err("Should have failed to parse the tokens");
+
+        assert!(matches!(err, Error::InvalidData(_)));
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{Bits256, InvalidOutputType, ParamType, Parameterize, Token, Tokenizable};
+use fuel_tx::{Address, ContractId};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The Rust-side representation of Sway's `Identity`, i.e. either an
+/// `Address` or a `ContractId`. This is typically what a contract uses to
+/// refer to the sender of a call, or to the recipient of a transfer.
+///
+/// It serializes like the enums generated by the abigen, with the id as a
+/// `0x`-prefixed hex string, e.g. `{"Address":"0x01..."}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Identity {
+    Address(Address),
+    ContractId(ContractId),
+}
+
+impl From<Address> for Identity {
+    fn from(address: Address) -> Self {
+        Identity::Address(address)
+    }
+}
+
+impl From<ContractId> for Identity {
+    fn from(contract_id: ContractId) -> Self {
+        Identity::ContractId(contract_id)
+    }
+}
+
+impl Parameterize for Identity {
+    fn param_types() -> Vec<ParamType> {
+        vec![
+            ParamType::Struct(Address::param_types()),
+            ParamType::Struct(ContractId::param_types()),
+        ]
+    }
+
+    fn new_from_tokens(tokens: &[Token]) -> Result<Self, InvalidOutputType> {
+        match tokens.first() {
+            Some(token) => Self::from_token(token.clone()),
+            None => Err(InvalidOutputType(
+                "Expected an `Enum` token in `Identity::new_from_tokens`, got no tokens"
+                    .to_string(),
+            )),
+        }
+    }
+}
+
+impl Tokenizable for Identity {
+    fn from_token(token: Token) -> Result<Self, InvalidOutputType> {
+        match token {
+            Token::Enum(selector) => match *selector {
+                (0, token) => Ok(Identity::Address(Address::from_token(token)?)),
+                (1, token) => Ok(Identity::ContractId(ContractId::from_token(token)?)),
+                (discriminant, _) => Err(InvalidOutputType(format!(
+                    "Discriminant {} doesn't match any variant of `Identity`",
+                    discriminant
+                ))),
+            },
+            other => Err(InvalidOutputType(format!(
+                "Expected `Identity`, got {:?}",
+                other
+            ))),
+        }
+    }
+
+    fn into_token(self) -> Token {
+        let selector = match self {
+            Identity::Address(address) => (0, address.into_token()),
+            Identity::ContractId(contract_id) => (1, contract_id.into_token()),
+        };
+        Token::Enum(Box::new(selector))
+    }
+}
+
+// The serialized form of `Identity`, whose ids are serialized as `Bits256`s.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Identity")]
+enum IdentityRepr {
+    Address(Bits256),
+    ContractId(Bits256),
+}
+
+impl Serialize for Identity {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match *self {
+            Identity::Address(address) => IdentityRepr::Address(address.into()),
+            Identity::ContractId(contract_id) => IdentityRepr::ContractId(contract_id.into()),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Identity {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match IdentityRepr::deserialize(deserializer)? {
+            IdentityRepr::Address(bits) => Identity::Address(bits.into()),
+            IdentityRepr::ContractId(bits) => Identity::ContractId(bits.into()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_be_tokenized_and_detokenized() {
+        let contract_id = ContractId::from([1u8; 32]);
+        let identity = Identity::from(contract_id);
+
+        let token = identity.into_token();
+        assert_eq!(
+            token,
+            Token::Enum(Box::new((1, Token::Struct(vec![Token::B256([1u8; 32])]))))
+        );
+        assert_eq!(Identity::from_token(token).unwrap(), identity);
+
+        let err = Identity::from_token(Token::Enum(Box::new((2, Token::Unit))))
+            .expect_err("Should have failed on the discriminant");
+        assert_eq!(
+            err.0,
+            "Discriminant 2 doesn't match any variant of `Identity`"
+        );
+    }
+}
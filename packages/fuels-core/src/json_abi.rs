@@ -1,7 +1,6 @@
-use crate::Token;
 use crate::{abi_decoder::ABIDecoder, abi_encoder::ABIEncoder, errors::Error, ParamType};
+use crate::{sized_ascii_string::check_ascii, Bits256, DecodedValue, Token, Tokenizable};
 use fuels_types::{JsonABI, Property};
-use hex::FromHex;
 use itertools::Itertools;
 use serde_json;
 use std::str;
@@ -210,20 +209,20 @@ impl ABIParser {
             ParamType::U64 => Ok(Token::U64(trimmed_value.parse::<u64>()?)),
             ParamType::Bool => Ok(Token::Bool(trimmed_value.parse::<bool>()?)),
             ParamType::Byte => Ok(Token::Byte(trimmed_value.parse::<u8>()?)),
-            ParamType::B256 => {
-                const B256_HEX_ENC_LENGTH: usize = 64;
-                if trimmed_value.len() != B256_HEX_ENC_LENGTH {
+            ParamType::B256 => Ok(Bits256::from_hex_str(trimmed_value)?.into_token()),
+            ParamType::Array(t, _) => Ok(self.tokenize_array(trimmed_value, &*t)?),
+            ParamType::String(length) => {
+                check_ascii(trimmed_value)?;
+                if trimmed_value.len() != *length {
                     return Err(Error::InvalidData(format!(
-                        "the hex encoding of the b256 must have {} characters",
-                        B256_HEX_ENC_LENGTH
+                        "expected a string of length {}, got `{}` of length {}",
+                        length,
+                        trimmed_value,
+                        trimmed_value.len()
                     )));
                 }
-                let v = Vec::from_hex(trimmed_value)?;
-                let s: [u8; 32] = v.as_slice().try_into().unwrap();
-                Ok(Token::B256(s))
+                Ok(Token::String(trimmed_value.to_string()))
             }
-            ParamType::Array(t, _) => Ok(self.tokenize_array(trimmed_value, &*t)?),
-            ParamType::String(_) => Ok(Token::String(trimmed_value.to_string())),
             ParamType::Struct(struct_params) => {
                 Ok(self.tokenize_struct(trimmed_value, struct_params)?)
             }
@@ -568,6 +567,28 @@ impl ABIParser {
         }
     }
 
+    /// Similar to `decode`, but the decoded tokens are labelled with the
+    /// field, variant and type names found in the ABI. See [`DecodedValue`].
+    pub fn decode_labelled(
+        &self,
+        abi: &str,
+        fn_name: &str,
+        value: &[u8],
+    ) -> Result<Vec<DecodedValue>, Error> {
+        let parsed_abi: JsonABI = serde_json::from_str(abi)?;
+
+        let entry = parsed_abi
+            .iter()
+            .find(|e| e.name == fn_name)
+            .ok_or_else(|| {
+                Error::InvalidName(format!("couldn't find function name: {}", fn_name))
+            })?;
+
+        let tokens = self.decode(abi, fn_name, value)?;
+
+        DecodedValue::from_tokens(&entry.outputs, tokens)
+    }
+
     /// Similar to decode, but it decodes only an array types and the encoded data
     /// without having to reference to a JSON specification of the ABI.
     pub fn decode_params(&self, params: &[ParamType], data: &[u8]) -> Result<Vec<Token>, Error> {
@@ -610,7 +631,7 @@ impl ABIParser {
         let mut result: String = format!("{}(", fn_selector);
 
         for (idx, param) in params.iter().enumerate() {
-            result.push_str(&self.build_fn_selector_params(param));
+            result.push_str(&self.build_fn_selector_params(param)?);
             if idx + 1 < params.len() {
                 result.push(',');
             }
@@ -621,7 +642,7 @@ impl ABIParser {
         Ok(result)
     }
 
-    fn build_fn_selector_params(&self, param: &Property) -> String {
+    fn build_fn_selector_params(&self, param: &Property) -> Result<String, Error> {
         let mut result: String = String::new();
 
         if param.is_custom_type() {
@@ -639,38 +660,40 @@ impl ABIParser {
             } else if param.has_custom_type_in_tuple() {
                 result.push('(');
             } else {
-                panic!("unexpected custom type");
+                return Err(Error::InvalidType(unsupported_type_message(
+                    &param.type_field,
+                )));
             }
 
-            for (idx, component) in param
-                .components
-                .as_ref()
-                .expect("No components found")
-                .iter()
-                .enumerate()
-            {
-                result.push_str(&self.build_fn_selector_params(component));
+            let components = param.components.as_ref().ok_or_else(|| {
+                Error::MissingData(format!("`{}` has no `components`", param.type_field))
+            })?;
+            for (idx, component) in components.iter().enumerate() {
+                result.push_str(&self.build_fn_selector_params(component)?);
 
-                if idx + 1 < param.components.as_ref().unwrap().len() {
+                if idx + 1 < components.len() {
                     result.push(',');
                 }
             }
 
             if result.starts_with("a[") {
-                let array_type_field = param.type_field.clone();
-
                 // Type field, in this case, looks like
                 // "[struct Person; 2]" and we want to extract the
                 // length, which in this example is 2.
-                // First, get the last part after `;`: `"<length>]"`.
-                let mut array_length = array_type_field.split(';').collect::<Vec<&str>>()[1]
-                    .trim()
-                    .to_string();
-
-                array_length.pop(); // Remove the trailing "]"
+                let array_length = param
+                    .type_field
+                    .split(';')
+                    .nth(1)
+                    .and_then(|length| length.trim().strip_suffix(']'))
+                    .ok_or_else(|| {
+                        Error::InvalidType(format!(
+                            "Expected parameter type `[T; n]`, found `{}`",
+                            param.type_field
+                        ))
+                    })?;
 
                 // Make sure the length is a valid number.
-                let array_length = array_length.parse::<usize>().expect("Invalid array length");
+                let array_length = array_length.parse::<usize>()?;
 
                 result.push(';');
                 result.push_str(array_length.to_string().as_str());
@@ -687,7 +710,7 @@ impl ABIParser {
                 .collect();
             result.push_str(&param_str_no_whitespace);
         }
-        result
+        Ok(result)
     }
 }
 
@@ -720,11 +743,10 @@ pub fn parse_param(param: &Property) -> Result<ParamType, Error> {
 pub fn parse_tuple_param(param: &Property) -> Result<ParamType, Error> {
     let mut params: Vec<ParamType> = Vec::new();
 
-    for tuple_component in param
-        .components
-        .as_ref()
-        .expect("tuples should have components")
-    {
+    let components = param.components.as_ref().ok_or_else(|| {
+        Error::MissingData(format!("tuple `{}` has no `components`", param.type_field))
+    })?;
+    for tuple_component in components {
         params.push(parse_param(tuple_component)?);
     }
 
@@ -759,14 +781,14 @@ pub fn parse_array_param(param: &Property) -> Result<ParamType, Error> {
 
     let param_type = match ParamType::from_str(&type_field) {
         Ok(param_type) => param_type,
-        Err(_) => parse_custom_type_param(
-            param
+        Err(_) => {
+            let element = param
                 .components
                 .as_ref()
-                .expect("array should have components")
-                .first()
-                .expect("components in array should have at least one component"),
-        )?,
+                .and_then(|components| components.first())
+                .ok_or_else(|| Error::InvalidType(unsupported_type_message(type_field.trim())))?;
+            parse_custom_type_param(element)?
+        }
     };
 
     // Grab size the `n` in "[T; n]"
@@ -775,24 +797,72 @@ pub fn parse_array_param(param: &Property) -> Result<ParamType, Error> {
 }
 
 pub fn parse_custom_type_param(param: &Property) -> Result<ParamType, Error> {
-    let mut params: Vec<ParamType> = vec![];
-    match &param.components {
-        Some(c) => {
-            for component in c {
-                params.push(parse_param(component)?)
-            }
-            if param.is_struct_type() {
-                return Ok(ParamType::Struct(params));
-            }
-            if param.is_enum_type() {
-                return Ok(ParamType::Enum(params));
-            }
-            Err(Error::InvalidType(param.type_field.clone()))
+    if !param.is_struct_type() && !param.is_enum_type() {
+        return Err(Error::InvalidType(unsupported_type_message(
+            &param.type_field,
+        )));
+    }
+
+    let components = param
+        .components
+        .as_ref()
+        .ok_or_else(|| Error::MissingData(format!("`{}` has no `components`", param.type_field)))?;
+    let params = components
+        .iter()
+        .map(parse_param)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if param.is_struct_type() {
+        Ok(ParamType::Struct(params))
+    } else {
+        Ok(ParamType::Enum(params))
+    }
+}
+
+const SUPPORTED_TYPES: [&str; 8] = ["()", "bool", "u8", "u16", "u32", "u64", "byte", "b256"];
+
+// Describes an unsupported `type_field`, suggesting the supported type it was
+// most likely meant to be, if any.
+fn unsupported_type_message(type_field: &str) -> String {
+    let closest = SUPPORTED_TYPES
+        .iter()
+        .map(|supported| {
+            (
+                edit_distance(&type_field.to_lowercase(), supported),
+                supported,
+            )
+        })
+        .min();
+
+    match closest {
+        Some((distance, supported)) if distance <= 1 => format!(
+            "unsupported type `{}`, did you mean `{}`?",
+            type_field, supported
+        ),
+        _ => format!(
+            "unsupported type `{}`, expected one of {}, `str[N]`, `[T; N]`, a tuple, \
+            `struct <name>` or `enum <name>`",
+            type_field,
+            SUPPORTED_TYPES.map(|t| format!("`{}`", t)).join(", ")
+        ),
+    }
+}
+
+// The Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
         }
-        None => Err(Error::MissingData(
-            "cannot parse custom type with no components".into(),
-        )),
+        previous = current;
     }
+
+    previous[b.len()]
 }
 
 #[cfg(test)]
@@ -1021,6 +1091,47 @@ mod tests {
         assert_eq!(decoded_return, expected_return);
     }
 
+    #[test]
+    fn struct_output_decode_labelled() {
+        let json_abi = r#"
+        [
+            {
+                "type":"contract",
+                "inputs":[],
+                "name":"my_func",
+                "outputs":[
+                    {
+                        "name":"",
+                        "type":"struct MyStruct",
+                        "components": [
+                            {
+                                "name": "id",
+                                "type": "u64"
+                            },
+                            {
+                                "name": "flag",
+                                "type": "bool"
+                            }
+                        ]
+                    }
+                ]
+            }
+        ]
+        "#;
+
+        let return_value = hex::decode("00000000000000010000000000000001").unwrap();
+
+        let decoded_return = ABIParser::new()
+            .decode_labelled(json_abi, "my_func", &return_value)
+            .unwrap();
+
+        assert_eq!(decoded_return.len(), 1);
+        assert_eq!(
+            decoded_return[0].to_string(),
+            "MyStruct { id: 1, flag: true }"
+        );
+    }
+
     #[test]
     fn array_encode_and_decode() {
         let json_abi = r#"
@@ -1860,6 +1971,31 @@ mod tests {
         assert_eq!(encoded, expected_encode);
     }
 
+    #[test]
+    fn tokenize_string_of_wrong_length_expected_error() {
+        let abi = ABIParser::new();
+
+        let error = abi
+            .tokenize(&ParamType::String(10), "fuel".to_string())
+            .expect_err("Should have failed since the string is too short");
+
+        let expected = "Invalid data: expected a string of length 10, got `fuel` of length 4";
+        assert_eq!(error.to_string(), expected);
+    }
+
+    #[test]
+    fn tokenize_non_ascii_string_expected_error() {
+        let abi = ABIParser::new();
+
+        let error = abi
+            .tokenize(&ParamType::String(4), "fué".to_string())
+            .expect_err("Should have failed since the string isn't ASCII");
+
+        let expected = "Invalid data: SizedAsciiString must be constructed from a string \
+        containing only ascii encodable characters. Got: fué";
+        assert_eq!(error.to_string(), expected);
+    }
+
     #[test]
     fn tokenize_uint_types_expected_error() {
         let abi = ABIParser::new();
@@ -1907,6 +2043,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tokenize_b256_accepts_0x_prefix() {
+        let abi = ABIParser::new();
+
+        let hex = "d57a9c46dfcc7f18207013e65b44e4cb4e2c2298f4ac457ba8f82743f31e90b5";
+        let with_prefix = abi
+            .tokenize(&ParamType::B256, format!("0x{}", hex))
+            .unwrap();
+        let without_prefix = abi.tokenize(&ParamType::B256, hex.to_string()).unwrap();
+
+        assert_eq!(with_prefix, without_prefix);
+    }
+
     #[test]
     fn tokenize_b256_invalid_character_expected_error() {
         let abi = ABIParser::new();
@@ -4,16 +4,27 @@ use strum_macros::EnumString;
 
 pub mod abi_decoder;
 pub mod abi_encoder;
+pub mod bits256;
 pub mod code_gen;
 pub mod constants;
+pub mod decoded_value;
 pub mod errors;
+pub mod formatter;
+pub mod identity;
 pub mod json_abi;
 pub mod parameters;
+#[deprecated(note = "bindings are now formatted in-process, use `formatter::format` instead")]
 pub mod rustfmt;
+pub mod sized_ascii_string;
 pub mod source;
 pub mod types;
 pub mod utils;
 
+pub use bits256::Bits256;
+pub use decoded_value::DecodedValue;
+pub use identity::Identity;
+pub use sized_ascii_string::SizedAsciiString;
+
 pub mod tx {
     #[doc(no_inline)]
     pub use fuel_tx::*;
@@ -21,7 +32,6 @@ pub mod tx {
 
 pub type ByteArray = [u8; 8];
 pub type Selector = ByteArray;
-pub type Bits256 = [u8; 32];
 pub type EnumSelector = (u8, Token);
 
 #[derive(Debug, Clone, EnumString, PartialEq, Eq)]
@@ -127,7 +137,7 @@ pub enum Token {
     U64(u64),
     Bool(bool),
     Byte(u8),
-    B256(Bits256),
+    B256([u8; 32]),
     Array(Vec<Token>),
     String(String),
     Struct(Vec<Token>),
@@ -199,41 +209,89 @@ impl Tokenizable for String {
     }
 }
 
-impl Tokenizable for Bits256 {
+impl<T: Tokenizable> Tokenizable for Vec<T> {
+    fn from_token(token: Token) -> Result<Self, InvalidOutputType> {
+        match token {
+            Token::Array(data) => data.into_iter().map(T::from_token).collect(),
+            other => Err(InvalidOutputType(format!("Expected `T`, got {:?}", other))),
+        }
+    }
+    fn into_token(self) -> Token {
+        let mut v: Vec<Token> = Vec::new();
+        for t in self {
+            let tok = T::into_token(t);
+            v.push(tok);
+        }
+        Token::Array(v)
+    }
+}
+
+/// Sway's `Option<T>`, an enum whose `None` variant comes first.
+impl<T: Tokenizable> Tokenizable for Option<T> {
     fn from_token(token: Token) -> Result<Self, InvalidOutputType> {
         match token {
-            Token::B256(data) => Ok(data),
+            Token::Enum(selector) => match *selector {
+                (0, _) => Ok(None),
+                (1, token) => Ok(Some(T::from_token(token)?)),
+                (discriminant, _) => Err(InvalidOutputType(format!(
+                    "Discriminant {} doesn't match any variant of `Option`",
+                    discriminant
+                ))),
+            },
             other => Err(InvalidOutputType(format!(
-                "Expected `String`, got {:?}",
+                "Expected `Option`, got {:?}",
                 other
             ))),
         }
     }
     fn into_token(self) -> Token {
-        Token::B256(self)
+        let selector = match self {
+            None => (0, Token::Unit),
+            Some(value) => (1, value.into_token()),
+        };
+        Token::Enum(Box::new(selector))
     }
 }
 
-impl<T: Tokenizable> Tokenizable for Vec<T> {
+impl<T: Tokenizable, const SIZE: usize> Tokenizable for [T; SIZE] {
     fn from_token(token: Token) -> Result<Self, InvalidOutputType> {
+        let gen_error = |reason| {
+            InvalidOutputType(format!(
+                "While constructing an array of size {}: {}",
+                SIZE, reason
+            ))
+        };
+
         match token {
-            Token::Array(data) => {
-                let mut v: Vec<T> = Vec::new();
-                for tok in data {
-                    v.push(T::from_token(tok.clone()).unwrap());
+            Token::Array(elements) => {
+                let len = elements.len();
+                if len != SIZE {
+                    return Err(gen_error(format!(
+                        "`Token::Array` has wrong number of elements: {}",
+                        len
+                    )));
+                }
+
+                let detokenized = elements
+                    .into_iter()
+                    .map(Tokenizable::from_token)
+                    .collect::<Result<Vec<T>, _>>()
+                    .map_err(|err| gen_error(err.0))?;
+
+                match detokenized.try_into() {
+                    Ok(array) => Ok(array),
+                    Err(_) => unreachable!("the number of elements was checked above"),
                 }
-                Ok(v)
             }
-            other => Err(InvalidOutputType(format!("Expected `T`, got {:?}", other))),
+            other => Err(gen_error(format!(
+                "Expected `Token::Array`, got {:?}",
+                other
+            ))),
         }
     }
+
     fn into_token(self) -> Token {
-        let mut v: Vec<Token> = Vec::new();
-        for t in self {
-            let tok = T::into_token(t);
-            v.push(tok);
-        }
-        Token::Array(v)
+        Token::Array(self.map(Tokenizable::into_token).to_vec())
     }
 }
 
@@ -307,10 +365,15 @@ macro_rules! impl_tuples {
         {
             fn from_token(token: Token) -> Result<Self, InvalidOutputType> {
                 match token {
-                    Token::Tuple(mut tokens) => {
-                        let mut it = tokens.drain(..);
+                    Token::Tuple(tokens) => {
+                        let len = tokens.len();
+                        let mut it = tokens.into_iter();
                         Ok(($(
-                          $ty::from_token(it.next().expect("All elements are in vector."))?,
+                          $ty::from_token(it.next().ok_or_else(|| InvalidOutputType(format!(
+                              "Expected a tuple of {} elements, got {}",
+                              $num,
+                              len,
+                          )))?)?,
                         )+))
                     },
                     other => Err(InvalidOutputType(format!(
@@ -377,61 +440,38 @@ impl<T: Tokenizable> Detokenize for T {
     }
 }
 
-impl Detokenize for fuel_tx::ContractId {
-    fn from_tokens(t: Vec<Token>) -> std::result::Result<Self, InvalidOutputType>
-    where
-        Self: Sized,
-    {
-        if let Token::Struct(tokens) = &t[0] {
-            if let Token::B256(id) = &tokens[0] {
-                Ok(fuel_tx::ContractId::from(*id))
-            } else {
-                Err(InvalidOutputType(format!(
-                    "Expected `b256`, got {:?}",
-                    tokens[0]
-                )))
-            }
-        } else {
-            Err(InvalidOutputType(format!(
-                "Expected `ContractId`, got {:?}",
-                t
-            )))
-        }
-    }
-}
-
-impl Detokenize for fuel_tx::Address {
-    fn from_tokens(t: Vec<Token>) -> std::result::Result<Self, InvalidOutputType>
-    where
-        Self: Sized,
-    {
-        if let Token::Struct(tokens) = &t[0] {
-            if let Token::B256(id) = &tokens[0] {
-                Ok(fuel_tx::Address::from(*id))
-            } else {
-                Err(InvalidOutputType(format!(
-                    "Expected `b256`, got {:?}",
-                    tokens[0]
-                )))
-            }
-        } else {
-            Err(InvalidOutputType(format!(
-                "Expected `Address`, got {:?}",
-                t
-            )))
-        }
-    }
-}
-
 /// This trait is similar to `Detokenize`, but it is used inside the abigen
 /// generated code in order to get the parameter types (`ParamType`) and
 /// instantiate a new struct/enum from tokens. This is used in the generated
 /// code in `custom_types_gen.rs`, with the exception of the Sway-native types
-/// `Address`, `ContractId`, and `AssetId`, that are implemented right here,
-/// without code generation.
+/// `Address`, `ContractId`, `AssetId` and [`Identity`], that are implemented
+/// right here, without code generation.
 pub trait Parameterize {
     fn param_types() -> Vec<ParamType>;
-    fn new_from_tokens(tokens: &[Token]) -> Self;
+    fn new_from_tokens(tokens: &[Token]) -> Result<Self, InvalidOutputType>
+    where
+        Self: Sized;
+}
+
+/// Extracts the `b256` of the Sway-native struct `type_name`, e.g. `Address`,
+/// from the tokens it was decoded into.
+fn b256_from_native_struct(
+    tokens: &[Token],
+    type_name: &str,
+) -> Result<[u8; 32], InvalidOutputType> {
+    match tokens.first() {
+        Some(Token::Struct(inner_tokens)) => match inner_tokens.first() {
+            Some(Token::B256(id)) => Ok(*id),
+            other => Err(InvalidOutputType(format!(
+                "Expected a `b256` inside the {} struct, got {:?}",
+                type_name, other
+            ))),
+        },
+        other => Err(InvalidOutputType(format!(
+            "Expected a struct containing `b256`, got {:?}",
+            other
+        ))),
+    }
 }
 
 impl Parameterize for fuel_tx::Address {
@@ -439,19 +479,8 @@ impl Parameterize for fuel_tx::Address {
         vec![ParamType::B256]
     }
 
-    fn new_from_tokens(tokens: &[Token]) -> Self {
-        if let Token::Struct(inner_tokens) = &tokens[0] {
-            if let Token::B256(id) = &inner_tokens[0] {
-                Self::from(*id)
-            } else {
-                panic!(
-                    "Expected a `b256` inside the Address struct, got {:?}",
-                    inner_tokens[0]
-                )
-            }
-        } else {
-            panic!("Expected a struct containing `b256`, got {:?}", tokens[0])
-        }
+    fn new_from_tokens(tokens: &[Token]) -> Result<Self, InvalidOutputType> {
+        b256_from_native_struct(tokens, "Address").map(Self::from)
     }
 }
 
@@ -460,19 +489,8 @@ impl Parameterize for fuel_tx::ContractId {
         vec![ParamType::B256]
     }
 
-    fn new_from_tokens(tokens: &[Token]) -> Self {
-        if let Token::Struct(inner_tokens) = &tokens[0] {
-            if let Token::B256(id) = &inner_tokens[0] {
-                Self::from(*id)
-            } else {
-                panic!(
-                    "Expected a `b256` inside the ContractId struct, got {:?}",
-                    inner_tokens[0]
-                )
-            }
-        } else {
-            panic!("Expected a struct containing `b256`, got {:?}", tokens[0])
-        }
+    fn new_from_tokens(tokens: &[Token]) -> Result<Self, InvalidOutputType> {
+        b256_from_native_struct(tokens, "ContractId").map(Self::from)
     }
 }
 
@@ -481,19 +499,36 @@ impl Parameterize for fuel_tx::AssetId {
         vec![ParamType::B256]
     }
 
-    fn new_from_tokens(tokens: &[Token]) -> Self {
-        if let Token::Struct(inner_tokens) = &tokens[0] {
-            if let Token::B256(id) = &inner_tokens[0] {
-                Self::from(*id)
-            } else {
-                panic!(
-                    "Expected a `b256` inside the AssetId struct, got {:?}",
-                    inner_tokens[0]
-                )
-            }
-        } else {
-            panic!("Expected a struct containing `b256`, got {:?}", tokens[0])
-        }
+    fn new_from_tokens(tokens: &[Token]) -> Result<Self, InvalidOutputType> {
+        b256_from_native_struct(tokens, "AssetId").map(Self::from)
+    }
+}
+
+// The Sway-native types are structs holding a single `b256`.
+impl Tokenizable for fuel_tx::Address {
+    fn from_token(token: Token) -> Result<Self, InvalidOutputType> {
+        Self::new_from_tokens(&[token])
+    }
+    fn into_token(self) -> Token {
+        Token::Struct(vec![Token::B256(*self)])
+    }
+}
+
+impl Tokenizable for fuel_tx::ContractId {
+    fn from_token(token: Token) -> Result<Self, InvalidOutputType> {
+        Self::new_from_tokens(&[token])
+    }
+    fn into_token(self) -> Token {
+        Token::Struct(vec![Token::B256(*self)])
+    }
+}
+
+impl Tokenizable for fuel_tx::AssetId {
+    fn from_token(token: Token) -> Result<Self, InvalidOutputType> {
+        Self::new_from_tokens(&[token])
+    }
+    fn into_token(self) -> Token {
+        Token::Struct(vec![Token::B256(*self)])
     }
 }
 
@@ -527,3 +562,106 @@ pub fn pad_string(s: &str) -> Vec<u8> {
 
     padded
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arrays_are_tokenized_element_wise() {
+        let token = [1u16, 2, 3].into_token();
+
+        assert_eq!(
+            token,
+            Token::Array(vec![Token::U16(1), Token::U16(2), Token::U16(3)])
+        );
+        assert_eq!(<[u16; 3]>::from_token(token).unwrap(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn array_from_token_checks_the_number_of_elements() {
+        let token = Token::Array(vec![Token::U8(1), Token::U8(2)]);
+
+        let err = <[u8; 3]>::from_token(token).expect_err("Should have failed on the length");
+
+        assert_eq!(
+            err.0,
+            "While constructing an array of size 3: `Token::Array` has wrong number of elements: 2"
+        );
+    }
+
+    #[test]
+    fn vec_from_token_fails_on_an_invalid_element() {
+        let token = Token::Array(vec![Token::U8(1), Token::Bool(true)]);
+
+        let err = Vec::<u8>::from_token(token).expect_err("Should have failed on the `Bool`");
+
+        assert_eq!(err.0, "Expected `u8`, got Bool(true)");
+    }
+
+    #[test]
+    fn native_types_fail_on_malformed_tokens() {
+        let id = [1u8; 32];
+        let tokens = [Token::Struct(vec![Token::B256(id)])];
+        assert_eq!(
+            fuel_tx::ContractId::new_from_tokens(&tokens).unwrap(),
+            fuel_tx::ContractId::from(id)
+        );
+
+        let err =
+            fuel_tx::Address::new_from_tokens(&[]).expect_err("Should have failed on no tokens");
+        assert_eq!(err.0, "Expected a struct containing `b256`, got None");
+
+        let err = fuel_tx::AssetId::new_from_tokens(&[Token::Struct(vec![Token::U64(1)])])
+            .expect_err("Should have failed on the `U64`");
+        assert_eq!(
+            err.0,
+            "Expected a `b256` inside the AssetId struct, got Some(U64(1))"
+        );
+    }
+
+    #[test]
+    fn options_are_tokenized_as_enums() {
+        assert_eq!(
+            Some(3u64).into_token(),
+            Token::Enum(Box::new((1, Token::U64(3))))
+        );
+        assert_eq!(
+            Option::<u64>::None.into_token(),
+            Token::Enum(Box::new((0, Token::Unit)))
+        );
+
+        let token = Token::Enum(Box::new((1, Token::U64(3))));
+        assert_eq!(Option::<u64>::from_token(token).unwrap(), Some(3));
+        let token = Token::Enum(Box::new((0, Token::Unit)));
+        assert_eq!(Option::<u64>::from_token(token).unwrap(), None);
+    }
+
+    #[test]
+    fn native_types_can_be_nested_in_vecs_and_tuples() {
+        let address = fuel_tx::Address::from([2u8; 32]);
+        let asset_id = fuel_tx::AssetId::from([3u8; 32]);
+
+        let token = (vec![address], asset_id).into_token();
+        assert_eq!(
+            token,
+            Token::Tuple(vec![
+                Token::Array(vec![Token::Struct(vec![Token::B256([2u8; 32])])]),
+                Token::Struct(vec![Token::B256([3u8; 32])]),
+            ])
+        );
+
+        let (addresses, recreated_asset_id) =
+            <(Vec<fuel_tx::Address>, fuel_tx::AssetId)>::from_token(token).unwrap();
+        assert_eq!(addresses, vec![address]);
+        assert_eq!(recreated_asset_id, asset_id);
+    }
+
+    #[test]
+    fn short_tuples_are_an_error() {
+        let token = Token::Tuple(vec![Token::U64(1)]);
+
+        let err = <(u64, bool)>::from_token(token).expect_err("Should have failed on the length");
+        assert_eq!(err.0, "Expected a tuple of 2 elements, got 1");
+    }
+}
@@ -3,7 +3,7 @@ use crate::constants::{
 };
 use fuel_tx::AssetId;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct TxParameters {
     pub gas_price: u64,
     pub gas_limit: u64,
@@ -1,10 +1,14 @@
 //! This module implements basic `rustfmt` code formatting.
+//!
+//! Deprecated: it needs a locally installed copy of `rustfmt`, the bindings
+//! are now formatted with [`crate::formatter`].
 
 use anyhow::{anyhow, Result};
 use std::io::Write;
 use std::process::{Command, Stdio};
 
 /// Format the raw input source string and return formatted output.
+#[deprecated(note = "use fuels_core::formatter::format")]
 pub fn format<S>(source: S) -> Result<String>
 where
     S: AsRef<str>,
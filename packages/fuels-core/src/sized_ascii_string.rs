@@ -0,0 +1,256 @@
+use crate::errors::Error;
+use crate::{InvalidOutputType, Token, Tokenizable};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+/// A string of exactly `LEN` ASCII characters. This is the Rust-side
+/// representation of Sway's `str[LEN]`, and is what the abigen generates for
+/// `str[LEN]` fields and arguments. Both the length and the ASCII-ness of the
+/// data are checked on construction, so an instance can always be encoded
+/// into valid call data.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SizedAsciiString<const LEN: usize> {
+    data: String,
+}
+
+impl<const LEN: usize> SizedAsciiString<LEN> {
+    pub fn new(data: String) -> Result<Self, Error> {
+        check_ascii(&data)?;
+        if data.len() != LEN {
+            return Err(Error::InvalidData(format!(
+                "SizedAsciiString<{}> can only be constructed from a String of length {}. Got: {}",
+                LEN, LEN, data
+            )));
+        }
+        Ok(Self { data })
+    }
+
+    /// Pads `data` with trailing whitespace until it is exactly `LEN` characters
+    /// long. Fails if `data` is already longer than `LEN` or isn't ASCII.
+    pub fn new_with_right_whitespace_padding(data: String) -> Result<Self, Error> {
+        if data.len() > LEN {
+            return Err(Error::InvalidData(format!(
+                "SizedAsciiString<{}> cannot be constructed from a string of size {}",
+                LEN,
+                data.len()
+            )));
+        }
+
+        Self::new(format!("{:width$}", data, width = LEN))
+    }
+
+    /// Returns the underlying data without any trailing whitespace, i.e. the
+    /// inverse of `new_with_right_whitespace_padding`.
+    pub fn to_trimmed_str(&self) -> &str {
+        self.data.trim_end()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.data
+    }
+
+    pub fn len(&self) -> usize {
+        LEN
+    }
+
+    pub fn is_empty(&self) -> bool {
+        LEN == 0
+    }
+}
+
+/// Checks that `data` can be the content of a `str[N]`, i.e. that it is ASCII.
+pub(crate) fn check_ascii(data: &str) -> Result<(), Error> {
+    if !data.is_ascii() {
+        return Err(Error::InvalidData(format!(
+            "SizedAsciiString must be constructed from a string containing only ascii encodable characters. Got: {}",
+            data
+        )));
+    }
+    Ok(())
+}
+
+/// `LEN` spaces, i.e. an empty string once padded.
+impl<const LEN: usize> Default for SizedAsciiString<LEN> {
+    fn default() -> Self {
+        Self {
+            data: " ".repeat(LEN),
+        }
+    }
+}
+
+impl<const LEN: usize> TryFrom<&str> for SizedAsciiString<LEN> {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::new(value.to_owned())
+    }
+}
+
+impl<const LEN: usize> TryFrom<String> for SizedAsciiString<LEN> {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl<const LEN: usize> From<SizedAsciiString<LEN>> for String {
+    fn from(sized_ascii_str: SizedAsciiString<LEN>) -> Self {
+        sized_ascii_str.data
+    }
+}
+
+impl<const LEN: usize> FromStr for SizedAsciiString<LEN> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+impl<const LEN: usize> AsRef<str> for SizedAsciiString<LEN> {
+    fn as_ref(&self) -> &str {
+        &self.data
+    }
+}
+
+impl<const LEN: usize> Display for SizedAsciiString<LEN> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.data)
+    }
+}
+
+impl<const LEN: usize> Debug for SizedAsciiString<LEN> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.data)
+    }
+}
+
+impl<const LEN: usize> PartialEq<&str> for SizedAsciiString<LEN> {
+    fn eq(&self, other: &&str) -> bool {
+        self.data == *other
+    }
+}
+
+impl<const LEN: usize> PartialEq<SizedAsciiString<LEN>> for &str {
+    fn eq(&self, other: &SizedAsciiString<LEN>) -> bool {
+        *self == other.data
+    }
+}
+
+impl<const LEN: usize> Tokenizable for SizedAsciiString<LEN> {
+    fn from_token(token: Token) -> Result<Self, InvalidOutputType> {
+        match token {
+            Token::String(data) => Self::new(data).map_err(|e| InvalidOutputType(e.to_string())),
+            other => Err(InvalidOutputType(format!(
+                "Expected `String`, got {:?}",
+                other
+            ))),
+        }
+    }
+
+    fn into_token(self) -> Token {
+        Token::String(self.data)
+    }
+}
+
+impl<const LEN: usize> Serialize for SizedAsciiString<LEN> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.data)
+    }
+}
+
+impl<'de, const LEN: usize> Deserialize<'de> for SizedAsciiString<LEN> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = String::deserialize(deserializer)?;
+        Self::new(data).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_ascii_of_correct_length() {
+        let ascii_data = "abc".to_string();
+
+        SizedAsciiString::<3>::new(ascii_data)
+            .expect("Should have succeeded since we gave ascii data of correct length!");
+    }
+
+    #[test]
+    fn refuses_non_ascii() {
+        let ascii_data = "ab©".to_string();
+
+        let err = SizedAsciiString::<3>::new(ascii_data)
+            .expect_err("Should not have succeeded since we gave non ascii data");
+
+        let expected_reason =
+            "SizedAsciiString must be constructed from a string containing only ascii encodable characters. Got: ";
+        assert!(matches!(err, Error::InvalidData(reason) if reason.starts_with(expected_reason)));
+    }
+
+    #[test]
+    fn refuses_invalid_len() {
+        let ascii_data = "abcd".to_string();
+
+        let err = SizedAsciiString::<3>::new(ascii_data)
+            .expect_err("Should not have succeeded since we gave data of wrong length");
+
+        let expected_reason =
+            "SizedAsciiString<3> can only be constructed from a String of length 3. Got: abcd";
+        assert!(matches!(err, Error::InvalidData(reason) if reason.starts_with(expected_reason)));
+    }
+
+    #[test]
+    fn pads_and_trims_whitespace() {
+        let padded =
+            SizedAsciiString::<6>::new_with_right_whitespace_padding("abc".into()).unwrap();
+
+        assert_eq!(padded, "abc   ");
+        assert_eq!(padded.to_trimmed_str(), "abc");
+        assert_eq!(SizedAsciiString::<3>::default(), "   ");
+
+        let err = SizedAsciiString::<2>::new_with_right_whitespace_padding("abc".into())
+            .expect_err("Should not have succeeded since the data is longer than 2");
+        assert!(matches!(err, Error::InvalidData(_)));
+    }
+
+    #[test]
+    fn can_be_tokenized_and_detokenized() {
+        let sized_str = SizedAsciiString::<4>::try_from("fuel").unwrap();
+
+        let token = sized_str.clone().into_token();
+        assert_eq!(token, Token::String("fuel".to_string()));
+
+        let recreated = SizedAsciiString::<4>::from_token(token).unwrap();
+        assert_eq!(recreated, sized_str);
+
+        let wrong_len = SizedAsciiString::<3>::from_token(Token::String("fuel".to_string()));
+        assert!(wrong_len.is_err());
+    }
+
+    #[test]
+    fn can_be_parsed_and_displayed() {
+        let sized_str: SizedAsciiString<4> = "fuel".parse().unwrap();
+
+        assert_eq!(sized_str.to_string(), "fuel");
+        assert!("fue".parse::<SizedAsciiString<4>>().is_err());
+    }
+
+    #[test]
+    fn can_be_serialized_and_deserialized() {
+        let sized_str = SizedAsciiString::<4>::try_from("fuel").unwrap();
+
+        let serialized = serde_json::to_string(&sized_str).unwrap();
+        assert_eq!(serialized, "\"fuel\"");
+
+        let deserialized: SizedAsciiString<4> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, sized_str);
+
+        let too_long = serde_json::from_str::<SizedAsciiString<3>>(&serialized);
+        assert!(too_long.is_err());
+    }
+}
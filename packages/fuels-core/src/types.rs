@@ -1,6 +1,6 @@
 use crate::errors::Error;
 use anyhow::Result;
-use proc_macro2::TokenStream;
+use proc_macro2::{Literal, TokenStream};
 use quote::quote;
 
 use crate::ParamType;
@@ -15,11 +15,15 @@ pub fn expand_type(kind: &ParamType) -> Result<TokenStream, Error> {
         ParamType::U32 => Ok(quote! { u32 }),
         ParamType::U64 => Ok(quote! { u64 }),
         ParamType::Bool => Ok(quote! { bool }),
-        ParamType::B256 => Ok(quote! { [u8; 32] }),
-        ParamType::String(_) => Ok(quote! { String }),
-        ParamType::Array(t, _size) => {
+        ParamType::B256 => Ok(quote! { Bits256 }),
+        ParamType::String(size) => {
+            let size = Literal::usize_unsuffixed(*size);
+            Ok(quote! { SizedAsciiString<#size> })
+        }
+        ParamType::Array(t, size) => {
             let inner = expand_type(t)?;
-            Ok(quote! { ::std::vec::Vec<#inner> })
+            let size = Literal::usize_unsuffixed(*size);
+            Ok(quote! { [#inner; #size] })
         }
         ParamType::Struct(members) => {
             if members.is_empty() {
@@ -1,4 +1,5 @@
-use proc_macro2::{Ident, Span};
+use crate::errors::Error;
+use proc_macro2::{Ident, Span, TokenStream};
 use syn::Ident as SynIdent;
 
 /// Expands a identifier string into an token.
@@ -6,10 +7,29 @@ pub fn ident(name: &str) -> Ident {
     Ident::new(name, Span::call_site())
 }
 
-// Expands an identifier string into a token and appending `_` if the
-/// identifier is for a reserved keyword.
+/// Expands an identifier string into a token, escaping it if it is a
+/// reserved keyword: `type` becomes `r#type`.
 ///
-/// Parsing keywords like `self` can fail, in this case we add an underscore.
+/// `self`, `Self`, `super` and `crate` can't be raw identifiers, so an
+/// underscore is appended to them instead.
 pub fn safe_ident(name: &str) -> Ident {
-    syn::parse_str::<SynIdent>(name).unwrap_or_else(|_| ident(&format!("{}_", name)))
+    if syn::parse_str::<SynIdent>(name).is_ok() {
+        return ident(name);
+    }
+
+    match name {
+        "self" | "Self" | "super" | "crate" | "_" => ident(&format!("{}_", name)),
+        _ => Ident::new_raw(name, Span::call_site()),
+    }
+}
+
+/// Parses generated code, e.g. a type signature built from the ABI, into
+/// tokens.
+pub fn parse_tokens(code: &str) -> Result<TokenStream, Error> {
+    code.parse().map_err(|e| {
+        Error::InvalidType(format!(
+            "`{}` is not a valid Rust type or expression: {}",
+            code, e
+        ))
+    })
 }
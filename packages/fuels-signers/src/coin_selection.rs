@@ -0,0 +1,251 @@
+use fuel_gql_client::client::schema::coin::Coin;
+use rand::seq::SliceRandom;
+use std::cmp::Reverse;
+use std::fmt;
+
+/// A client-side strategy choosing the coins that pay for a transaction.
+///
+/// Without a selector, coins are chosen by the node. A selector is set per
+/// wallet with `Wallet::set_coin_selector`. For a single transaction, set it
+/// on a clone of the wallet, e.g. `wallet.clone().with_coin_selector(SmallestFirst)`.
+pub trait CoinSelector: fmt::Debug + Send + Sync {
+    /// Chooses, among `coins`, coins adding up at least to `amount`, and
+    /// returns their indices. `coins` are the spendable coins of the owner
+    /// for a single asset, in no particular order. Returns `None` if they
+    /// don't add up to `amount`.
+    fn select(&self, coins: &[Coin], amount: u64) -> Option<Vec<usize>>;
+}
+
+/// Spends the largest coins first, which uses as few coins as possible.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LargestFirst;
+
+/// Spends the smallest coins first, which consolidates small coins, at the
+/// cost of larger transactions.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SmallestFirst;
+
+/// Spends coins in a random order, so that the coins spent don't reveal the
+/// wallet's strategy or the rest of its coins.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomSelection;
+
+/// Looks for coins adding up exactly to the amount, up to `tolerance`, so
+/// that the transaction doesn't create change, with a branch and bound search
+/// of at most `max_tries` steps. Falls back to `LargestFirst` if there is no
+/// such set of coins.
+#[derive(Debug, Clone, Copy)]
+pub struct BranchAndBound {
+    /// How much more than the amount the coins may add up to, e.g. an amount
+    /// not worth creating a coin for.
+    pub tolerance: u64,
+    pub max_tries: usize,
+}
+
+impl Default for BranchAndBound {
+    fn default() -> Self {
+        Self {
+            tolerance: 0,
+            max_tries: 100_000,
+        }
+    }
+}
+
+impl CoinSelector for LargestFirst {
+    fn select(&self, coins: &[Coin], amount: u64) -> Option<Vec<usize>> {
+        let mut order: Vec<usize> = (0..coins.len()).collect();
+        order.sort_by_key(|&i| Reverse(coins[i].amount.0));
+        take_until_covered(coins, order, amount)
+    }
+}
+
+impl CoinSelector for SmallestFirst {
+    fn select(&self, coins: &[Coin], amount: u64) -> Option<Vec<usize>> {
+        let mut order: Vec<usize> = (0..coins.len()).collect();
+        order.sort_by_key(|&i| coins[i].amount.0);
+        take_until_covered(coins, order, amount)
+    }
+}
+
+impl CoinSelector for RandomSelection {
+    fn select(&self, coins: &[Coin], amount: u64) -> Option<Vec<usize>> {
+        let mut order: Vec<usize> = (0..coins.len()).collect();
+        order.shuffle(&mut rand::thread_rng());
+        take_until_covered(coins, order, amount)
+    }
+}
+
+impl CoinSelector for BranchAndBound {
+    fn select(&self, coins: &[Coin], amount: u64) -> Option<Vec<usize>> {
+        let mut order: Vec<usize> = (0..coins.len()).collect();
+        order.sort_by_key(|&i| Reverse(coins[i].amount.0));
+
+        let mut search = Search {
+            coins,
+            order: &order,
+            target: amount,
+            upper_bound: amount.saturating_add(self.tolerance),
+            tries_left: self.max_tries,
+            selected: vec![],
+        };
+        let available = order
+            .iter()
+            .map(|&i| coins[i].amount.0)
+            .fold(0, u64::saturating_add);
+        if search.explore(0, 0, available) {
+            Some(search.selected)
+        } else {
+            LargestFirst.select(coins, amount)
+        }
+    }
+}
+
+/// Takes coins in the given order until they add up to `amount`.
+fn take_until_covered(coins: &[Coin], order: Vec<usize>, amount: u64) -> Option<Vec<usize>> {
+    let mut total = 0u64;
+    let mut selected = vec![];
+    for i in order {
+        if total >= amount {
+            break;
+        }
+        total = total.saturating_add(coins[i].amount.0);
+        selected.push(i);
+    }
+
+    (total >= amount).then(|| selected)
+}
+
+/// The state of a branch and bound search, going through the coins from the
+/// largest to the smallest and deciding to spend each one or not.
+struct Search<'a> {
+    coins: &'a [Coin],
+    order: &'a [usize],
+    target: u64,
+    upper_bound: u64,
+    tries_left: usize,
+    selected: Vec<usize>,
+}
+
+impl Search<'_> {
+    /// Whether spending some of the coins from `position` on, in addition to
+    /// the selected ones adding up to `total`, reaches the target without
+    /// exceeding the upper bound. `available` is the sum of these coins.
+    fn explore(&mut self, position: usize, total: u64, available: u64) -> bool {
+        if total > self.upper_bound {
+            return false;
+        }
+        if total >= self.target {
+            return true;
+        }
+        if position == self.order.len() || total.saturating_add(available) < self.target {
+            return false;
+        }
+        if self.tries_left == 0 {
+            return false;
+        }
+        self.tries_left -= 1;
+
+        let coin = self.order[position];
+        let amount = self.coins[coin].amount.0;
+
+        self.selected.push(coin);
+        let remaining = available.saturating_sub(amount);
+        if self.explore(position + 1, total.saturating_add(amount), remaining) {
+            return true;
+        }
+        self.selected.pop();
+
+        self.explore(position + 1, total, remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_gql_client::client::schema::{coin::CoinStatus, primitives::U64};
+
+    fn coins(amounts: &[u64]) -> Vec<Coin> {
+        amounts
+            .iter()
+            .map(|&amount| Coin {
+                amount: U64(amount),
+                block_created: U64(0),
+                asset_id: Default::default(),
+                utxo_id: Default::default(),
+                maturity: U64(0),
+                owner: Default::default(),
+                status: CoinStatus::Unspent,
+            })
+            .collect()
+    }
+
+    fn selected_amounts(coins: &[Coin], selected: Option<Vec<usize>>) -> Vec<u64> {
+        selected
+            .unwrap()
+            .into_iter()
+            .map(|i| coins[i].amount.0)
+            .collect()
+    }
+
+    #[test]
+    fn largest_and_smallest_first() {
+        let coins = coins(&[5, 1, 20, 3, 8]);
+
+        assert_eq!(
+            selected_amounts(&coins, LargestFirst.select(&coins, 21)),
+            vec![20, 8]
+        );
+        assert_eq!(
+            selected_amounts(&coins, SmallestFirst.select(&coins, 6)),
+            vec![1, 3, 5]
+        );
+        assert_eq!(LargestFirst.select(&coins, 38), None);
+        assert_eq!(SmallestFirst.select(&coins, 0), Some(vec![]));
+    }
+
+    #[test]
+    fn random_selection_covers_the_amount() {
+        let coins = coins(&[5, 1, 20, 3, 8]);
+
+        for _ in 0..20 {
+            let selected = selected_amounts(&coins, RandomSelection.select(&coins, 10));
+            assert!(selected.iter().sum::<u64>() >= 10);
+            // No coin is spent needlessly, but for the last one.
+            assert!(selected[..selected.len() - 1].iter().sum::<u64>() < 10);
+        }
+        assert_eq!(RandomSelection.select(&coins, 38), None);
+    }
+
+    #[test]
+    fn branch_and_bound_avoids_change() {
+        let coins = coins(&[5, 1, 20, 3, 8]);
+
+        let exact = BranchAndBound::default();
+        let mut selected = selected_amounts(&coins, exact.select(&coins, 12));
+        selected.sort_unstable();
+        assert_eq!(selected, vec![1, 3, 8]);
+
+        // No exact match: falls back to largest first.
+        let round_coins = self::coins(&[10, 20, 40]);
+        assert_eq!(
+            selected_amounts(&round_coins, exact.select(&round_coins, 25)),
+            vec![40]
+        );
+        let tolerant = BranchAndBound {
+            tolerance: 2,
+            ..Default::default()
+        };
+        let total: u64 = selected_amounts(&coins, tolerant.select(&coins, 30))
+            .iter()
+            .sum();
+        assert!((30..=32).contains(&total));
+    }
+
+    #[test]
+    fn branch_and_bound_handles_amounts_adding_up_past_u64_max() {
+        let coins = coins(&[u64::MAX, u64::MAX - 1, 2]);
+
+        let selected = BranchAndBound::default().select(&coins, u64::MAX);
+        assert_eq!(selected_amounts(&coins, selected), vec![u64::MAX]);
+    }
+}
@@ -0,0 +1,339 @@
+use crate::provider::Provider;
+use crate::wallet::{Wallet, WalletError, DEFAULT_DERIVATION_PATH_PREFIX};
+use crate::Signer;
+use coins_bip32::{path::DerivationPath, prelude::SigningKey, xkeys::Parent, xkeys::XPriv};
+use coins_bip39::{English, Mnemonic};
+use fuel_crypto::SecretKey;
+use fuel_gql_client::client::{PageDirection, PaginationRequest};
+use rand::{CryptoRng, Rng};
+use std::{fmt, path::Path, str::FromStr};
+
+/// The first hardened child index, 2^31.
+const HARDENED_INDEX: u32 = 1 << 31;
+
+/// A hierarchical deterministic wallet: manages the accounts derived from a
+/// single mnemonic phrase.
+///
+/// The phrase is turned into a seed once, when the `HdWallet` is created.
+/// Accounts are then derived on demand, account `i` being the key at
+/// `m/44'/1179993420'/0'/0/i`, the path `Wallet::new_from_mnemonic_phrase`
+/// uses for account `0`. Deriving an account is cheap, so an `HdWallet` can
+/// manage any number of addresses without storing their keys.
+///
+/// # Examples
+///
+/// ```
+/// use fuels::prelude::*;
+/// use fuels::signers::hd_wallet::HdWallet;
+///
+/// async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+///   let (provider, _) = setup_test_provider(vec![], Config::local_node()).await;
+///
+///   let phrase = LocalWallet::generate_mnemonic_phrase(&mut rand::thread_rng(), 12)?;
+///   let hd_wallet = HdWallet::new_from_mnemonic_phrase(&phrase, None, Some(provider))?;
+///
+///   // A deposit address per customer.
+///   let deposit_wallet = hd_wallet.account(42)?;
+///
+///   // The accounts that have been used, stopping after 20 unused ones in a row.
+///   let used_wallets = hd_wallet.discover_accounts(20).await?;
+///   Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct HdWallet {
+    phrase: String,
+    /// The key at `m/44'/1179993420'/0'/0`, the parent of every account.
+    accounts_key: XPriv,
+    provider: Option<Provider>,
+}
+
+impl HdWallet {
+    /// Creates an `HdWallet` from a mnemonic phrase, protected by the BIP-39
+    /// `passphrase` if there is one. A different passphrase gives different
+    /// accounts.
+    pub fn new_from_mnemonic_phrase(
+        phrase: &str,
+        passphrase: Option<&str>,
+        provider: Option<Provider>,
+    ) -> Result<Self, WalletError> {
+        let mnemonic = Mnemonic::<English>::new_from_phrase(phrase)?;
+
+        let accounts_path =
+            DerivationPath::from_str(DEFAULT_DERIVATION_PATH_PREFIX.trim_end_matches('/'))?;
+        let accounts_key = mnemonic
+            .master_key(passphrase)?
+            .derive_path(accounts_path)?;
+
+        Ok(Self {
+            phrase: mnemonic.to_phrase()?,
+            accounts_key,
+            provider,
+        })
+    }
+
+    /// Creates an `HdWallet` from a newly generated mnemonic phrase of
+    /// `count` words. The phrase is available with `phrase`.
+    pub fn new_random<R: Rng>(
+        rng: &mut R,
+        count: usize,
+        passphrase: Option<&str>,
+        provider: Option<Provider>,
+    ) -> Result<Self, WalletError> {
+        let phrase = Wallet::generate_mnemonic_phrase(rng, count)?;
+        Self::new_from_mnemonic_phrase(&phrase, passphrase, provider)
+    }
+
+    /// The mnemonic phrase the accounts are derived from.
+    pub fn phrase(&self) -> &str {
+        &self.phrase
+    }
+
+    /// Derives the account at `index`, i.e. the wallet at
+    /// `m/44'/1179993420'/0'/0/index`, using the `HdWallet`'s provider.
+    /// Indices are non-hardened, so they must be lower than 2^31.
+    pub fn account(&self, index: u32) -> Result<Wallet, WalletError> {
+        if index >= HARDENED_INDEX {
+            return Err(WalletError::InvalidAccountIndex(index));
+        }
+
+        let account_key = self.accounts_key.derive_child(index)?;
+        let key: &SigningKey = account_key.as_ref();
+        let secret_key = unsafe { SecretKey::from_slice_unchecked(key.to_bytes().as_ref()) };
+
+        Ok(Wallet::new_from_private_key(
+            secret_key,
+            self.provider.clone(),
+        ))
+    }
+
+    /// Scans the accounts in order, from index `0`, and returns the ones
+    /// that have been used, i.e. that sent or received coins in a
+    /// transaction, even if they hold none anymore. The scan stops once
+    /// `gap_limit` accounts in a row are unused, as is usual for BIP-44
+    /// account discovery.
+    pub async fn discover_accounts(&self, gap_limit: u32) -> Result<Vec<Wallet>, WalletError> {
+        let provider = self.get_provider()?;
+
+        let mut used = vec![];
+        let mut gap = 0;
+        let mut index = 0;
+        while gap < gap_limit {
+            let account = self.account(index)?;
+            let first_transaction = PaginationRequest {
+                cursor: None,
+                results: 1,
+                direction: PageDirection::Forward,
+            };
+            let transactions = provider
+                .get_transactions_by_owner(&account.address().to_string(), first_transaction)
+                .await?;
+            if transactions.results.is_empty() {
+                gap += 1;
+            } else {
+                gap = 0;
+                used.push(account);
+            }
+            index += 1;
+        }
+
+        Ok(used)
+    }
+
+    pub fn get_provider(&self) -> Result<&Provider, WalletError> {
+        self.provider.as_ref().ok_or(WalletError::NoProvider)
+    }
+
+    pub fn set_provider(&mut self, provider: Provider) {
+        self.provider = Some(provider)
+    }
+
+    /// Encrypts the mnemonic phrase with the given password and saves it to
+    /// the given directory. Returns the name of the file, as
+    /// `Wallet::encrypt` does. The BIP-39 passphrase isn't saved, it has to
+    /// be given again to `load_keystore`.
+    pub fn encrypt<P, S>(&self, dir: P, password: S) -> Result<String, WalletError>
+    where
+        P: AsRef<Path>,
+        S: AsRef<[u8]>,
+    {
+        let mut rng = rand::thread_rng();
+
+        Ok(eth_keystore::encrypt_key(
+            dir,
+            &mut rng,
+            self.phrase.as_bytes(),
+            password,
+        )?)
+    }
+
+    /// Recreates an `HdWallet` from a mnemonic phrase saved by `encrypt`.
+    pub fn load_keystore<P, S>(
+        keypath: P,
+        password: S,
+        passphrase: Option<&str>,
+        provider: Option<Provider>,
+    ) -> Result<Self, WalletError>
+    where
+        P: AsRef<Path>,
+        S: AsRef<[u8]>,
+    {
+        let phrase = eth_keystore::decrypt_key(keypath, password)?;
+
+        Self::new_from_mnemonic_phrase(&String::from_utf8_lossy(&phrase), passphrase, provider)
+    }
+
+    /// Generates an `HdWallet` from a new mnemonic phrase and saves the
+    /// encrypted phrase in the given directory, see `encrypt`.
+    pub fn new_from_keystore<P, R, S>(
+        dir: P,
+        rng: &mut R,
+        password: S,
+        passphrase: Option<&str>,
+        provider: Option<Provider>,
+    ) -> Result<(Self, String), WalletError>
+    where
+        P: AsRef<Path>,
+        R: Rng + CryptoRng,
+        S: AsRef<[u8]>,
+    {
+        let hd_wallet = Self::new_random(rng, 24, passphrase, provider)?;
+        let uuid = hd_wallet.encrypt(dir, password)?;
+
+        Ok((hd_wallet, uuid))
+    }
+}
+
+impl fmt::Debug for HdWallet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HdWallet")
+            .field("provider", &self.provider)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const PHRASE: &str =
+        "oblige salon price punch saddle immune slogan rare snap desert retire surprise";
+
+    #[test]
+    fn accounts_follow_the_default_derivation_path() {
+        let hd_wallet = HdWallet::new_from_mnemonic_phrase(PHRASE, None, None).unwrap();
+
+        let first = Wallet::new_from_mnemonic_phrase(PHRASE, None).unwrap();
+        let third = Wallet::new_from_mnemonic_phrase_with_path(
+            PHRASE,
+            None,
+            &format!("{}{}", DEFAULT_DERIVATION_PATH_PREFIX, 2),
+        )
+        .unwrap();
+
+        assert_eq!(hd_wallet.account(0).unwrap().address(), first.address());
+        assert_eq!(hd_wallet.account(2).unwrap().address(), third.address());
+        assert_ne!(
+            hd_wallet.account(0).unwrap().address(),
+            hd_wallet.account(1).unwrap().address()
+        );
+    }
+
+    #[test]
+    fn hardened_account_indices_are_an_error() {
+        let hd_wallet = HdWallet::new_from_mnemonic_phrase(PHRASE, None, None).unwrap();
+
+        assert!(hd_wallet.account(HARDENED_INDEX - 1).is_ok());
+        let err = hd_wallet.account(HARDENED_INDEX).unwrap_err();
+        assert!(matches!(err, WalletError::InvalidAccountIndex(index) if index == HARDENED_INDEX));
+    }
+
+    #[test]
+    fn passphrase_changes_the_accounts() {
+        let without = HdWallet::new_from_mnemonic_phrase(PHRASE, None, None).unwrap();
+        let with = HdWallet::new_from_mnemonic_phrase(PHRASE, Some("secret"), None).unwrap();
+
+        assert_ne!(
+            without.account(0).unwrap().address(),
+            with.account(0).unwrap().address()
+        );
+    }
+
+    #[test]
+    fn encrypted_keystore() {
+        let dir = tempdir().unwrap();
+        let hd_wallet = HdWallet::new_from_mnemonic_phrase(PHRASE, Some("secret"), None).unwrap();
+
+        let uuid = hd_wallet.encrypt(&dir, "password").unwrap();
+        let path = dir.path().join(uuid);
+
+        let recovered = HdWallet::load_keystore(&path, "password", Some("secret"), None).unwrap();
+        assert_eq!(recovered.phrase(), PHRASE);
+        assert_eq!(
+            recovered.account(7).unwrap().address(),
+            hd_wallet.account(7).unwrap().address()
+        );
+
+        assert!(HdWallet::load_keystore(&path, "wrong", Some("secret"), None).is_err());
+    }
+
+    #[tokio::test]
+    async fn discovery_needs_a_provider() {
+        let hd_wallet = HdWallet::new_from_mnemonic_phrase(PHRASE, None, None).unwrap();
+
+        let err = hd_wallet.discover_accounts(20).await.unwrap_err();
+        assert!(matches!(err, WalletError::NoProvider));
+    }
+
+    #[cfg(feature = "test-helpers")]
+    #[tokio::test]
+    async fn discovers_used_accounts_up_to_the_gap_limit() {
+        use fuel_core::service::Config;
+        use fuels_core::constants::BASE_ASSET_ID;
+        use fuels_core::parameters::TxParameters;
+        use fuels_test_helpers::{setup_single_asset_coins, setup_test_client};
+
+        let mut funder = Wallet::new_random(None);
+        let coins = setup_single_asset_coins(funder.address(), BASE_ASSET_ID, 3, 100);
+        let (client, _) = setup_test_client(coins, Config::local_node()).await;
+        let provider = Provider::new(client);
+        funder.set_provider(provider.clone());
+
+        let hd_wallet =
+            HdWallet::new_from_mnemonic_phrase(PHRASE, None, Some(provider.clone())).unwrap();
+        for index in [0, 3, 10] {
+            let address = hd_wallet.account(index).unwrap().address();
+            funder
+                .transfer(&address, 1, BASE_ASSET_ID, TxParameters::default())
+                .await
+                .unwrap();
+        }
+
+        // An account stays used once it has spent all its coins.
+        let mut account = hd_wallet.account(3).unwrap();
+        account.set_provider(provider);
+        account
+            .transfer(&funder.address(), 1, BASE_ASSET_ID, TxParameters::default())
+            .await
+            .unwrap();
+        assert!(account.get_balances().await.unwrap().is_empty());
+
+        let addresses = |wallets: Vec<Wallet>| -> Vec<_> {
+            wallets.iter().map(|wallet| wallet.address()).collect()
+        };
+
+        // Account 10 is 7 accounts after account 3.
+        let used = hd_wallet.discover_accounts(5).await.unwrap();
+        assert_eq!(
+            addresses(used),
+            vec![
+                hd_wallet.account(0).unwrap().address(),
+                hd_wallet.account(3).unwrap().address()
+            ]
+        );
+
+        let used = hd_wallet.discover_accounts(7).await.unwrap();
+        assert_eq!(used.len(), 3);
+    }
+}
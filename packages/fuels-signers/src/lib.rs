@@ -1,12 +1,19 @@
+pub mod coin_selection;
+pub mod hd_wallet;
+pub mod partially_signed;
 pub mod provider;
+pub mod remote_signer;
+pub mod typed_data;
 pub mod wallet;
 
 #[doc(no_inline)]
 pub use fuel_crypto;
 
 use async_trait::async_trait;
+use coin_selection::CoinSelector;
 use fuel_crypto::Signature;
 use fuel_gql_client::{fuel_tx::Transaction, fuel_types::Address};
+use provider::Provider;
 use std::error::Error;
 
 /// A wallet instantiated with a locally stored private key
@@ -30,6 +37,35 @@ pub trait Signer: std::fmt::Debug + Send + Sync {
 
     /// Returns the signer's Fuel Address
     fn address(&self) -> Address;
+
+    /// Returns the provider used to fetch the signer's coins and submit the
+    /// transactions it signs, if it has one. Signers without a provider can
+    /// sign, but can't be used to call contracts.
+    fn provider(&self) -> Option<&Provider> {
+        None
+    }
+
+    /// Returns the strategy choosing the coins that pay for the signer's
+    /// transactions. Without one, the coins are chosen by the node.
+    fn coin_selector(&self) -> Option<&dyn CoinSelector> {
+        None
+    }
+}
+
+/// Keys and wallets for the tests, the same for a given seed.
+#[cfg(test)]
+pub(crate) mod test_utils {
+    use crate::LocalWallet;
+    use fuel_crypto::SecretKey;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    pub(crate) fn secret_key(seed: u64) -> SecretKey {
+        SecretKey::random(&mut StdRng::seed_from_u64(seed))
+    }
+
+    pub(crate) fn wallet(seed: u64) -> LocalWallet {
+        LocalWallet::new_from_private_key(secret_key(seed), None)
+    }
 }
 
 #[cfg(test)]
@@ -255,4 +291,466 @@ mod tests {
         let total_amount: u64 = wallet_2_final_coins.iter().map(|c| c.amount.0).sum();
         assert_eq!(total_amount, 7);
     }
+
+    #[tokio::test]
+    async fn transfer_signed_offline() {
+        use crate::partially_signed::PartiallySignedTransaction;
+
+        // The cold wallet never gets a provider.
+        let cold_wallet = LocalWallet::new_random(None);
+        let recipient = LocalWallet::new_random(None);
+
+        let coins = setup_single_asset_coins(cold_wallet.address, BASE_ASSET_ID, 1, 5);
+        let (client, _) = setup_test_client(coins, Config::local_node()).await;
+        let provider = Provider::new(client);
+
+        // The networked host only knows the cold wallet's address.
+        let inputs = provider
+            .get_asset_inputs_for_amount(&cold_wallet.address(), BASE_ASSET_ID, 2, 0)
+            .await
+            .unwrap();
+
+        // The transaction is built, signed and exported without a provider.
+        let tx = cold_wallet.build_transfer(
+            &recipient.address(),
+            2,
+            BASE_ASSET_ID,
+            inputs,
+            TxParameters::default(),
+        );
+        let mut pst = PartiallySignedTransaction::new(tx).unwrap();
+        pst.sign(&cold_wallet).await.unwrap();
+        let exported = serde_json::to_string(&pst).unwrap();
+
+        // The signed transaction is broadcast later.
+        let pst: PartiallySignedTransaction = serde_json::from_str(&exported).unwrap();
+        provider
+            .send_transaction(&pst.into_transaction().unwrap())
+            .await
+            .unwrap();
+
+        let balance = provider
+            .get_asset_balance(&recipient.address(), BASE_ASSET_ID)
+            .await
+            .unwrap();
+        assert_eq!(balance, 2);
+        let balance = provider
+            .get_asset_balance(&cold_wallet.address(), BASE_ASSET_ID)
+            .await
+            .unwrap();
+        assert_eq!(balance, 3);
+    }
+
+    #[tokio::test]
+    async fn concurrent_transfers_use_different_coins() {
+        let mut wallet = LocalWallet::new_random(None);
+        let recipient = LocalWallet::new_random(None);
+
+        let coins = setup_single_asset_coins(wallet.address, BASE_ASSET_ID, 6, 1);
+        let (client, _) = setup_test_client(coins, Config::local_node()).await;
+        let provider = Provider::new(client);
+        wallet.set_provider(provider.clone());
+
+        // Coins selected for a transaction aren't selected again until it is sent.
+        let inputs = provider
+            .get_asset_inputs_for_amount(&wallet.address(), BASE_ASSET_ID, 1, 0)
+            .await
+            .unwrap();
+        let reserved = provider.reserved_coins(&wallet.address()).await;
+        assert_eq!(reserved.len(), inputs.len());
+        let spendable = wallet.get_spendable_coins(&BASE_ASSET_ID, 1).await.unwrap();
+        assert!(spendable
+            .iter()
+            .all(|coin| !reserved.contains(&coin.utxo_id.clone().into())));
+
+        provider.release_coins(&inputs).await;
+        assert!(provider.reserved_coins(&wallet.address()).await.is_empty());
+
+        let to = recipient.address();
+        let transfer = || wallet.transfer(&to, 1, BASE_ASSET_ID, TxParameters::default());
+        let (first, second, third) = tokio::join!(transfer(), transfer(), transfer());
+        first.unwrap();
+        second.unwrap();
+        third.unwrap();
+
+        let balance = provider
+            .get_asset_balance(&recipient.address(), BASE_ASSET_ID)
+            .await
+            .unwrap();
+        assert_eq!(balance, 3);
+        assert!(provider.reserved_coins(&wallet.address()).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn transfer_with_a_coin_selector() {
+        use crate::coin_selection::{BranchAndBound, SmallestFirst};
+        use crate::provider::ProviderError;
+        use crate::wallet::WalletError;
+        use fuel_gql_client::client::schema::coin::CoinStatus;
+
+        let mut wallet = LocalWallet::new_random(None);
+        let recipient = LocalWallet::new_random(None);
+
+        let mut coins = setup_single_asset_coins(wallet.address, BASE_ASSET_ID, 1, 100);
+        coins.extend(setup_single_asset_coins(
+            wallet.address,
+            BASE_ASSET_ID,
+            2,
+            5,
+        ));
+        let (client, _) = setup_test_client(coins, Config::local_node()).await;
+        wallet.set_provider(Provider::new(client));
+
+        let spent_amounts = |inputs: Vec<Input>| -> Vec<u64> {
+            let mut amounts: Vec<u64> = inputs
+                .iter()
+                .filter_map(|input| match input {
+                    Input::CoinSigned { amount, .. } => Some(*amount),
+                    _ => None,
+                })
+                .collect();
+            amounts.sort_unstable();
+            amounts
+        };
+
+        let smallest_first = wallet.clone().with_coin_selector(SmallestFirst);
+        let inputs = smallest_first
+            .get_asset_inputs_for_amount(BASE_ASSET_ID, 10, 0)
+            .await
+            .unwrap();
+        assert_eq!(spent_amounts(inputs.clone()), vec![5, 5]);
+        wallet.get_provider().unwrap().release_coins(&inputs).await;
+
+        // Spending both small coins leaves no change.
+        let exact = wallet.with_coin_selector(BranchAndBound::default());
+        let to = recipient.address();
+        exact
+            .transfer(&to, 10, BASE_ASSET_ID, TxParameters::default())
+            .await
+            .unwrap();
+        let unspent: Vec<u64> = exact
+            .get_coins()
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|coin| coin.status == CoinStatus::Unspent)
+            .map(|coin| coin.amount.0)
+            .collect();
+        assert_eq!(unspent, vec![100]);
+
+        let err = exact
+            .transfer(&to, 1_000, BASE_ASSET_ID, TxParameters::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            WalletError::ProviderError(ProviderError::NotEnoughCoins { amount: 1_000, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn consolidate_and_split_coins() {
+        use crate::wallet::WalletError;
+
+        let mut wallet = LocalWallet::new_random(None);
+        let asset_id = AssetId::from([1u8; 32]);
+
+        let mut coins = setup_single_asset_coins(wallet.address, BASE_ASSET_ID, 10, 100);
+        coins.extend(setup_single_asset_coins(wallet.address, asset_id, 3, 10));
+        let (client, _) = setup_test_client(coins, Config::local_node()).await;
+        wallet.set_provider(Provider::new(client));
+
+        let unspent = |wallet: LocalWallet, asset_id: AssetId| async move {
+            let mut amounts: Vec<u64> = wallet
+                .get_provider()
+                .unwrap()
+                .get_asset_coins(&wallet.address(), asset_id)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|coin| coin.amount.0)
+                .collect();
+            amounts.sort_unstable();
+            amounts
+        };
+
+        // 10 coins, 4 per transaction: 7 coins, then 4, then a single one.
+        let sent = wallet
+            .consolidate_coins(BASE_ASSET_ID, 4, TxParameters::default())
+            .await
+            .unwrap();
+        assert_eq!(sent.len(), 3);
+        assert!(sent.iter().all(|tx| tx.fee == 0));
+        assert_eq!(unspent(wallet.clone(), BASE_ASSET_ID).await, vec![1000]);
+
+        // The fees of another asset are paid with the base asset.
+        let tx_parameters = TxParameters {
+            gas_price: 1,
+            gas_limit: 100,
+            ..Default::default()
+        };
+        let sent = wallet
+            .split_coins(asset_id, 3, 5, tx_parameters)
+            .await
+            .unwrap();
+        assert_eq!(sent.len(), 1);
+        let fee = sent[0].fee;
+        assert!(fee > 0);
+        let split = unspent(wallet.clone(), asset_id).await;
+        assert_eq!(split.iter().filter(|&&amount| amount == 5).count(), 3);
+        assert_eq!(split.iter().sum::<u64>(), 30);
+        assert_eq!(
+            unspent(wallet.clone(), BASE_ASSET_ID).await,
+            vec![1000 - fee]
+        );
+
+        let sent = wallet
+            .consolidate_coins(asset_id, 255, tx_parameters)
+            .await
+            .unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(unspent(wallet.clone(), asset_id).await, vec![30]);
+        assert_eq!(
+            unspent(wallet.clone(), BASE_ASSET_ID).await,
+            vec![1000 - fee - sent[0].fee]
+        );
+
+        let err = wallet
+            .consolidate_coins(asset_id, 1, tx_parameters)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, WalletError::InvalidMaxInputs(1)));
+    }
+
+    #[tokio::test]
+    async fn consolidate_dust_coins_paying_fees() {
+        let mut wallet = LocalWallet::new_random(None);
+        let asset_id = AssetId::from([1u8; 32]);
+
+        let mut coins = setup_single_asset_coins(wallet.address, BASE_ASSET_ID, 20, 1);
+        coins.extend(setup_single_asset_coins(
+            wallet.address,
+            BASE_ASSET_ID,
+            5,
+            200,
+        ));
+        coins.extend(setup_single_asset_coins(wallet.address, asset_id, 20, 1));
+        let (client, _) = setup_test_client(coins, Config::local_node()).await;
+        let provider = Provider::new(client);
+        wallet.set_provider(provider.clone());
+
+        let tx_parameters = TxParameters {
+            gas_price: 1,
+            gas_limit: 100,
+            ..Default::default()
+        };
+
+        // The dust coins don't cover the fee: other coins of the base asset, possibly those
+        // merged by the next transactions, pay for it.
+        let sent = wallet
+            .consolidate_coins(BASE_ASSET_ID, 4, tx_parameters)
+            .await
+            .unwrap();
+        let fees: u64 = sent.iter().map(|tx| tx.fee).sum();
+        assert!(sent.iter().all(|tx| tx.fee > 0));
+        let coins = provider
+            .get_asset_coins(&wallet.address(), BASE_ASSET_ID)
+            .await
+            .unwrap();
+        assert_eq!(coins.len(), 1);
+        assert_eq!(coins[0].amount.0, 1020 - fees);
+
+        let sent = wallet
+            .consolidate_coins(asset_id, 4, tx_parameters)
+            .await
+            .unwrap();
+        let coins = provider
+            .get_asset_coins(&wallet.address(), asset_id)
+            .await
+            .unwrap();
+        assert_eq!(coins.len(), 1);
+        assert_eq!(coins[0].amount.0, 20);
+        let new_fees: u64 = sent.iter().map(|tx| tx.fee).sum();
+        let balance = wallet.get_asset_balance(&BASE_ASSET_ID).await.unwrap();
+        assert_eq!(balance, 1020 - fees - new_fees);
+        assert!(provider.reserved_coins(&wallet.address()).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn split_coins_needing_too_many_inputs() {
+        use crate::coin_selection::LargestFirst;
+
+        let mut wallet = LocalWallet::new_random(None);
+
+        let coins = setup_single_asset_coins(wallet.address, BASE_ASSET_ID, 300, 1);
+        let (client, _) = setup_test_client(coins, Config::local_node()).await;
+        wallet.set_provider(Provider::new(client));
+
+        // Both coins need 300 inputs, more than a transaction can have: they are created one
+        // at a time. The node doesn't select that many coins, a coin selector does.
+        let sent = wallet
+            .clone()
+            .with_coin_selector(LargestFirst)
+            .split_coins(BASE_ASSET_ID, 2, 150, TxParameters::default())
+            .await
+            .unwrap();
+        assert_eq!(sent.len(), 2);
+        // The second transaction doesn't spend the coin created by the first.
+        let provider = wallet.get_provider().unwrap();
+        let coins = provider
+            .get_asset_coins(&wallet.address(), BASE_ASSET_ID)
+            .await
+            .unwrap();
+        assert_eq!(coins.iter().filter(|coin| coin.amount.0 == 150).count(), 2);
+        assert!(provider.reserved_coins(&wallet.address()).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn transfer_to_many_recipients() {
+        let mut wallet = LocalWallet::new_random(None);
+        let asset_id = AssetId::from([1u8; 32]);
+
+        let mut coins = setup_single_asset_coins(wallet.address, BASE_ASSET_ID, 1, 1000);
+        coins.extend(setup_single_asset_coins(wallet.address, asset_id, 1, 1000));
+        let (client, _) = setup_test_client(coins, Config::local_node()).await;
+        let provider = Provider::new(client);
+        wallet.set_provider(provider.clone());
+
+        let recipients: Vec<Address> = (0..300)
+            .map(|_| LocalWallet::new_random(None).address())
+            .collect();
+        let transfers: Vec<_> = recipients
+            .iter()
+            .enumerate()
+            .map(|(i, to)| {
+                let asset_id = if i % 2 == 0 { BASE_ASSET_ID } else { asset_id };
+                (*to, 2, asset_id)
+            })
+            .collect();
+
+        // More outputs than a transaction can hold.
+        let sent = wallet
+            .transfer_many(&transfers, TxParameters::default())
+            .await
+            .unwrap();
+        assert_eq!(sent.len(), 2);
+
+        for (i, to) in recipients.iter().enumerate().step_by(37) {
+            let asset_id = if i % 2 == 0 { BASE_ASSET_ID } else { asset_id };
+            let balance = provider.get_asset_balance(to, asset_id).await.unwrap();
+            assert_eq!(balance, 2);
+        }
+        let balance = provider.get_asset_balance(recipients.last().unwrap(), asset_id);
+        assert_eq!(balance.await.unwrap(), 2);
+        assert_eq!(wallet.get_asset_balance(&BASE_ASSET_ID).await.unwrap(), 700);
+        assert_eq!(wallet.get_asset_balance(&asset_id).await.unwrap(), 700);
+    }
+
+    #[tokio::test]
+    async fn failed_transfer_to_many_recipients_returns_the_sent_transactions() {
+        use crate::wallet::WalletError;
+
+        let mut wallet = LocalWallet::new_random(None);
+        let coins = setup_single_asset_coins(wallet.address, BASE_ASSET_ID, 1, 1000);
+        let (client, _) = setup_test_client(coins, Config::local_node()).await;
+        wallet.set_provider(Provider::new(client));
+
+        let to = LocalWallet::new_random(None).address();
+        // The first transaction pays in the base asset, the second one in an
+        // asset the wallet doesn't own.
+        let mut transfers = vec![(to, 2, BASE_ASSET_ID); 254];
+        transfers.push((to, 2, AssetId::from([1u8; 32])));
+
+        let err = wallet
+            .transfer_many(&transfers, TxParameters::default())
+            .await
+            .unwrap_err();
+        match err {
+            WalletError::TransferManyFailed { sent, source } => {
+                assert_eq!(sent.len(), 1);
+                assert!(matches!(*source, WalletError::ProviderError(_)));
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+        assert_eq!(wallet.get_asset_balance(&BASE_ASSET_ID).await.unwrap(), 492);
+    }
+
+    #[tokio::test]
+    async fn force_transfer_to_a_contract() {
+        use fuel_gql_client::fuel_tx::{Contract, Salt};
+        use fuel_gql_client::fuel_vm::{consts::REG_ONE, prelude::Opcode};
+
+        let mut wallet = LocalWallet::new_random(None);
+        let asset_id = AssetId::from([1u8; 32]);
+
+        let mut coins = setup_single_asset_coins(wallet.address, BASE_ASSET_ID, 1, 1000);
+        coins.extend(setup_single_asset_coins(wallet.address, asset_id, 1, 1000));
+        let (client, _) = setup_test_client(coins, Config::local_node()).await;
+        let provider = Provider::new(client);
+        wallet.set_provider(provider.clone());
+
+        // A contract returning as soon as it is called.
+        let code = Opcode::RET(REG_ONE).to_bytes().to_vec();
+        let contract = Contract::from(code.clone());
+        let state_root = Contract::default_state_root();
+        let contract_id = contract.id(&Salt::zeroed(), &contract.root(), &state_root);
+        let inputs = wallet
+            .get_asset_inputs_for_amount(BASE_ASSET_ID, 1, 1)
+            .await
+            .unwrap();
+        let mut deployment = Transaction::create(
+            0,
+            1_000_000,
+            0,
+            0,
+            0,
+            Salt::zeroed(),
+            vec![],
+            vec![],
+            inputs,
+            vec![
+                Output::contract_created(contract_id, state_root),
+                Output::change(wallet.address(), 0, BASE_ASSET_ID),
+            ],
+            vec![code.into()],
+        );
+        wallet.sign_transaction(&mut deployment).await.unwrap();
+        provider.send_transaction(&deployment).await.unwrap();
+
+        wallet
+            .force_transfer_to_contract(&contract_id, 100, BASE_ASSET_ID, TxParameters::default())
+            .await
+            .unwrap();
+        let tx_parameters = TxParameters {
+            gas_price: 1,
+            gas_limit: 100,
+            ..Default::default()
+        };
+        wallet
+            .force_transfer_to_contract(&contract_id, 40, asset_id, tx_parameters)
+            .await
+            .unwrap();
+
+        let balance = provider
+            .get_contract_balance(&contract_id, BASE_ASSET_ID)
+            .await
+            .unwrap();
+        assert_eq!(balance, 100);
+        let balances = provider
+            .get_contract_balances(&contract_id, &[BASE_ASSET_ID, asset_id])
+            .await
+            .unwrap();
+        assert_eq!(balances[&format!("{:#x}", BASE_ASSET_ID)], 100);
+        assert_eq!(balances[&format!("{:#x}", asset_id)], 40);
+        assert_eq!(wallet.get_asset_balance(&asset_id).await.unwrap(), 960);
+        assert!(wallet.get_asset_balance(&BASE_ASSET_ID).await.unwrap() < 900);
+
+        // There is no such contract.
+        let unknown = fuel_gql_client::fuel_types::ContractId::from([9u8; 32]);
+        assert!(wallet
+            .force_transfer_to_contract(&unknown, 1, BASE_ASSET_ID, TxParameters::default())
+            .await
+            .is_err());
+        assert!(provider.reserved_coins(&wallet.address()).await.is_empty());
+    }
 }
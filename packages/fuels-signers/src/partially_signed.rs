@@ -0,0 +1,534 @@
+use crate::provider::Provider;
+use crate::Signer;
+use fuel_crypto::{Message, Signature};
+use fuel_gql_client::{
+    fuel_tx::{Input, Receipt, Transaction, Witness},
+    fuel_types::{
+        bytes::{Deserializable, SerializableVec},
+        Address, Bytes32, Bytes64,
+    },
+};
+use fuels_core::errors::Error;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// A transaction waiting for the signatures of several parties.
+///
+/// Each witness of the transaction is expected to hold the signature of a
+/// given address: the owner of the signed coins that point to it, or any
+/// address required with `require_signer`, e.g. a key checked by a multisig
+/// predicate. The other witnesses, e.g. the bytecode of a contract being
+/// created, aren't signatures and are left as they are. The signers add
+/// their signature in any order, on the same `PartiallySignedTransaction` or
+/// on copies of it that are then `combine`d. Once every witness is signed,
+/// `into_transaction` checks the signatures and returns the transaction,
+/// ready to be submitted.
+///
+/// It serializes, e.g. to JSON, as the hex encoding of the transaction along
+/// with the list of expected signers, `null` for the witnesses that aren't
+/// signatures, to be passed between machines:
+///
+/// ```json
+/// { "transaction": "0x...", "signers": [null, "0x...", "0x..."] }
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use fuels::prelude::*;
+/// use fuels::signers::partially_signed::PartiallySignedTransaction;
+///
+/// async fn foo(tx: fuels::tx::Transaction, alice: LocalWallet, bob: LocalWallet) -> Result<(), Error> {
+///   // The coordinator records who has to sign what and sends it around.
+///   let json = serde_json::to_string(&PartiallySignedTransaction::new(tx)?)?;
+///
+///   // Each party adds its signature, on its own machine.
+///   let mut alice_copy: PartiallySignedTransaction = serde_json::from_str(&json)?;
+///   alice_copy.sign(&alice).await?;
+///   let mut bob_copy: PartiallySignedTransaction = serde_json::from_str(&json)?;
+///   bob_copy.sign(&bob).await?;
+///
+///   // The coordinator gathers the signatures and submits the transaction.
+///   alice_copy.combine(&bob_copy)?;
+///   alice_copy.submit(alice.get_provider()?).await?;
+///   Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "PortableTransaction", into = "PortableTransaction")]
+pub struct PartiallySignedTransaction {
+    /// Has one witness per signer, empty until signed.
+    tx: Transaction,
+    /// The address expected to sign each witness, by witness index. `None`
+    /// for the witnesses that aren't signatures.
+    signers: Vec<Option<Address>>,
+}
+
+impl PartiallySignedTransaction {
+    /// Records the signers of `tx`: the owner of each signed coin input
+    /// signs the witness at its `witness_index`. The witnesses already in
+    /// `tx` are kept, so it can be partially signed already.
+    pub fn new(tx: Transaction) -> Result<Self, Error> {
+        let mut pst = Self {
+            signers: vec![],
+            tx,
+        };
+
+        let owners: Vec<_> = pst
+            .tx
+            .inputs()
+            .iter()
+            .filter_map(|input| match input {
+                Input::CoinSigned {
+                    owner,
+                    witness_index,
+                    ..
+                } => Some((*witness_index, *owner)),
+                _ => None,
+            })
+            .collect();
+        for (witness_index, owner) in owners {
+            pst.add_signer(witness_index, owner)?;
+        }
+
+        let mut witnesses = pst.tx.witnesses().to_vec();
+        let len = witnesses.len().max(pst.signers.len());
+        pst.signers.resize(len, None);
+        witnesses.resize(len, Witness::default());
+        pst.tx.set_witnesses(witnesses);
+
+        Ok(pst)
+    }
+
+    /// Requires `signer` to sign the witness at `witness_index`, for
+    /// witnesses that aren't pointed to by a signed coin, e.g. the
+    /// signatures checked by a multisig predicate.
+    pub fn require_signer(mut self, witness_index: u8, signer: Address) -> Result<Self, Error> {
+        self.add_signer(witness_index, signer)?;
+
+        let mut witnesses = self.tx.witnesses().to_vec();
+        witnesses.resize(self.signers.len(), Witness::default());
+        self.tx.set_witnesses(witnesses);
+
+        Ok(self)
+    }
+
+    fn add_signer(&mut self, witness_index: u8, signer: Address) -> Result<(), Error> {
+        let index = witness_index as usize;
+        if self.signers.len() <= index {
+            self.signers.resize(index + 1, None);
+        }
+
+        match self.signers[index] {
+            Some(expected) if expected != signer => Err(Error::InvalidData(format!(
+                "witness {} is expected to be signed by both {} and {}",
+                witness_index, expected, signer
+            ))),
+            _ => {
+                self.signers[index] = Some(signer);
+                Ok(())
+            }
+        }
+    }
+
+    /// The transaction, with the signatures added so far.
+    pub fn transaction(&self) -> &Transaction {
+        &self.tx
+    }
+
+    /// The ID of the transaction, which is what every signer signs. It
+    /// doesn't depend on the witnesses.
+    pub fn id(&self) -> Bytes32 {
+        self.tx.id()
+    }
+
+    /// The address expected to sign each witness, by witness index. `None`
+    /// for the witnesses that aren't signatures.
+    pub fn signers(&self) -> &[Option<Address>] {
+        &self.signers
+    }
+
+    /// The addresses that still have to sign, without duplicates.
+    pub fn missing_signers(&self) -> Vec<Address> {
+        let mut missing: Vec<Address> = vec![];
+        for (signer, witness) in self.signers.iter().zip(self.tx.witnesses()) {
+            match signer {
+                Some(signer) if witness.as_ref().is_empty() && !missing.contains(signer) => {
+                    missing.push(*signer)
+                }
+                _ => {}
+            }
+        }
+        missing
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.missing_signers().is_empty()
+    }
+
+    /// Signs every witness expected from `signer`. Fails if `signer` isn't
+    /// one of the signers of the transaction.
+    pub async fn sign<S: Signer>(&mut self, signer: &S) -> Result<(), Error> {
+        let address = signer.address();
+        if !self.signers.contains(&Some(address)) {
+            return Err(Error::InvalidData(format!(
+                "{} isn't a signer of transaction {}",
+                address,
+                self.id()
+            )));
+        }
+
+        // `sign_transaction` adds the witness to the transaction it signs,
+        // while here it has to go to the indices expected from `signer`.
+        let signature = signer
+            .sign_transaction(&mut self.tx.clone())
+            .await
+            .map_err(|e| Error::WalletError(e.to_string()))?;
+
+        self.add_signature(address, signature)
+    }
+
+    /// Adds a signature made by `signer` outside of a `Signer`, e.g. on a
+    /// hardware wallet, to every witness expected from `signer`.
+    pub fn add_signature(&mut self, signer: Address, signature: Signature) -> Result<(), Error> {
+        if recover_signer(&self.id(), &signature)? != signer {
+            return Err(Error::InvalidData(format!(
+                "the signature isn't a signature of transaction {} by {}",
+                self.id(),
+                signer
+            )));
+        }
+
+        let witnesses = self
+            .signers
+            .iter()
+            .zip(self.tx.witnesses())
+            .map(|(expected, witness)| match *expected == Some(signer) {
+                true => Witness::from(signature.as_ref()),
+                false => witness.clone(),
+            })
+            .collect();
+        self.tx.set_witnesses(witnesses);
+
+        Ok(())
+    }
+
+    /// Adds the signatures of `other`, a copy of the same transaction signed
+    /// by other parties.
+    pub fn combine(&mut self, other: &Self) -> Result<(), Error> {
+        if self.id() != other.id() || self.signers != other.signers {
+            return Err(Error::InvalidData(format!(
+                "cannot combine the signatures of transaction {} with those of transaction {}",
+                self.id(),
+                other.id()
+            )));
+        }
+
+        let witnesses = self
+            .tx
+            .witnesses()
+            .iter()
+            .zip(other.tx.witnesses())
+            .map(|(own, other)| match own.as_ref().is_empty() {
+                true => other.clone(),
+                false => own.clone(),
+            })
+            .collect();
+        self.tx.set_witnesses(witnesses);
+
+        Ok(())
+    }
+
+    /// Checks that every witness holds a valid signature from its signer and
+    /// returns the signed transaction.
+    pub fn into_transaction(self) -> Result<Transaction, Error> {
+        let missing = self.missing_signers();
+        if !missing.is_empty() {
+            let missing: Vec<_> = missing.iter().map(ToString::to_string).collect();
+            return Err(Error::TransactionError(format!(
+                "transaction {} is missing the signatures of {}",
+                self.id(),
+                missing.join(", ")
+            )));
+        }
+
+        let id = self.id();
+        for (index, (signer, witness)) in self.signers.iter().zip(self.tx.witnesses()).enumerate() {
+            let signer = match signer {
+                Some(signer) => signer,
+                None => continue,
+            };
+            let signature = Bytes64::try_from(witness.as_ref())
+                .map(Signature::from)
+                .map_err(|_| {
+                    Error::TransactionError(format!("witness {} isn't a signature", index))
+                })?;
+            if recover_signer(&id, &signature)? != *signer {
+                return Err(Error::TransactionError(format!(
+                    "witness {} isn't signed by {}",
+                    index, signer
+                )));
+            }
+        }
+
+        Ok(self.tx)
+    }
+
+    /// Checks the signatures, see `into_transaction`, and submits the
+    /// transaction.
+    pub async fn submit(self, provider: &Provider) -> Result<Vec<Receipt>, Error> {
+        let tx = self.into_transaction()?;
+        Ok(provider.send_transaction(&tx).await?)
+    }
+}
+
+fn recover_signer(id: &Bytes32, signature: &Signature) -> Result<Address, Error> {
+    // Safety: `id` is the hash of the transaction, see `Wallet::sign_transaction`.
+    let message = unsafe { Message::from_bytes_unchecked(**id) };
+    let public_key = signature
+        .recover(&message)
+        .map_err(|e| Error::InvalidData(format!("invalid signature: {}", e)))?;
+
+    Ok(Address::new(*public_key.hash()))
+}
+
+/// The serialized form of a `PartiallySignedTransaction`.
+#[derive(Serialize, Deserialize)]
+struct PortableTransaction {
+    transaction: String,
+    signers: Vec<Option<String>>,
+}
+
+impl From<PartiallySignedTransaction> for PortableTransaction {
+    fn from(mut pst: PartiallySignedTransaction) -> Self {
+        Self {
+            transaction: format!("0x{}", hex::encode(pst.tx.to_bytes())),
+            signers: pst
+                .signers
+                .iter()
+                .map(|signer| signer.map(|signer| format!("{:#x}", signer)))
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<PortableTransaction> for PartiallySignedTransaction {
+    type Error = Error;
+
+    fn try_from(portable: PortableTransaction) -> Result<Self, Self::Error> {
+        let bytes = hex::decode(portable.transaction.trim_start_matches("0x"))?;
+        let tx = Transaction::from_bytes(&bytes)
+            .map_err(|e| Error::InvalidData(format!("invalid transaction: {}", e)))?;
+
+        let signers = portable
+            .signers
+            .iter()
+            .map(|signer| {
+                signer
+                    .as_ref()
+                    .map(|signer| {
+                        Address::from_str(signer).map_err(|e| {
+                            Error::InvalidData(format!("invalid signer `{}`: {}", signer, e))
+                        })
+                    })
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut pst = Self::new(tx)?;
+        for (witness_index, signer) in signers.into_iter().enumerate() {
+            let witness_index = u8::try_from(witness_index)
+                .map_err(|_| Error::InvalidData("too many signers".to_string()))?;
+            if let Some(signer) = signer {
+                pst = pst.require_signer(witness_index, signer)?;
+            }
+        }
+
+        Ok(pst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::wallet;
+    use crate::wallet::Wallet;
+    use fuel_gql_client::fuel_tx::{Output, UtxoId};
+    use fuel_gql_client::fuel_types::AssetId;
+
+    // Spends a coin of each of `owners`, the coins of `owners[i]` pointing
+    // to witness `i`.
+    fn transaction(owners: &[&Wallet]) -> Transaction {
+        let inputs = owners
+            .iter()
+            .enumerate()
+            .map(|(idx, owner)| {
+                Input::coin_signed(
+                    UtxoId::new(Bytes32::new([idx as u8; 32]), 0),
+                    owner.address(),
+                    100,
+                    AssetId::default(),
+                    idx as u8,
+                    0,
+                )
+            })
+            .collect();
+        let outputs = vec![Output::coin(Address::zeroed(), 100, AssetId::default())];
+
+        Transaction::script(0, 1_000_000, 0, 0, vec![], vec![], inputs, outputs, vec![])
+    }
+
+    #[tokio::test]
+    async fn is_signed_in_any_order_and_combined() {
+        let (alice, bob, carol) = (wallet(1), wallet(2), wallet(3));
+        let pst = PartiallySignedTransaction::new(transaction(&[&alice, &bob])).unwrap();
+        assert_eq!(pst.missing_signers(), vec![alice.address(), bob.address()]);
+
+        let mut bob_copy = pst.clone();
+        bob_copy.sign(&bob).await.unwrap();
+        assert_eq!(bob_copy.missing_signers(), vec![alice.address()]);
+
+        let mut alice_copy = pst.clone();
+        alice_copy.sign(&alice).await.unwrap();
+        assert!(alice_copy.clone().into_transaction().is_err());
+
+        alice_copy.combine(&bob_copy).unwrap();
+        assert!(alice_copy.is_complete());
+        let tx = alice_copy.into_transaction().unwrap();
+        assert_eq!(tx.witnesses().len(), 2);
+
+        let mut pst = pst;
+        assert!(pst.sign(&carol).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn requires_signers_of_predicates() {
+        let (alice, bob) = (wallet(1), wallet(2));
+        let mut pst = PartiallySignedTransaction::new(transaction(&[&alice]))
+            .unwrap()
+            .require_signer(1, bob.address())
+            .unwrap();
+
+        pst.sign(&alice).await.unwrap();
+        assert_eq!(pst.missing_signers(), vec![bob.address()]);
+
+        // A witness can't be expected from two signers.
+        assert!(pst.clone().require_signer(0, bob.address()).is_err());
+
+        // Signatures are checked when they are added.
+        let mut bob_signature_of_another_tx = transaction(&[&bob]);
+        let signature = bob
+            .sign_transaction(&mut bob_signature_of_another_tx)
+            .await
+            .unwrap();
+        assert!(pst.add_signature(bob.address(), signature).is_err());
+    }
+
+    #[tokio::test]
+    async fn leaves_the_bytecode_witness_of_a_create_transaction() {
+        let alice = wallet(1);
+        let bytecode = Witness::from(vec![0x47u8; 8]);
+        let input = Input::coin_signed(
+            UtxoId::new(Bytes32::new([1u8; 32]), 0),
+            alice.address(),
+            100,
+            AssetId::default(),
+            1,
+            0,
+        );
+        let tx = Transaction::create(
+            0,
+            1_000_000,
+            0,
+            0,
+            0,
+            Default::default(),
+            vec![],
+            vec![],
+            vec![input],
+            vec![],
+            vec![bytecode.clone()],
+        );
+
+        let mut pst = PartiallySignedTransaction::new(tx).unwrap();
+        assert_eq!(pst.signers(), &[None, Some(alice.address())]);
+        assert_eq!(pst.missing_signers(), vec![alice.address()]);
+
+        let json = serde_json::to_value(&pst).unwrap();
+        assert_eq!(
+            json["signers"],
+            serde_json::json!([null, format!("{:#x}", alice.address())])
+        );
+        let deserialized: PartiallySignedTransaction = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, pst);
+
+        pst.sign(&alice).await.unwrap();
+        let tx = pst.into_transaction().unwrap();
+        assert_eq!(tx.witnesses().len(), 2);
+        assert_eq!(tx.witnesses()[0], bytecode);
+    }
+
+    #[tokio::test]
+    async fn serializes_to_json() {
+        let (alice, bob) = (wallet(1), wallet(2));
+        let mut pst = PartiallySignedTransaction::new(transaction(&[&alice, &bob])).unwrap();
+        pst.sign(&bob).await.unwrap();
+
+        let json = serde_json::to_value(&pst).unwrap();
+        assert_eq!(
+            json["signers"],
+            serde_json::json!([
+                format!("{:#x}", alice.address()),
+                format!("{:#x}", bob.address())
+            ])
+        );
+
+        let deserialized: PartiallySignedTransaction = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, pst);
+        assert_eq!(deserialized.missing_signers(), vec![alice.address()]);
+    }
+
+    #[cfg(feature = "test-helpers")]
+    #[tokio::test]
+    async fn submits_a_transaction_spending_the_coins_of_two_wallets() {
+        use fuel_core::service::Config;
+        use fuels_core::{constants::BASE_ASSET_ID, parameters::TxParameters};
+        use fuels_test_helpers::{setup_single_asset_coins, setup_test_client};
+
+        let (mut alice, mut bob, carol) = (wallet(1), wallet(2), wallet(3));
+        let mut coins = setup_single_asset_coins(alice.address(), BASE_ASSET_ID, 1, 10);
+        coins.extend(setup_single_asset_coins(
+            bob.address(),
+            BASE_ASSET_ID,
+            1,
+            10,
+        ));
+        let (client, _) = setup_test_client(coins, Config::local_node()).await;
+        let provider = Provider::new(client);
+        alice.set_provider(provider.clone());
+        bob.set_provider(provider.clone());
+
+        let mut inputs = alice
+            .get_asset_inputs_for_amount(BASE_ASSET_ID, 10, 0)
+            .await
+            .unwrap();
+        inputs.extend(
+            bob.get_asset_inputs_for_amount(BASE_ASSET_ID, 10, 1)
+                .await
+                .unwrap(),
+        );
+        let outputs = vec![Output::coin(carol.address(), 20, BASE_ASSET_ID)];
+        let tx = provider.build_transfer_tx(&inputs, &outputs, TxParameters::default());
+
+        let mut pst = PartiallySignedTransaction::new(tx).unwrap();
+        pst.sign(&bob).await.unwrap();
+        pst.sign(&alice).await.unwrap();
+        pst.submit(&provider).await.unwrap();
+
+        let balance = provider
+            .get_asset_balance(&carol.address(), BASE_ASSET_ID)
+            .await
+            .unwrap();
+        assert_eq!(balance, 20);
+    }
+}
@@ -5,17 +5,30 @@ use std::net::SocketAddr;
 use fuel_core::service::{Config, FuelService};
 use fuel_gql_client::{
     client::{
-        schema::coin::Coin, types::TransactionResponse, FuelClient, PageDirection, PaginatedResult,
-        PaginationRequest,
+        schema::coin::{Coin, CoinStatus},
+        types::TransactionResponse,
+        FuelClient, PageDirection, PaginatedResult, PaginationRequest,
+    },
+    fuel_tx::{Input, Output, Receipt, Transaction, UtxoId},
+    fuel_types::{
+        bytes::padded_len_usize, Address, AssetId, Bytes32, ContractId, Immediate12, Immediate18,
+    },
+    fuel_vm::{
+        consts::{REG_ONE, VM_TX_MEMORY},
+        prelude::Opcode,
     },
-    fuel_tx::{Input, Output, Receipt, Transaction},
-    fuel_types::{Address, AssetId},
-    fuel_vm::{consts::REG_ONE, prelude::Opcode},
 };
 use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use thiserror::Error;
+use tokio::sync::{Mutex, MutexGuard};
 
+use crate::coin_selection::CoinSelector;
 use crate::wallet::WalletError;
+use crate::Signer;
+use fuels_core::constants::WORD_SIZE;
 use fuels_core::errors::Error;
 use fuels_core::parameters::TxParameters;
 
@@ -28,6 +41,8 @@ pub enum ProviderError {
     ClientRequestError(#[from] io::Error),
     #[error("Wallet error: {0}")]
     WalletError(String),
+    #[error("Not enough coins of asset {asset_id:#x} to cover {amount}")]
+    NotEnoughCoins { asset_id: AssetId, amount: u64 },
 }
 
 impl From<WalletError> for ProviderError {
@@ -35,26 +50,70 @@ impl From<WalletError> for ProviderError {
         ProviderError::WalletError(e.to_string())
     }
 }
+
+impl From<ProviderError> for Error {
+    fn from(e: ProviderError) -> Self {
+        match e {
+            ProviderError::TransactionRequestError(e) => Error::TransactionError(e),
+            ProviderError::ClientRequestError(e) => e.into(),
+            ProviderError::WalletError(e) => Error::WalletError(e),
+            e @ ProviderError::NotEnoughCoins { .. } => Error::TransactionError(e.to_string()),
+        }
+    }
+}
+/// How long coins stay reserved for a transaction that is never sent.
+pub const COIN_RESERVATION_TIMEOUT: Duration = Duration::from_secs(60);
+
 /// Encapsulates common client operations in the SDK.
 /// Note that you may also use `client`, which is an instance
 /// of `FuelClient`, directly, which providers a broader API.
+///
+/// The coins selected to pay for a transaction, e.g. by `get_asset_inputs_for_amount`,
+/// are reserved until the transaction is sent with `send_transaction`, so that
+/// concurrent transactions of the same owner don't select the same coins. The
+/// reservations are shared by the clones of a `Provider`.
 #[derive(Debug, Clone)]
 pub struct Provider {
     pub client: FuelClient,
+    reservations: CoinReservations,
 }
 
 impl Provider {
     pub fn new(client: FuelClient) -> Self {
-        Self { client }
+        Self {
+            client,
+            reservations: Default::default(),
+        }
     }
 
     /// Shallow wrapper on client's submit.
+    /// The transaction's coins are no longer reserved once it is submitted: they are
+    /// spent if it was accepted, and can be selected again if it was rejected.
     pub async fn send_transaction(&self, tx: &Transaction) -> io::Result<Vec<Receipt>> {
-        let tx_id = self.client.submit(tx).await?;
+        let submitted = self.client.submit(tx).await;
+        self.release_coins(tx.inputs()).await;
+        let tx_id = submitted?;
 
         self.client.receipts(&tx_id.0.to_string()).await
     }
 
+    /// Releases the coins of `inputs` reserved by `get_asset_inputs_for_amount`, e.g. those of
+    /// a transaction that won't be sent, so that they can be selected again.
+    pub async fn release_coins(&self, inputs: &[Input]) {
+        self.reservations.lock().await.release(inputs);
+    }
+
+    /// Reserves the coins of `inputs` for `owner`, e.g. coins just created that must not be
+    /// selected by the next transactions.
+    pub(crate) async fn reserve_coins(&self, owner: &Address, inputs: &[Input]) {
+        self.reservations.lock().await.reserve(*owner, inputs);
+    }
+
+    /// The coins of `owner` currently reserved for transactions that haven't been sent.
+    pub async fn reserved_coins(&self, owner: &Address) -> Vec<UtxoId> {
+        self.reservations.lock().await.reserved(owner)
+    }
+
     #[cfg(feature = "fuel-core")]
     /// Launches a local `fuel-core` network based on provided config.
     pub async fn launch(config: Config) -> Result<FuelClient, Error> {
@@ -64,9 +123,7 @@ impl Provider {
 
     /// Connects to an existing node at the given address
     pub async fn connect(socket: SocketAddr) -> Result<Provider, Error> {
-        Ok(Self {
-            client: FuelClient::from(socket),
-        })
+        Ok(Self::new(FuelClient::from(socket)))
     }
 
     /// Gets all coins owned by address `from`, *even spent ones*. This returns actual coins
@@ -100,6 +157,46 @@ impl Provider {
         Ok(coins)
     }
 
+    /// Gets all the spendable coins of asset `asset_id` owned by address `from`.
+    pub async fn get_asset_coins(
+        &self,
+        from: &Address,
+        asset_id: AssetId,
+    ) -> Result<Vec<Coin>, ProviderError> {
+        let mut coins: Vec<Coin> = vec![];
+        let asset_id = format!("{:#x}", asset_id);
+
+        let mut cursor = None;
+
+        loop {
+            let res = self
+                .client
+                .coins(
+                    &from.to_string(),
+                    Some(&asset_id),
+                    PaginationRequest {
+                        cursor: cursor.clone(),
+                        results: 9999,
+                        direction: PageDirection::Forward,
+                    },
+                )
+                .await?;
+
+            // The node drops the spent coins of a page after reading it, so a page can be
+            // short, or empty without being the last: only a missing cursor ends the coins.
+            coins.extend(res.results);
+            match res.cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(coins
+            .into_iter()
+            .filter(|coin| coin.status == CoinStatus::Unspent)
+            .collect())
+    }
+
     /// Get some spendable coins of asset `asset_id` for address `from` that add up at least to
     /// amount `amount`. The returned coins (UTXOs) are actual coins that can be spent. The number
     /// of coins (UXTOs) is minimized via an approximate solution.
@@ -109,18 +206,387 @@ impl Provider {
         asset_id: AssetId,
         amount: u64,
     ) -> io::Result<Vec<Coin>> {
+        let reserved = self.reserved_coins(from).await;
+        self.coins_to_spend(from, asset_id, amount, &reserved).await
+    }
+
+    /// Queries coins of `from` adding up at least to `amount`, other than the `excluded` ones.
+    async fn coins_to_spend(
+        &self,
+        from: &Address,
+        asset_id: AssetId,
+        amount: u64,
+        excluded: &[UtxoId],
+    ) -> io::Result<Vec<Coin>> {
+        let excluded: Vec<String> = excluded
+            .iter()
+            .map(|utxo_id| format!("{:#x}", utxo_id))
+            .collect();
         let res = self
             .client
             .coins_to_spend(
                 &from.to_string(),
                 vec![(format!("{:#x}", asset_id).as_str(), amount)],
                 None,
-                None,
+                (!excluded.is_empty()).then(|| excluded.iter().map(String::as_str).collect()),
             )
             .await?;
         Ok(res)
     }
 
+    /// Returns `Input::Coin`s spending some coins of asset `asset_id` owned by `owner` that add
+    /// up at least to amount `amount`, see `get_spendable_coins`. The inputs are signed by the
+    /// witness at `witness_index`. It only needs the owner's address, so the inputs of a
+    /// transaction can be gathered on a machine that doesn't hold the owner's key.
+    ///
+    /// The coins are reserved, i.e. they won't be selected again until the transaction
+    /// spending them is sent with `send_transaction`, they are released with `release_coins`,
+    /// or `COIN_RESERVATION_TIMEOUT` elapses.
+    pub async fn get_asset_inputs_for_amount(
+        &self,
+        owner: &Address,
+        asset_id: AssetId,
+        amount: u64,
+        witness_index: u8,
+    ) -> Result<Vec<Input>, ProviderError> {
+        self.reserve_selected(owner, |reserved| async move {
+            let spendable = self
+                .coins_to_spend(owner, asset_id, amount, &reserved)
+                .await?;
+            Ok(spendable
+                .into_iter()
+                .map(|coin| coin_input(coin, witness_index))
+                .collect())
+        })
+        .await
+    }
+
+    /// Like `get_asset_inputs_for_amount`, but the coins are chosen by `coin_selector` among
+    /// the spendable coins of `owner` returned by `get_asset_coins`, instead of by the node.
+    pub async fn get_asset_inputs_with_selector(
+        &self,
+        owner: &Address,
+        asset_id: AssetId,
+        amount: u64,
+        witness_index: u8,
+        coin_selector: &dyn CoinSelector,
+    ) -> Result<Vec<Input>, ProviderError> {
+        self.reserve_selected(owner, |reserved| async move {
+            let spendable: Vec<Coin> = self
+                .get_asset_coins(owner, asset_id)
+                .await?
+                .into_iter()
+                .filter(|coin| !reserved.contains(&coin.utxo_id.clone().into()))
+                .collect();
+
+            let selected = coin_selector
+                .select(&spendable, amount)
+                .ok_or(ProviderError::NotEnoughCoins { asset_id, amount })?;
+            let mut spendable: Vec<Option<Coin>> = spendable.into_iter().map(Some).collect();
+            Ok(selected
+                .into_iter()
+                .filter_map(|i| spendable.get_mut(i).and_then(Option::take))
+                .map(|coin| coin_input(coin, witness_index))
+                .collect())
+        })
+        .await
+    }
+
+    /// Reserves for `owner` the inputs returned by `select`, given the coins of `owner` already
+    /// reserved. The reservations aren't locked while `select` queries the node, so that the
+    /// selections of concurrent transactions don't wait for each other: if another selection
+    /// reserved one of the coins meanwhile, the coins are selected again.
+    async fn reserve_selected<F, Fut>(
+        &self,
+        owner: &Address,
+        mut select: F,
+    ) -> Result<Vec<Input>, ProviderError>
+    where
+        F: FnMut(Vec<UtxoId>) -> Fut,
+        Fut: Future<Output = Result<Vec<Input>, ProviderError>>,
+    {
+        loop {
+            let inputs = select(self.reserved_coins(owner).await).await?;
+
+            let mut reservations = self.reservations.lock().await;
+            let reserved = reservations.reserved(owner);
+            if !inputs
+                .iter()
+                .any(|input| reserved.contains(input.utxo_id()))
+            {
+                reservations.reserve(*owner, &inputs);
+                return Ok(inputs);
+            }
+        }
+    }
+
+    /// Returns the inputs spending coins of `owner` adding up at least to `amount`, chosen by
+    /// the owner's coin selector if it has one, or by the node, see
+    /// `get_asset_inputs_with_selector` and `get_asset_inputs_for_amount`.
+    pub async fn select_asset_inputs<S: Signer>(
+        &self,
+        owner: &S,
+        asset_id: AssetId,
+        amount: u64,
+        witness_index: u8,
+    ) -> Result<Vec<Input>, ProviderError> {
+        match owner.coin_selector() {
+            Some(coin_selector) => {
+                self.get_asset_inputs_with_selector(
+                    &owner.address(),
+                    asset_id,
+                    amount,
+                    witness_index,
+                    coin_selector,
+                )
+                .await
+            }
+            None => {
+                self.get_asset_inputs_for_amount(&owner.address(), asset_id, amount, witness_index)
+                    .await
+            }
+        }
+    }
+
+    /// Transfers `amount` of asset `asset_id` from the coins of `from` to `to`. The change goes
+    /// back to `from`, which signs the transaction, so any `Signer` can pay.
+    /// Returns the transaction ID that was sent and the list of receipts.
+    pub async fn transfer<S: Signer>(
+        &self,
+        from: &S,
+        to: &Address,
+        amount: u64,
+        asset_id: AssetId,
+        tx_parameters: TxParameters,
+    ) -> Result<(String, Vec<Receipt>), ProviderError> {
+        let inputs = self.select_asset_inputs(from, asset_id, amount, 0).await?;
+        let outputs = transfer_outputs(from.address(), to, amount, asset_id);
+
+        let mut tx = transfer_tx(&inputs, &outputs, tx_parameters);
+        if let Err(e) = from.sign_transaction(&mut tx).await {
+            self.release_coins(&inputs).await;
+            return Err(ProviderError::WalletError(e.to_string()));
+        }
+
+        let receipts = self.send_transaction(&tx).await?;
+
+        Ok((tx.id().to_string(), receipts))
+    }
+
+    /// Transfers `amount` of asset `asset_id` from the coins of `from` to the contract `to`,
+    /// without calling it: the contract's code doesn't run, so it can't refuse the coins. The
+    /// change goes back to `from`, which signs the transaction.
+    /// Returns the transaction ID that was sent and the list of receipts.
+    pub async fn force_transfer_to_contract<S: Signer>(
+        &self,
+        from: &S,
+        to: &ContractId,
+        amount: u64,
+        asset_id: AssetId,
+        tx_parameters: TxParameters,
+    ) -> Result<(String, Vec<Receipt>), ProviderError> {
+        let zeroes = Bytes32::zeroed();
+        let contract_input = Input::contract(UtxoId::new(zeroes, 0), zeroes, zeroes, *to);
+        let contract_output = Output::contract(0, zeroes, zeroes);
+
+        let tx = self
+            .fund_script(
+                from,
+                TxScript::force_transfer_to_contract(to, amount, asset_id),
+                vec![contract_input],
+                vec![contract_output],
+                tx_parameters,
+            )
+            .await?;
+        let receipts = self.send_transaction(&tx).await?;
+
+        if let Some(Receipt::Panic { reason, .. }) = receipts
+            .iter()
+            .find(|receipt| matches!(receipt, Receipt::Panic { .. }))
+        {
+            return Err(ProviderError::TransactionRequestError(format!(
+                "the transfer to contract {:#x} failed: {:?}",
+                to,
+                reason.reason()
+            )));
+        }
+
+        Ok((tx.id().to_string(), receipts))
+    }
+
+    /// Builds a transaction with `build`, paid for with coins of `owner` that point to the
+    /// witness at `witness_index`, and returns it unsigned. The selected coins are reserved
+    /// until the transaction is sent.
+    ///
+    /// `needed` is the amount of each asset the transaction spends besides its fee, part of which
+    /// is already covered by the coins among `provided`. Coins are selected for the rest, along
+    /// with coins of the base asset for the fee, and passed to `build`. As the byte fee depends
+    /// on the size of the transaction, so on the coins spent, the fee is only known once the
+    /// transaction is built: the coins are selected again, for the fee of the last transaction
+    /// built, until they cover it. At first, `base_amount` of the base asset is selected, an
+    /// estimate of what the transaction spends of it along with the fee.
+    pub async fn build_paid_transaction<S, F>(
+        &self,
+        owner: &S,
+        needed: &HashMap<AssetId, u64>,
+        provided: &[Input],
+        base_amount: u64,
+        witness_index: u8,
+        mut build: F,
+    ) -> Result<Transaction, ProviderError>
+    where
+        S: Signer,
+        F: FnMut(Vec<Input>) -> Transaction,
+    {
+        let needed_base = needed.get(&AssetId::default()).copied().unwrap_or_default();
+        let provided = coin_amounts(provided);
+
+        let mut base_amount = base_amount;
+        loop {
+            let mut missing: Vec<(AssetId, u64)> = needed
+                .iter()
+                .filter(|(asset_id, _)| **asset_id != AssetId::default())
+                .map(|(asset_id, amount)| (*asset_id, *amount))
+                .chain([(AssetId::default(), base_amount)])
+                .collect();
+            // The base asset comes first, so that the inputs don't depend on the map's order.
+            missing.sort();
+
+            let mut selected = vec![];
+            for (asset_id, amount) in missing {
+                let amount =
+                    amount.saturating_sub(provided.get(&asset_id).copied().unwrap_or_default());
+                if amount == 0 {
+                    continue;
+                }
+                match self
+                    .select_asset_inputs(owner, asset_id, amount, witness_index)
+                    .await
+                {
+                    Ok(coins) => selected.extend(coins),
+                    Err(e) => {
+                        self.release_coins(&selected).await;
+                        return Err(e);
+                    }
+                }
+            }
+
+            let tx = build(selected.clone());
+            let needed_with_fee = needed_base.saturating_add(max_fee(&tx));
+            let base_amount_spent = coin_amounts(tx.inputs())
+                .get(&AssetId::default())
+                .copied()
+                .unwrap_or_default();
+            if base_amount_spent >= needed_with_fee {
+                return Ok(tx);
+            }
+            self.release_coins(&selected).await;
+            base_amount = needed_with_fee;
+        }
+    }
+
+    /// Builds the transaction paying `outputs` with `inputs`, completed with coins of `owner`
+    /// for what they don't cover, including the fee, and signs it. A change output back to
+    /// `owner` is added for every asset spent. The inputs, which must be signed by the first
+    /// witness, are reserved until the transaction is sent.
+    pub(crate) async fn fund_transaction<S: Signer>(
+        &self,
+        owner: &S,
+        inputs: Vec<Input>,
+        outputs: Vec<Output>,
+        tx_parameters: TxParameters,
+    ) -> Result<Transaction, ProviderError> {
+        self.fund_script(owner, TxScript::transfer(), inputs, outputs, tx_parameters)
+            .await
+    }
+
+    /// Like `fund_transaction`, for a transaction running `script`, which also has to be paid
+    /// for what the script spends.
+    pub(crate) async fn fund_script<S: Signer>(
+        &self,
+        owner: &S,
+        script: TxScript,
+        inputs: Vec<Input>,
+        outputs: Vec<Output>,
+        tx_parameters: TxParameters,
+    ) -> Result<Transaction, ProviderError> {
+        self.reservations
+            .lock()
+            .await
+            .reserve(owner.address(), &inputs);
+
+        let mut needed: HashMap<AssetId, u64> = HashMap::new();
+        let output_amounts = outputs.iter().filter_map(|output| match output {
+            Output::Coin {
+                amount, asset_id, ..
+            } => Some((*asset_id, *amount)),
+            _ => None,
+        });
+        for (asset_id, amount) in output_amounts.chain(script.spent.iter().copied()) {
+            let total = needed.entry(asset_id).or_default();
+            *total = total.saturating_add(amount);
+        }
+        let base_amount = needed
+            .get(&AssetId::default())
+            .copied()
+            .unwrap_or_default()
+            .saturating_add(
+                tx_parameters
+                    .gas_limit
+                    .saturating_mul(tx_parameters.gas_price),
+            );
+
+        let built = self
+            .build_paid_transaction(owner, &needed, &inputs, base_amount, 0, |selected| {
+                let tx_inputs: Vec<Input> = inputs.iter().cloned().chain(selected).collect();
+                let mut tx_outputs = outputs.clone();
+                for (asset_id, _) in coin_amounts(&tx_inputs) {
+                    tx_outputs.push(Output::change(owner.address(), 0, asset_id));
+                }
+                Transaction::script(
+                    tx_parameters.gas_price,
+                    tx_parameters.gas_limit,
+                    tx_parameters.byte_price,
+                    tx_parameters.maturity,
+                    script.code.clone(),
+                    script.data.clone(),
+                    tx_inputs,
+                    tx_outputs,
+                    vec![],
+                )
+            })
+            .await;
+        let mut tx = match built {
+            Ok(tx) => tx,
+            Err(e) => {
+                self.release_coins(&inputs).await;
+                return Err(e);
+            }
+        };
+
+        if let Err(e) = owner.sign_transaction(&mut tx).await {
+            self.release_coins(tx.inputs()).await;
+            return Err(ProviderError::WalletError(e.to_string()));
+        }
+
+        Ok(tx)
+    }
+
+    /// Sends a transaction built by `fund_transaction` or `fund_script` and reports what it
+    /// cost.
+    pub(crate) async fn send_funded_transaction(
+        &self,
+        tx: &Transaction,
+    ) -> Result<SentTransaction, ProviderError> {
+        let receipts = self.send_transaction(tx).await?;
+
+        Ok(SentTransaction {
+            tx_id: tx.id().to_string(),
+            fee: paid_fee(tx, &receipts),
+            receipts,
+        })
+    }
+
     /// Craft a transaction used to transfer funds between two addresses.
     pub fn build_transfer_tx(
         &self,
@@ -128,25 +594,38 @@ impl Provider {
         outputs: &[Output],
         params: TxParameters,
     ) -> Transaction {
-        // This script contains a single Opcode that returns immediately (RET)
-        // since all this transaction does is move Inputs and Outputs around.
-        let script = Opcode::RET(REG_ONE).to_bytes().to_vec();
-        Transaction::Script {
-            gas_price: params.gas_price,
-            gas_limit: params.gas_limit,
-            byte_price: params.byte_price,
-            maturity: params.maturity,
-            receipts_root: Default::default(),
-            script,
-            script_data: vec![],
-            inputs: inputs.to_vec(),
-            outputs: outputs.to_vec(),
-            witnesses: vec![],
-            metadata: None,
-        }
+        transfer_tx(inputs, outputs, params)
     }
     // TODO: add unit tests for the balance API. This is tracked in #321.
 
+    /// Gets the balance of asset `asset_id` held by the contract `contract_id`.
+    pub async fn get_contract_balance(
+        &self,
+        contract_id: &ContractId,
+        asset_id: AssetId,
+    ) -> Result<u64, ProviderError> {
+        Ok(self
+            .client
+            .contract_balance(&contract_id.to_string(), Some(&format!("{:#x}", asset_id)))
+            .await?)
+    }
+
+    /// Gets the contract's balance of each of the given assets, keyed by their `0x`-prefixed
+    /// hex id. The node can't list the assets a contract holds, so the assets to look up have
+    /// to be given.
+    pub async fn get_contract_balances(
+        &self,
+        contract_id: &ContractId,
+        asset_ids: &[AssetId],
+    ) -> Result<HashMap<String, u64>, ProviderError> {
+        let mut balances = HashMap::with_capacity(asset_ids.len());
+        for asset_id in asset_ids {
+            let balance = self.get_contract_balance(contract_id, *asset_id).await?;
+            balances.insert(format!("{:#x}", asset_id), balance);
+        }
+        Ok(balances)
+    }
+
     /// Get the balance of all spendable coins `asset_id` for address `address`. This is different
     /// from getting coins because we are just returning a number (the sum of UTXOs amount) instead
     /// of the UTXOs.
@@ -212,3 +691,292 @@ impl Provider {
     // @todo
     // - Get block(s)
 }
+
+/// The script of a transaction built by `Provider::fund_script`, along with the amount of
+/// each asset it spends out of the transaction's coins, e.g. by transferring them to a
+/// contract.
+pub(crate) struct TxScript {
+    pub(crate) code: Vec<u8>,
+    pub(crate) data: Vec<u8>,
+    pub(crate) spent: Vec<(AssetId, u64)>,
+}
+
+impl TxScript {
+    /// The script of a transaction that only moves coins from its inputs to its outputs.
+    pub(crate) fn transfer() -> Self {
+        Self {
+            code: Opcode::RET(REG_ONE).to_bytes().to_vec(),
+            data: vec![],
+            spent: vec![],
+        }
+    }
+
+    /// The script transferring `amount` of asset `asset_id` to the contract `to`, which must
+    /// be an input of the transaction. The script data holds the contract's id, the amount
+    /// and the asset's id.
+    pub(crate) fn force_transfer_to_contract(
+        to: &ContractId,
+        amount: u64,
+        asset_id: AssetId,
+    ) -> Self {
+        let data_offset = |len_script: usize| {
+            VM_TX_MEMORY + Transaction::script_offset() + padded_len_usize(len_script)
+        };
+        let instructions = |data_offset: usize| {
+            [
+                Opcode::MOVI(0x10, data_offset as Immediate18),
+                Opcode::LW(0x11, 0x10, (ContractId::LEN / WORD_SIZE) as Immediate12),
+                Opcode::ADDI(0x12, 0x10, (ContractId::LEN + WORD_SIZE) as Immediate12),
+                Opcode::TR(0x10, 0x11, 0x12),
+                Opcode::RET(REG_ONE),
+            ]
+        };
+        // The length of the script doesn't depend on the data's offset.
+        let code: Vec<u8> = instructions(data_offset(instructions(0).len() * Opcode::LEN))
+            .iter()
+            .copied()
+            .collect();
+
+        let mut data = to.to_vec();
+        data.extend(amount.to_be_bytes());
+        data.extend(asset_id.as_ref());
+
+        Self {
+            code,
+            data,
+            spent: vec![(asset_id, amount)],
+        }
+    }
+}
+
+/// A transaction sent on behalf of a wallet, along with the fee it paid.
+#[derive(Debug, Clone)]
+pub struct SentTransaction {
+    pub tx_id: String,
+    pub receipts: Vec<Receipt>,
+    /// The fee paid in the base asset, unused gas being refunded.
+    pub fee: u64,
+}
+
+/// The most a transaction can cost in fees, paid in the base asset.
+pub fn max_fee(tx: &Transaction) -> u64 {
+    let byte_fee = (tx.metered_bytes_size() as u64).saturating_mul(tx.byte_price());
+    tx.gas_limit()
+        .saturating_mul(tx.gas_price())
+        .saturating_add(byte_fee)
+}
+
+/// The fee paid by `tx`, given the receipts of its execution: the gas it used, unused gas
+/// being refunded, and the bytes it takes.
+pub fn paid_fee(tx: &Transaction, receipts: &[Receipt]) -> u64 {
+    let gas_used = receipts
+        .iter()
+        .find_map(|receipt| match receipt {
+            Receipt::ScriptResult { gas_used, .. } => Some(*gas_used),
+            _ => None,
+        })
+        .unwrap_or_else(|| tx.gas_limit());
+    let byte_fee = (tx.metered_bytes_size() as u64).saturating_mul(tx.byte_price());
+
+    gas_used
+        .saturating_mul(tx.gas_price())
+        .saturating_add(byte_fee)
+}
+
+/// The amount of each asset spent by the coin inputs among `inputs`.
+fn coin_amounts(inputs: &[Input]) -> HashMap<AssetId, u64> {
+    let mut amounts: HashMap<AssetId, u64> = HashMap::new();
+    for input in inputs {
+        if let Input::CoinSigned {
+            amount, asset_id, ..
+        }
+        | Input::CoinPredicate {
+            amount, asset_id, ..
+        } = input
+        {
+            let total = amounts.entry(*asset_id).or_default();
+            *total = total.saturating_add(*amount);
+        }
+    }
+    amounts
+}
+
+/// The input spending `coin`, signed by the witness at `witness_index`.
+pub(crate) fn coin_input(coin: Coin, witness_index: u8) -> Input {
+    Input::coin_signed(
+        coin.utxo_id.into(),
+        coin.owner.into(),
+        coin.amount.0,
+        coin.asset_id.into(),
+        witness_index,
+        0,
+    )
+}
+
+/// The outputs of a transfer of `amount` of asset `asset_id` from `from` to `to`.
+pub(crate) fn transfer_outputs(
+    from: Address,
+    to: &Address,
+    amount: u64,
+    asset_id: AssetId,
+) -> Vec<Output> {
+    vec![
+        Output::coin(*to, amount, asset_id),
+        // Note that the change will be computed by the node.
+        // Here we only have to tell the node who will own the change and its asset ID.
+        Output::change(from, 0, asset_id),
+    ]
+}
+
+/// Crafts a transaction moving the coins of `inputs` to `outputs`. It doesn't
+/// need a provider, so that transfers can be built offline.
+pub(crate) fn transfer_tx(
+    inputs: &[Input],
+    outputs: &[Output],
+    params: TxParameters,
+) -> Transaction {
+    // This script contains a single Opcode that returns immediately (RET)
+    // since all this transaction does is move Inputs and Outputs around.
+    let script = Opcode::RET(REG_ONE).to_bytes().to_vec();
+    Transaction::Script {
+        gas_price: params.gas_price,
+        gas_limit: params.gas_limit,
+        byte_price: params.byte_price,
+        maturity: params.maturity,
+        receipts_root: Default::default(),
+        script,
+        script_data: vec![],
+        inputs: inputs.to_vec(),
+        outputs: outputs.to_vec(),
+        witnesses: vec![],
+        metadata: None,
+    }
+}
+
+/// The coins selected for transactions that haven't been sent yet, by owner, along with
+/// when they were selected. Shared by the clones of a `Provider`.
+#[derive(Debug, Clone)]
+struct CoinReservations {
+    coins: Arc<Mutex<ReservedCoins>>,
+}
+
+impl Default for CoinReservations {
+    fn default() -> Self {
+        Self {
+            coins: Arc::new(Mutex::new(ReservedCoins {
+                by_owner: HashMap::new(),
+                timeout: COIN_RESERVATION_TIMEOUT,
+            })),
+        }
+    }
+}
+
+impl CoinReservations {
+    async fn lock(&self) -> MutexGuard<'_, ReservedCoins> {
+        self.coins.lock().await
+    }
+}
+
+#[derive(Debug)]
+struct ReservedCoins {
+    by_owner: HashMap<Address, HashMap<UtxoId, Instant>>,
+    timeout: Duration,
+}
+
+impl ReservedCoins {
+    /// The coins reserved for `owner`, dropping the reservations that timed out.
+    fn reserved(&mut self, owner: &Address) -> Vec<UtxoId> {
+        let timeout = self.timeout;
+        match self.by_owner.get_mut(owner) {
+            Some(coins) => {
+                coins.retain(|_, reserved_at| reserved_at.elapsed() < timeout);
+                coins.keys().copied().collect()
+            }
+            None => vec![],
+        }
+    }
+
+    fn reserve(&mut self, owner: Address, inputs: &[Input]) {
+        let now = Instant::now();
+        let coins = self.by_owner.entry(owner).or_default();
+        for input in inputs {
+            if let Input::CoinSigned { utxo_id, .. } | Input::CoinPredicate { utxo_id, .. } = input
+            {
+                coins.insert(*utxo_id, now);
+            }
+        }
+    }
+
+    fn release(&mut self, inputs: &[Input]) {
+        for input in inputs {
+            if let Input::CoinSigned { owner, utxo_id, .. }
+            | Input::CoinPredicate { owner, utxo_id, .. } = input
+            {
+                if let Some(coins) = self.by_owner.get_mut(owner) {
+                    coins.remove(utxo_id);
+                }
+            }
+        }
+        self.by_owner.retain(|_, coins| !coins.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_gql_client::fuel_types::Bytes32;
+
+    fn coin(owner: Address, index: u8) -> Input {
+        Input::coin_signed(
+            UtxoId::new(Bytes32::zeroed(), index),
+            owner,
+            1,
+            AssetId::default(),
+            0,
+            0,
+        )
+    }
+
+    #[test]
+    fn reserved_coins_are_released_or_time_out() {
+        let (alice, bob) = (Address::new([1; 32]), Address::new([2; 32]));
+        let mut reserved = ReservedCoins {
+            by_owner: HashMap::new(),
+            timeout: COIN_RESERVATION_TIMEOUT,
+        };
+
+        reserved.reserve(alice, &[coin(alice, 0), coin(alice, 1)]);
+        reserved.reserve(bob, &[coin(bob, 2)]);
+        assert_eq!(reserved.reserved(&alice).len(), 2);
+
+        reserved.release(&[coin(alice, 0), coin(bob, 2)]);
+        assert_eq!(reserved.reserved(&alice), vec![*coin(alice, 1).utxo_id()]);
+        assert!(reserved.reserved(&bob).is_empty());
+
+        reserved.timeout = Duration::ZERO;
+        assert!(reserved.reserved(&alice).is_empty());
+    }
+
+    #[test]
+    fn only_coin_inputs_are_reserved() {
+        let alice = Address::new([1; 32]);
+        let mut reserved = ReservedCoins {
+            by_owner: HashMap::new(),
+            timeout: COIN_RESERVATION_TIMEOUT,
+        };
+
+        // Contract inputs aren't released with the coins, so they must not be reserved.
+        let contract = Input::contract(
+            UtxoId::new(Bytes32::zeroed(), 0),
+            Bytes32::zeroed(),
+            Bytes32::zeroed(),
+            Default::default(),
+        );
+        let inputs = [contract, coin(alice, 1)];
+        reserved.reserve(alice, &inputs);
+        assert_eq!(reserved.reserved(&alice), vec![*coin(alice, 1).utxo_id()]);
+
+        reserved.release(&inputs);
+        assert!(reserved.reserved(&alice).is_empty());
+    }
+}
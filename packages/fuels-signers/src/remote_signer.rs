@@ -0,0 +1,341 @@
+use crate::provider::Provider;
+use crate::Signer;
+use async_trait::async_trait;
+use fuel_crypto::{Message, Signature};
+use fuel_gql_client::{
+    fuel_tx::{Transaction, Witness},
+    fuel_types::Address,
+};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// A signer whose key is held by a signing service, e.g. a signing daemon
+/// or an HSM gateway, and never by the SDK.
+///
+/// The service is reached over HTTP with a single JSON endpoint. Messages and
+/// transactions are hashed locally, so the service only ever signs 32-byte
+/// digests (the transaction's id for transactions):
+///
+/// ```text
+/// POST <url>/sign
+/// { "address": "0x<signer's address>", "message": "0x<32-byte digest>" }
+///
+/// 200 OK
+/// { "signature": "0x<64-byte signature>" }
+/// ```
+///
+/// Any other status is an error, its body being the reason. Each signature is
+/// checked against the signer's address before being used.
+///
+/// # Examples
+///
+/// ```
+/// use fuels::prelude::*;
+/// use fuels::signers::remote_signer::RemoteSigner;
+///
+/// async fn foo(provider: Provider, address: Address) -> Result<(), Box<dyn std::error::Error>> {
+///   let signer = RemoteSigner::new("http://127.0.0.1:4001", address, Some(provider));
+///
+///   // A `RemoteSigner` is used wherever a `LocalWallet` would be.
+///   let _contract_id = Contract::deploy("out/debug/contract.bin", &signer, TxParameters::default()).await?;
+///   Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    url: String,
+    address: Address,
+    provider: Option<Provider>,
+}
+
+#[derive(Error, Debug)]
+/// Error thrown by the RemoteSigner module
+pub enum RemoteSignerError {
+    #[error("Request to the signing service failed: {0}")]
+    Request(String),
+    #[error("The signing service refused to sign ({status}): {reason}")]
+    Refused { status: u16, reason: String },
+    #[error("Invalid response from the signing service: {0}")]
+    InvalidResponse(String),
+    #[error("The signing service signed with the key of {recovered:#x} instead of {expected:#x}")]
+    WrongSigner {
+        expected: Address,
+        recovered: Address,
+    },
+    #[error("No provider was setup: make sure to set_provider in your signer!")]
+    NoProvider,
+}
+
+#[derive(Serialize)]
+struct SignRequest {
+    address: String,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct SignResponse {
+    signature: String,
+}
+
+impl RemoteSigner {
+    /// Creates a signer for the key of `address`, held by the signing service
+    /// at `url`.
+    pub fn new(url: impl Into<String>, address: Address, provider: Option<Provider>) -> Self {
+        Self {
+            url: url.into(),
+            address,
+            provider,
+        }
+    }
+
+    /// The URL of the signing service.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn get_provider(&self) -> Result<&Provider, RemoteSignerError> {
+        self.provider.as_ref().ok_or(RemoteSignerError::NoProvider)
+    }
+
+    pub fn set_provider(&mut self, provider: Provider) {
+        self.provider = Some(provider)
+    }
+
+    /// Asks the signing service to sign `message` and checks that the
+    /// signature was made with the key of the signer's address.
+    async fn sign_digest(&self, message: &Message) -> Result<Signature, RemoteSignerError> {
+        let request = SignRequest {
+            address: format!("{:#x}", self.address),
+            message: format!("{:#x}", message),
+        };
+
+        let mut response = surf::post(format!("{}/sign", self.url.trim_end_matches('/')))
+            .body_json(&request)
+            .map_err(|e| RemoteSignerError::Request(e.to_string()))?
+            .await
+            .map_err(|e| RemoteSignerError::Request(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(RemoteSignerError::Refused {
+                status: response.status().into(),
+                reason: response.body_string().await.unwrap_or_default(),
+            });
+        }
+
+        let SignResponse { signature } = response
+            .body_json()
+            .await
+            .map_err(|e| RemoteSignerError::InvalidResponse(e.to_string()))?;
+        let signature = Signature::from_str(&signature)
+            .map_err(|_| RemoteSignerError::InvalidResponse(format!("`{}`", signature)))?;
+
+        let recovered = signature
+            .recover(message)
+            .map(|public_key| Address::new(*public_key.hash()))
+            .map_err(|e| RemoteSignerError::InvalidResponse(e.to_string()))?;
+        if recovered != self.address {
+            return Err(RemoteSignerError::WrongSigner {
+                expected: self.address,
+                recovered,
+            });
+        }
+
+        Ok(signature)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Signer for RemoteSigner {
+    type Error = RemoteSignerError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, Self::Error> {
+        self.sign_digest(&Message::new(message)).await
+    }
+
+    async fn sign_transaction(&self, tx: &mut Transaction) -> Result<Signature, Self::Error> {
+        let id = tx.id();
+
+        // Safety: `id` is the hash of the transaction, see `Wallet::sign_transaction`.
+        let message = unsafe { Message::from_bytes_unchecked(*id) };
+        let sig = self.sign_digest(&message).await?;
+
+        let mut witnesses: Vec<Witness> = tx.witnesses().to_vec();
+        witnesses.push(Witness::from(sig.as_ref()));
+        tx.set_witnesses(witnesses);
+
+        Ok(sig)
+    }
+
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn provider(&self) -> Option<&Provider> {
+        self.provider.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::secret_key;
+    use crate::LocalWallet;
+    use fuel_crypto::SecretKey;
+    use fuel_gql_client::fuel_types::Bytes32;
+    use hyper::service::{make_service_fn, service_fn};
+    use hyper::{Body, Request, Response, Server, StatusCode};
+    use std::convert::Infallible;
+
+    /// Starts a stand-in signing service signing for `address` with `key`,
+    /// returning its URL.
+    fn signing_service(address: Address, key: SecretKey) -> String {
+        let make_service = make_service_fn(move |_| async move {
+            Ok::<_, Infallible>(service_fn(move |request: Request<Body>| async move {
+                let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+                if request["address"] != format!("{:#x}", address) {
+                    let mut response = Response::new(Body::from("unknown address"));
+                    *response.status_mut() = StatusCode::NOT_FOUND;
+                    return Ok::<_, Infallible>(response);
+                }
+
+                let digest = Bytes32::from_str(request["message"].as_str().unwrap()).unwrap();
+                let message = unsafe { Message::from_bytes_unchecked(*digest) };
+                let signature = Signature::sign(&key, &message);
+
+                let response = serde_json::json!({ "signature": format!("{:#x}", signature) });
+                Ok(Response::new(Body::from(response.to_string())))
+            }))
+        });
+
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let url = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+        url
+    }
+
+    #[tokio::test]
+    async fn signs_like_a_local_wallet() {
+        let wallet = LocalWallet::new_from_private_key(secret_key(1), None);
+        let url = signing_service(wallet.address(), secret_key(1));
+        let signer = RemoteSigner::new(url, wallet.address(), None);
+
+        assert_eq!(
+            signer.sign_message("my message").await.unwrap(),
+            wallet.sign_message("my message").await.unwrap()
+        );
+
+        let mut remote_tx = Transaction::default();
+        let mut local_tx = Transaction::default();
+        let signature = signer.sign_transaction(&mut remote_tx).await.unwrap();
+        assert_eq!(
+            signature,
+            wallet.sign_transaction(&mut local_tx).await.unwrap()
+        );
+        assert_eq!(remote_tx, local_tx);
+    }
+
+    #[tokio::test]
+    async fn rejects_signatures_of_another_key() {
+        let wallet = LocalWallet::new_from_private_key(secret_key(1), None);
+        let other = LocalWallet::new_from_private_key(secret_key(2), None);
+
+        // The service doesn't hold the key.
+        let url = signing_service(wallet.address(), secret_key(1));
+        let signer = RemoteSigner::new(url, other.address(), None);
+        let err = signer.sign_message("my message").await.unwrap_err();
+        assert!(matches!(
+            err,
+            RemoteSignerError::Refused { status: 404, .. }
+        ));
+
+        // The service signs with the wrong key.
+        let url = signing_service(wallet.address(), secret_key(2));
+        let signer = RemoteSigner::new(url, wallet.address(), None);
+        let err = signer.sign_message("my message").await.unwrap_err();
+        assert!(matches!(
+            err,
+            RemoteSignerError::WrongSigner { expected, recovered }
+                if expected == wallet.address() && recovered == other.address()
+        ));
+    }
+
+    #[tokio::test]
+    async fn unreachable_service() {
+        let signer = RemoteSigner::new("http://127.0.0.1:1", Address::zeroed(), None);
+
+        let err = signer.sign_message("my message").await.unwrap_err();
+        assert!(matches!(err, RemoteSignerError::Request(_)));
+    }
+
+    #[cfg(feature = "test-helpers")]
+    #[tokio::test]
+    async fn failed_signatures_release_the_coins() {
+        use fuel_core::service::Config;
+        use fuels_core::{constants::BASE_ASSET_ID, parameters::TxParameters};
+        use fuels_test_helpers::{setup_single_asset_coins, setup_test_client};
+
+        let address = LocalWallet::new_from_private_key(secret_key(1), None).address();
+        let coins = setup_single_asset_coins(address, BASE_ASSET_ID, 1, 10);
+        let (client, _) = setup_test_client(coins, Config::local_node()).await;
+        let provider = Provider::new(client);
+        let signer = RemoteSigner::new("http://127.0.0.1:1", address, Some(provider.clone()));
+
+        let err = provider
+            .transfer(
+                &signer,
+                &Address::zeroed(),
+                4,
+                BASE_ASSET_ID,
+                TxParameters::default(),
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            crate::provider::ProviderError::WalletError(_)
+        ));
+        assert!(provider.reserved_coins(&address).await.is_empty());
+    }
+
+    #[cfg(feature = "test-helpers")]
+    #[tokio::test]
+    async fn transfers_with_a_remote_key() {
+        use fuel_core::service::Config;
+        use fuels_core::{constants::BASE_ASSET_ID, parameters::TxParameters};
+        use fuels_test_helpers::{setup_single_asset_coins, setup_test_client};
+
+        let address = LocalWallet::new_from_private_key(secret_key(1), None).address();
+        let url = signing_service(address, secret_key(1));
+        let recipient = LocalWallet::new_random(None).address();
+
+        let coins = setup_single_asset_coins(address, BASE_ASSET_ID, 1, 10);
+        let (client, _) = setup_test_client(coins, Config::local_node()).await;
+        let provider = Provider::new(client);
+        let signer = RemoteSigner::new(url, address, Some(provider.clone()));
+
+        provider
+            .transfer(
+                &signer,
+                &recipient,
+                4,
+                BASE_ASSET_ID,
+                TxParameters::default(),
+            )
+            .await
+            .unwrap();
+
+        let balance = provider
+            .get_asset_balance(&recipient, BASE_ASSET_ID)
+            .await
+            .unwrap();
+        assert_eq!(balance, 4);
+    }
+}
@@ -0,0 +1,230 @@
+use crate::Signer;
+use fuel_crypto::{Hasher, Message, Signature};
+use fuel_gql_client::fuel_types::{Address, Bytes32, ContractId};
+use fuels_core::{abi_encoder::ABIEncoder, Tokenizable};
+use thiserror::Error;
+
+/// The prefix of the signed payload, telling typed data apart from other messages, as in
+/// EIP-712.
+const TYPED_DATA_PREFIX: [u8; 2] = [0x19, 0x01];
+
+/// The domain a typed message is valid in: a chain, and the contract meant to verify it.
+/// Signatures made for a domain can't be replayed in another one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Domain {
+    pub chain_id: u64,
+    pub contract_id: ContractId,
+}
+
+impl Domain {
+    pub fn new(chain_id: u64, contract_id: ContractId) -> Self {
+        Self {
+            chain_id,
+            contract_id,
+        }
+    }
+
+    /// The hash identifying the domain: `sha256(chain_id ++ contract_id)`, the chain id
+    /// being encoded as a big-endian word, as the ABI does.
+    pub fn separator(&self) -> Bytes32 {
+        let mut hasher = Hasher::default();
+        hasher.input(self.chain_id.to_be_bytes());
+        hasher.input(self.contract_id);
+        hasher.digest()
+    }
+}
+
+/// A structured message, signed along with the domain it is valid in.
+///
+/// The message is any `Tokenizable` type, e.g. a struct generated by `abigen!`, encoded
+/// with the `ABIEncoder`. The signed digest is
+///
+/// ```text
+/// sha256(0x1901 ++ domain.separator() ++ sha256(abi_encode(message)))
+/// ```
+///
+/// so a contract can recompute it from the message's fields, e.g. to check the signer of
+/// an order with `ec_recover_address`. The signature is made with `Signer::sign_message`,
+/// so any `Signer` can sign typed data.
+///
+/// # Examples
+///
+/// ```
+/// use fuels::prelude::*;
+/// use fuels::signers::typed_data::{Domain, TypedData};
+///
+/// async fn foo(wallet: LocalWallet, order_book: ContractId) -> Result<(), Box<dyn std::error::Error>> {
+///   // An order, as (asset, amount, price).
+///   let order = (BASE_ASSET_ID, 100u64, 42u64);
+///   let typed_data = TypedData::new(Domain::new(0, order_book), order)?;
+///
+///   let signature = typed_data.sign(&wallet).await?;
+///   typed_data.verify(&signature, &wallet.address())?;
+///   Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedData {
+    domain: Domain,
+    encoded_message: Vec<u8>,
+}
+
+#[derive(Error, Debug)]
+/// Error thrown by the TypedData module
+pub enum TypedDataError {
+    #[error("Failed to encode the message: {0}")]
+    Encoding(String),
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+    #[error("The message was signed by {recovered:#x} instead of {expected:#x}")]
+    WrongSigner {
+        expected: Address,
+        recovered: Address,
+    },
+}
+
+impl TypedData {
+    /// Creates the typed data of `message`, valid in `domain`.
+    pub fn new<T: Tokenizable>(domain: Domain, message: T) -> Result<Self, TypedDataError> {
+        let encoded_message = ABIEncoder::new()
+            .encode(&[message.into_token()])
+            .map_err(|e| TypedDataError::Encoding(e.to_string()))?;
+
+        Ok(Self {
+            domain,
+            encoded_message,
+        })
+    }
+
+    pub fn domain(&self) -> &Domain {
+        &self.domain
+    }
+
+    /// The message, as encoded by the `ABIEncoder`.
+    pub fn encoded_message(&self) -> &[u8] {
+        &self.encoded_message
+    }
+
+    /// The hash of the encoded message.
+    pub fn message_hash(&self) -> Bytes32 {
+        Hasher::hash(&self.encoded_message)
+    }
+
+    /// The payload given to `Signer::sign_message`, which hashes it into the signed digest.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let mut payload = TYPED_DATA_PREFIX.to_vec();
+        payload.extend(self.domain.separator().as_ref());
+        payload.extend(self.message_hash().as_ref());
+        payload
+    }
+
+    /// The digest that is signed, see `TypedData`.
+    pub fn digest(&self) -> Message {
+        Message::new(self.signing_payload())
+    }
+
+    /// Signs the typed data with `signer`.
+    pub async fn sign<S: Signer>(&self, signer: &S) -> Result<Signature, S::Error> {
+        signer.sign_message(self.signing_payload()).await
+    }
+
+    /// Recovers the address of the signer of the typed data from `signature`.
+    pub fn recover(&self, signature: &Signature) -> Result<Address, TypedDataError> {
+        let public_key = signature
+            .recover(&self.digest())
+            .map_err(|e| TypedDataError::InvalidSignature(e.to_string()))?;
+
+        Ok(Address::new(*public_key.hash()))
+    }
+
+    /// Checks that `signature` is the signature of the typed data by `address`.
+    pub fn verify(&self, signature: &Signature, address: &Address) -> Result<(), TypedDataError> {
+        let recovered = self.recover(signature)?;
+        if recovered != *address {
+            return Err(TypedDataError::WrongSigner {
+                expected: *address,
+                recovered,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::wallet;
+    use fuels_core::Token;
+
+    fn order(amount: u64) -> (ContractId, u64, bool) {
+        (ContractId::from([3u8; 32]), amount, true)
+    }
+
+    #[test]
+    fn digest_follows_the_documented_scheme() {
+        let domain = Domain::new(7, ContractId::from([1u8; 32]));
+        let typed_data = TypedData::new(domain, order(100)).unwrap();
+
+        let mut encoded_domain = 7u64.to_be_bytes().to_vec();
+        encoded_domain.extend([1u8; 32]);
+        assert_eq!(domain.separator(), Hasher::hash(&encoded_domain));
+
+        let encoded_message = ABIEncoder::new()
+            .encode(&[Token::Struct(vec![
+                Token::B256([3u8; 32]),
+                Token::U64(100),
+                Token::Bool(true),
+            ])])
+            .unwrap();
+        assert_eq!(typed_data.encoded_message(), encoded_message);
+
+        let mut payload = vec![0x19, 0x01];
+        payload.extend(domain.separator().as_ref());
+        payload.extend(Hasher::hash(&encoded_message).as_ref());
+        assert_eq!(typed_data.digest(), Message::new(&payload));
+    }
+
+    #[tokio::test]
+    async fn sign_recover_and_verify() {
+        let signer = wallet(1);
+        let domain = Domain::new(0, ContractId::from([1u8; 32]));
+        let typed_data = TypedData::new(domain, order(100)).unwrap();
+
+        let signature = typed_data.sign(&signer).await.unwrap();
+        assert_eq!(typed_data.recover(&signature).unwrap(), signer.address());
+        typed_data.verify(&signature, &signer.address()).unwrap();
+
+        let other = wallet(2);
+        let err = typed_data.verify(&signature, &other.address()).unwrap_err();
+        assert!(matches!(
+            err,
+            TypedDataError::WrongSigner { expected, recovered }
+                if expected == other.address() && recovered == signer.address()
+        ));
+    }
+
+    #[tokio::test]
+    async fn signatures_are_bound_to_the_domain_and_message() {
+        let signer = wallet(1);
+        let domain = Domain::new(0, ContractId::from([1u8; 32]));
+        let typed_data = TypedData::new(domain, order(100)).unwrap();
+        let signature = typed_data.sign(&signer).await.unwrap();
+
+        let other_chain = TypedData::new(Domain::new(1, domain.contract_id), order(100)).unwrap();
+        let other_contract =
+            TypedData::new(Domain::new(0, ContractId::from([2u8; 32])), order(100)).unwrap();
+        let other_order = TypedData::new(domain, order(101)).unwrap();
+
+        for typed_data in [other_chain, other_contract, other_order] {
+            assert!(typed_data.verify(&signature, &signer.address()).is_err());
+        }
+
+        // Signing the payload as a plain message gives the same signature.
+        let plain = signer
+            .sign_message(typed_data.signing_payload())
+            .await
+            .unwrap();
+        assert_eq!(plain, signature);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::provider::{Provider, ProviderError};
+use crate::coin_selection::CoinSelector;
+use crate::provider::{
+    coin_input, transfer_outputs, transfer_tx, Provider, ProviderError, SentTransaction,
+};
 use crate::Signer;
 use async_trait::async_trait;
 use coins_bip32::{path::DerivationPath, Bip32Error};
@@ -8,14 +11,25 @@ use eth_keystore::KeystoreError;
 use fuel_crypto::{Message, PublicKey, SecretKey, Signature};
 use fuel_gql_client::{
     client::{schema::coin::Coin, types::TransactionResponse, PaginatedResult, PaginationRequest},
-    fuel_tx::{Address, AssetId, Input, Output, Receipt, Transaction, UtxoId, Witness},
+    fuel_tx::{
+        default_parameters::{MAX_INPUTS, MAX_OUTPUTS},
+        Address, AssetId, ContractId, Input, Output, Receipt, Transaction, UtxoId, Witness,
+    },
 };
 use fuels_core::{errors::Error, parameters::TxParameters};
 use rand::{CryptoRng, Rng};
-use std::{collections::HashMap, fmt, io, path::Path, str::FromStr};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, io,
+    path::Path,
+    str::FromStr,
+    sync::Arc,
+};
 use thiserror::Error;
 
-const DEFAULT_DERIVATION_PATH_PREFIX: &str = "m/44'/1179993420'/0'/0/";
+/// The derivation path of the accounts derived from a mnemonic phrase, up to
+/// the account index.
+pub const DEFAULT_DERIVATION_PATH_PREFIX: &str = "m/44'/1179993420'/0'/0/";
 type W = English;
 
 /// A FuelVM-compatible wallet which can be used for signing, sending transactions, and more.
@@ -64,6 +78,8 @@ pub struct Wallet {
     pub(crate) address: Address,
 
     pub(crate) provider: Option<Provider>,
+
+    pub(crate) coin_selector: Option<Arc<dyn CoinSelector>>,
 }
 
 #[derive(Error, Debug)]
@@ -91,6 +107,16 @@ pub enum WalletError {
     MnemonicError(#[from] MnemonicError),
     #[error(transparent)]
     Bip32Error(#[from] Bip32Error),
+    #[error("account index {0} is in the hardened range, it must be lower than 2^31")]
+    InvalidAccountIndex(u32),
+    #[error("cannot merge coins by groups of {0}, at least 2 inputs per transaction are needed")]
+    InvalidMaxInputs(usize),
+    /// A transaction of `transfer_many` failed, after `sent` had been sent.
+    #[error("transfer failed after {} transaction(s) were sent: {source}", sent.len())]
+    TransferManyFailed {
+        sent: Vec<SentTransaction>,
+        source: Box<WalletError>,
+    },
 }
 
 impl From<WalletError> for Error {
@@ -115,6 +141,7 @@ impl Wallet {
             private_key,
             address: Address::new(*hashed),
             provider,
+            coin_selector: None,
         }
     }
 
@@ -226,6 +253,19 @@ impl Wallet {
         self.provider = Some(provider)
     }
 
+    /// Sets the strategy choosing the coins that pay for the wallet's transfers,
+    /// contract calls and deployments, instead of letting the node choose them.
+    pub fn set_coin_selector(&mut self, coin_selector: impl CoinSelector + 'static) {
+        self.coin_selector = Some(Arc::new(coin_selector))
+    }
+
+    /// Returns the wallet, choosing coins with `coin_selector`, see `set_coin_selector`.
+    /// Use it on a clone of the wallet to choose the coins of a single transaction.
+    pub fn with_coin_selector(mut self, coin_selector: impl CoinSelector + 'static) -> Self {
+        self.set_coin_selector(coin_selector);
+        self
+    }
+
     /// Transfer funds from this wallet to another `Address`.
     /// Fails if amount for asset ID is larger than address's spendable coins.
     /// Returns the transaction ID that was sent and the list of receipts.
@@ -274,26 +314,232 @@ impl Wallet {
         asset_id: AssetId,
         tx_parameters: TxParameters,
     ) -> Result<(String, Vec<Receipt>), WalletError> {
-        let inputs = self
-            .get_asset_inputs_for_amount(asset_id, amount, 0)
-            .await?;
-        let outputs: Vec<Output> = vec![
-            Output::coin(*to, amount, asset_id),
-            // Note that the change will be computed by the node.
-            // Here we only have to tell the node who will own the change and its asset ID.
-            Output::change(self.address(), 0, asset_id),
-        ];
+        Ok(self
+            .get_provider()?
+            .transfer(self, to, amount, asset_id, tx_parameters)
+            .await?)
+    }
+
+    /// Transfers, in as few transactions as possible, `amount` of asset `asset_id` to `to` for
+    /// every `(to, amount, asset_id)` of `transfers`. Each transaction pays several recipients,
+    /// possibly in several assets, with one change output per asset spent, within the limits
+    /// on the number of inputs and outputs of a transaction. The transfers are sent in order:
+    /// if one of the transactions fails, a `WalletError::TransferManyFailed` carries the
+    /// transactions sent before it.
+    pub async fn transfer_many(
+        &self,
+        transfers: &[(Address, u64, AssetId)],
+        tx_parameters: TxParameters,
+    ) -> Result<Vec<SentTransaction>, WalletError> {
+        let provider = self.get_provider()?;
+
+        let mut sent = vec![];
+        match self
+            .send_transfers(provider, transfers, tx_parameters, &mut sent)
+            .await
+        {
+            Ok(()) => Ok(sent),
+            Err(e) => Err(WalletError::TransferManyFailed {
+                sent,
+                source: Box::new(e),
+            }),
+        }
+    }
+
+    async fn send_transfers(
+        &self,
+        provider: &Provider,
+        transfers: &[(Address, u64, AssetId)],
+        tx_parameters: TxParameters,
+        sent: &mut Vec<SentTransaction>,
+    ) -> Result<(), WalletError> {
+        let mut remaining = transfers;
+        while !remaining.is_empty() {
+            let mut count = transfers_fitting_outputs(remaining);
+            let tx = loop {
+                let outputs = remaining[..count]
+                    .iter()
+                    .map(|(to, amount, asset_id)| Output::coin(*to, *amount, *asset_id))
+                    .collect();
+                let tx = provider
+                    .fund_transaction(self, vec![], outputs, tx_parameters)
+                    .await?;
+                if tx.inputs().len() <= MAX_INPUTS as usize || count == 1 {
+                    break tx;
+                }
+                // Too many coins are needed: pays fewer recipients at once.
+                provider.release_coins(tx.inputs()).await;
+                count /= 2;
+            };
+
+            sent.push(provider.send_funded_transaction(&tx).await?);
+            remaining = &remaining[count..];
+        }
+
+        Ok(())
+    }
+
+    /// Builds the unsigned transaction of a transfer from this wallet, paying with
+    /// the given coin inputs instead of fetching the wallet's coins. It doesn't
+    /// need a provider, so that transfers can be built offline, e.g. from coins
+    /// queried on another machine. The coins of the wallet should point to witness
+    /// `0`, the change goes back to the wallet.
+    ///
+    /// Once signed, e.g. with `sign_transaction` or as a `PartiallySignedTransaction`
+    /// to be passed between machines, the transaction can be submitted with
+    /// `Provider::send_transaction`.
+    pub fn build_transfer(
+        &self,
+        to: &Address,
+        amount: u64,
+        asset_id: AssetId,
+        coin_inputs: Vec<Input>,
+        tx_parameters: TxParameters,
+    ) -> Transaction {
+        let outputs = transfer_outputs(self.address(), to, amount, asset_id);
+
+        transfer_tx(&coin_inputs, &outputs, tx_parameters)
+    }
+
+    /// Transfers `amount` of asset `asset_id` from this wallet to the contract `to`, without
+    /// calling it, e.g. to fund a contract that has no payable function.
+    /// Returns the transaction ID that was sent and the list of receipts.
+    pub async fn force_transfer_to_contract(
+        &self,
+        to: &ContractId,
+        amount: u64,
+        asset_id: AssetId,
+        tx_parameters: TxParameters,
+    ) -> Result<(String, Vec<Receipt>), WalletError> {
+        Ok(self
+            .get_provider()?
+            .force_transfer_to_contract(self, to, amount, asset_id, tx_parameters)
+            .await?)
+    }
 
-        // Build transaction and sign it
-        let mut tx =
-            self.get_provider()
-                .unwrap()
-                .build_transfer_tx(&inputs, &outputs, tx_parameters);
-        let _sig = self.sign_transaction(&mut tx).await.unwrap();
+    /// Merges the spendable coins of asset `asset_id` into a single coin, spending at most
+    /// `max_inputs_per_tx` coins per transaction, and as many transactions as needed. Merging
+    /// another asset than the base asset also spends base asset coins for the fee, which
+    /// aren't counted in `max_inputs_per_tx`, which must be at least 2. Returns the
+    /// transactions sent, with their fees.
+    pub async fn consolidate_coins(
+        &self,
+        asset_id: AssetId,
+        max_inputs_per_tx: usize,
+        tx_parameters: TxParameters,
+    ) -> Result<Vec<SentTransaction>, WalletError> {
+        if max_inputs_per_tx < 2 {
+            return Err(WalletError::InvalidMaxInputs(max_inputs_per_tx));
+        }
+        let provider = self.get_provider()?;
+
+        let mut sent = vec![];
+        loop {
+            // The coins are queried again after each transaction, as the coins paying its fee
+            // may be any of them.
+            let reserved = provider.reserved_coins(&self.address).await;
+            let inputs: Vec<Input> = provider
+                .get_asset_coins(&self.address, asset_id)
+                .await?
+                .into_iter()
+                .filter(|coin| !reserved.contains(&coin.utxo_id.clone().into()))
+                .map(|coin| coin_input(coin, 0))
+                .collect();
+            if inputs.len() < 2 {
+                return Ok(sent);
+            }
 
-        let receipts = self.get_provider().unwrap().send_transaction(&tx).await?;
+            let mut count = inputs.len().min(max_inputs_per_tx.min(MAX_INPUTS as usize));
+            let tx = loop {
+                let tx = provider
+                    .fund_transaction(self, inputs[..count].to_vec(), vec![], tx_parameters)
+                    .await?;
+                let total_inputs = tx.inputs().len();
+                if total_inputs <= MAX_INPUTS as usize || count == 2 {
+                    break tx;
+                }
+                // The coins paying the fee don't fit: merges fewer coins at once.
+                provider.release_coins(tx.inputs()).await;
+                count = count
+                    .saturating_sub(total_inputs - MAX_INPUTS as usize)
+                    .max(2);
+            };
+            sent.push(provider.send_funded_transaction(&tx).await?);
+        }
+    }
 
-        Ok((tx.id().to_string(), receipts))
+    /// Creates `n` coins of `amount` of asset `asset_id` from the wallet's coins, e.g. to
+    /// send that many transactions in parallel, in as many transactions as the inputs and
+    /// outputs limits require. The fees are paid with base asset coins. Returns the
+    /// transactions sent, with their fees.
+    pub async fn split_coins(
+        &self,
+        asset_id: AssetId,
+        n: usize,
+        amount: u64,
+        tx_parameters: TxParameters,
+    ) -> Result<Vec<SentTransaction>, WalletError> {
+        let provider = self.get_provider()?;
+
+        // The coins created are reserved until all of them are, so that the next transactions
+        // don't spend them.
+        let mut created = vec![];
+        let result = self
+            .split_coins_reserving(provider, asset_id, n, amount, tx_parameters, &mut created)
+            .await;
+        provider.release_coins(&created).await;
+        result
+    }
+
+    async fn split_coins_reserving(
+        &self,
+        provider: &Provider,
+        asset_id: AssetId,
+        n: usize,
+        amount: u64,
+        tx_parameters: TxParameters,
+        created: &mut Vec<Input>,
+    ) -> Result<Vec<SentTransaction>, WalletError> {
+        // Leaves room for the change of the asset and of the base asset.
+        let max_coins = MAX_OUTPUTS as usize - 2;
+
+        let mut sent = vec![];
+        let mut left = n;
+        while left > 0 {
+            let mut count = left.min(max_coins);
+            let tx = loop {
+                let outputs = vec![Output::coin(self.address, amount, asset_id); count];
+                let tx = provider
+                    .fund_transaction(self, vec![], outputs, tx_parameters)
+                    .await?;
+                if tx.inputs().len() <= MAX_INPUTS as usize || count == 1 {
+                    break tx;
+                }
+                // Too many coins are needed: creates fewer coins at once.
+                provider.release_coins(tx.inputs()).await;
+                count /= 2;
+            };
+
+            sent.push(provider.send_funded_transaction(&tx).await?);
+            let tx_id = tx.id();
+            let coins: Vec<Input> = (0..count)
+                .map(|index| {
+                    Input::coin_signed(
+                        UtxoId::new(tx_id, index as u8),
+                        self.address,
+                        amount,
+                        asset_id,
+                        0,
+                        0,
+                    )
+                })
+                .collect();
+            provider.reserve_coins(&self.address, &coins).await;
+            created.extend(coins);
+            left -= count;
+        }
+
+        Ok(sent)
     }
 
     /// Returns a proper vector of `Input::Coin`s for the given asset ID, amount, and witness index.
@@ -308,20 +554,10 @@ impl Wallet {
         amount: u64,
         witness_index: u8,
     ) -> Result<Vec<Input>, WalletError> {
-        let spendable = self.get_spendable_coins(&asset_id, amount).await?;
-        let mut inputs = vec![];
-        for coin in spendable {
-            let input_coin = Input::coin_signed(
-                UtxoId::from(coin.utxo_id),
-                coin.owner.into(),
-                coin.amount.0,
-                asset_id,
-                witness_index,
-                0,
-            );
-            inputs.push(input_coin);
-        }
-        Ok(inputs)
+        Ok(self
+            .get_provider()?
+            .select_asset_inputs(self, asset_id, amount, witness_index)
+            .await?)
     }
 
     /// Gets all coins owned by the wallet, *even spent ones*. This returns actual coins (UTXOs).
@@ -364,6 +600,22 @@ impl Wallet {
     }
 }
 
+/// How many of the first `transfers` a single transaction can pay, leaving room for the
+/// change outputs of their assets and of the base asset.
+fn transfers_fitting_outputs(transfers: &[(Address, u64, AssetId)]) -> usize {
+    let mut assets = HashSet::from([AssetId::default()]);
+    let mut count = 0;
+    for (_, _, asset_id) in transfers {
+        let new_asset = !assets.contains(asset_id);
+        if count + 1 + assets.len() + usize::from(new_asset) > MAX_OUTPUTS as usize {
+            break;
+        }
+        assets.insert(*asset_id);
+        count += 1;
+    }
+    count
+}
+
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl Signer for Wallet {
@@ -406,6 +658,14 @@ impl Signer for Wallet {
     fn address(&self) -> Address {
         self.address
     }
+
+    fn provider(&self) -> Option<&Provider> {
+        self.provider.as_ref()
+    }
+
+    fn coin_selector(&self) -> Option<&dyn CoinSelector> {
+        self.coin_selector.as_deref()
+    }
 }
 
 impl fmt::Debug for Wallet {
@@ -520,6 +780,21 @@ mod tests {
         assert!(std::fs::remove_file(&path).is_ok());
     }
 
+    #[test]
+    fn transfers_fitting_outputs_leave_room_for_change() {
+        let asset_id = AssetId::from([1u8; 32]);
+        let base = (Address::zeroed(), 1, AssetId::default());
+        let other = (Address::zeroed(), 1, asset_id);
+
+        // One change output for the base asset.
+        assert_eq!(transfers_fitting_outputs(&[base; 300]), 254);
+        // One more for the other asset.
+        let mut transfers = vec![other];
+        transfers.extend([base; 300]);
+        assert_eq!(transfers_fitting_outputs(&transfers), 253);
+        assert_eq!(transfers_fitting_outputs(&[base, other]), 2);
+    }
+
     async fn setup() -> Provider {
         let srv = FuelService::new_node(Config::local_node()).await.unwrap();
         let client = FuelClient::from(srv.bound_address);
@@ -60,8 +60,8 @@ pub mod prelude {
     pub use super::core::errors::Error;
     pub use super::core::parameters::*;
     pub use super::core::tx::{Address, AssetId, ContractId};
+    pub use super::core::{Bits256, Identity, SizedAsciiString, Token, Tokenizable};
     pub use super::core::{Detokenize, InvalidOutputType};
-    pub use super::core::{Token, Tokenizable};
     pub use super::node::service::Config;
     pub use super::signers::provider::*;
     pub use super::signers::{LocalWallet, Signer};
@@ -61,7 +61,7 @@ pub fn the_fn() {
         .decode(&[ParamType::U64, ParamType::B256], &data)
         .expect("Failed to decode");
 
-    let a_struct = SomeEvent::new_from_tokens(&obj);
+    let a_struct = SomeEvent::new_from_tokens(&obj).expect("Failed to build the struct");
 
     assert_eq!(1020, a_struct.id);
 }
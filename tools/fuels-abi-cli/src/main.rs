@@ -1,4 +1,5 @@
 use fuels_core::code_gen::abigen::Abigen;
+use fuels_core::code_gen::TypeAttributes;
 use fuels_core::json_abi::parse_param;
 use fuels_core::json_abi::ABIParser;
 use fuels_core::ParamType;
@@ -28,6 +29,13 @@ struct Codegen {
     output: Option<PathBuf>,
     #[structopt(short = "n", long = "no-std")]
     no_std: bool,
+    /// Extra derive of the generated structs and enums, e.g. `serde::Serialize`.
+    #[structopt(long = "derive", number_of_values = 1)]
+    derives: Vec<String>,
+    /// Extra attribute of the generated structs and enums, e.g.
+    /// `#[serde(rename_all = "camelCase")]`.
+    #[structopt(long = "attribute", number_of_values = 1)]
+    attributes: Vec<String>,
 }
 
 #[derive(StructOpt, Debug)]
@@ -61,6 +69,13 @@ enum Decode {
         abi_path: String,
         function_name: String,
         data: String,
+        /// Label the decoded values with the field, variant and type names
+        /// found in the ABI.
+        #[structopt(long)]
+        labelled: bool,
+        /// Print the labelled values as JSON. Implies `--labelled`.
+        #[structopt(long)]
+        json: bool,
     },
     /// Specify types of input params inline.
     Params {
@@ -91,7 +106,13 @@ where
             abi_path,
             function_name,
             data,
-        }) => decode_call_output(&abi_path, &function_name, &data),
+            labelled,
+            json,
+        }) => match (labelled, json) {
+            (_, true) => decode_call_output_as_json(&abi_path, &function_name, &data),
+            (true, false) => decode_call_output_labelled(&abi_path, &function_name, &data),
+            (false, false) => decode_call_output(&abi_path, &function_name, &data),
+        },
     }
 }
 
@@ -101,6 +122,8 @@ fn code_gen(code: Codegen) -> anyhow::Result<String> {
         input,
         output,
         no_std,
+        derives,
+        attributes,
     } = code;
 
     let contract = fs::read_to_string(input)?;
@@ -110,6 +133,15 @@ fn code_gen(code: Codegen) -> anyhow::Result<String> {
         abi = abi.no_std();
     }
 
+    let mut type_attributes = TypeAttributes::new();
+    for derive in &derives {
+        type_attributes.add_derive(derive)?;
+    }
+    for attribute in &attributes {
+        type_attributes.add_attribute(attribute)?;
+    }
+    abi = abi.with_type_attributes(type_attributes);
+
     let c = abi.generate()?;
 
     let outfile = output.unwrap_or_else(|| "./abi_code.rs".into());
@@ -182,8 +214,72 @@ fn decode_call_output(path: &str, function_name: &str, data: &str) -> anyhow::Re
     Ok(result)
 }
 
+fn decode_call_output_labelled(
+    path: &str,
+    function_name: &str,
+    data: &str,
+) -> anyhow::Result<String> {
+    let contract = fs::read_to_string(path)?;
+
+    let data: Vec<u8> = hex::decode(data)?;
+
+    let decoded = ABIParser::new().decode_labelled(&contract, function_name, &data)?;
+
+    let mut result: String = String::new();
+    for res in decoded {
+        let format = format!("{:#}\n", res);
+        result.push_str(&format);
+    }
+
+    Ok(result)
+}
+
+fn decode_call_output_as_json(
+    path: &str,
+    function_name: &str,
+    data: &str,
+) -> anyhow::Result<String> {
+    let contract = fs::read_to_string(path)?;
+
+    let data: Vec<u8> = hex::decode(data)?;
+
+    let decoded = ABIParser::new().decode_labelled(&contract, function_name, &data)?;
+
+    Ok(serde_json::to_string_pretty(&decoded)?)
+}
+
 fn main() -> anyhow::Result<()> {
     println!("{}", execute(std::env::args())?);
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_the_output_as_nested_json() {
+        let output = execute([
+            "fuels-abi-cli",
+            "decode",
+            "function",
+            "examples/struct_output.json",
+            "returns_struct",
+            "00000000000000010000000000000001000000000000000100000000000000ff",
+            "--json",
+        ])
+        .unwrap();
+
+        let expected = r#"[
+  {
+    "id": 1,
+    "flag": true,
+    "kind": {
+      "B": 255
+    }
+  }
+]"#;
+        assert_eq!(output, expected);
+    }
+}